@@ -3,9 +3,11 @@
 
 #[cfg(test)]
 mod tests {
+    #[allow(unused_imports)]
     use fast_i18n_scan::*;
 
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn test_integration_basic() {
         // 基础集成测试
         assert!(true);
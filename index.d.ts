@@ -7,6 +7,11 @@ export declare function getVersion(): string
 export interface JsScanResult {
   keys: Array<string>
   translations: Record<string, string>
+  generatedKeys: Record<string, string>
+  namespaces: Record<string, Array<string>>
+  contexts: Record<string, string>
+  pluralKeys: Array<string>
+  pluralTranslations: Record<string, string>
   errors: Array<JsScanError>
   warnings: Array<JsScanWarning>
   stats: JsScanStats
@@ -17,6 +22,13 @@ export interface JsScanError {
   column: number
   message: string
   errorType: string
+  span?: JsSpan
+}
+export interface JsSpan {
+  startLine: number
+  startColumn: number
+  endLine: number
+  endColumn: number
 }
 export interface JsScanWarning {
   filepath: string
@@ -24,6 +36,7 @@ export interface JsScanWarning {
   column: number
   message: string
   warningType: string
+  span?: JsSpan
 }
 export interface JsScanStats {
   filesScanned: number
@@ -32,5 +45,99 @@ export interface JsScanStats {
   warningsCount: number
   processingTimeMs: number
 }
-export declare function scanFilesJs(files: Array<string>): JsScanResult
-export declare function scanFileJs(filepath: string): JsScanResult
+/** Node 中传入的配置对象，字段与配置文件（`JsConfig`）相同，未给出的项使用默认值 */
+export interface JsScanConfig {
+  input?: Array<string>
+  output?: string
+  debug?: boolean
+  sort?: boolean
+  removeUnusedKeys?: boolean
+  lngs?: Array<string>
+  defaultLng?: string
+  duplicateKeys?: 'first' | 'last' | 'error'
+  hashCollisions?: 'report' | 'disambiguate'
+  keyStrategy?: { type: 'legacy', prefix?: string } | { type: 'hash64', prefix?: string, length?: number } | { type: 'slug', prefix?: string, maxLength?: number }
+  ns?: Array<string>
+  defaultNs?: string
+  pluralStyle?: 'v3' | 'v4'
+  nsSeparator?: string
+  frontEnds?: Record<string, 'script' | 'vue' | 'svelte' | 'angular' | 'markdown' | 'mdx' | 'html'>
+  staticContent?: { codeBlocks?: boolean, scripts?: boolean }
+  exclude?: Array<string>
+  ignoreFiles?: Array<string>
+  /** 继承的配置文件，相对于当前工作目录 */
+  extends?: string | Array<string>
+  workspaces?: Array<string>
+  /** 子命令专用的配置，Node 接口按 `scan` 处理 */
+  command?: Record<string, JsScanConfig>
+  resource?: { loadPath?: string, savePath: string }
+  func?: { list?: Array<string>, hooks?: Array<string>, hocs?: Array<string>, extensions?: Array<string>, babylon?: { plugins: Array<string | [string, any]>, sourceType: string } }
+  trans?: { component?: string, i18nKey?: string, extensions?: Array<string>, babylon?: { plugins: Array<string | [string, any]>, sourceType: string } }
+}
+export declare function scanFilesJs(files: Array<string>, config?: string | JsScanConfig | undefined | null): JsScanResult
+export declare function scanFileJs(filepath: string, config?: string | JsScanConfig | undefined | null): JsScanResult
+/** 扫描进度 */
+export interface JsScanProgress {
+  scanned: number
+  total: number
+  filepath: string
+}
+/** 异步扫描文件，不阻塞主线程 */
+export declare function scanFilesAsync(files: Array<string>, config?: string | JsScanConfig | undefined | null, onProgress?: ((progress: JsScanProgress) => void) | undefined | null, signal?: AbortSignal | undefined | null): Promise<JsScanResult>
+/** 异步扫描单个文件 */
+export declare function scanFileAsync(filepath: string, config?: string | JsScanConfig | undefined | null, onProgress?: ((progress: JsScanProgress) => void) | undefined | null, signal?: AbortSignal | undefined | null): Promise<JsScanResult>
+export declare function writeResourcesJs(files: Array<string>, config: string | JsScanConfig): Array<string>
+/** 扫描与检查发现的问题，与 `lint` 子命令的输出一致 */
+export interface JsFinding {
+  severity: 'error' | 'warning'
+  kind: string
+  filepath: string
+  line: number
+  column: number
+  message: string
+  span?: JsSpan
+}
+/** 内存中的源码，`filepath` 只用于选择语言前端和报告位置 */
+export interface JsSource {
+  filepath: string
+  content: string
+}
+/** 扫描内存中的源码（如打包工具转换后的模块），不读取文件，参数顺序与 `Scanner::scan_source` 相同 */
+export declare function scanSource(filepath: string, source: string, config?: string | JsScanConfig | undefined | null): JsScanResult
+/** 一次扫描多份内存中的源码 */
+export declare function scanSources(sources: Array<JsSource>, config?: string | JsScanConfig | undefined | null): JsScanResult
+/** 检查内存中的源码（如编辑器中未保存的内容），`filepath` 只用于选择语言前端和报告位置 */
+export declare function lintSource(filepath: string, source: string, config?: string | JsScanConfig | undefined | null): Array<JsFinding>
+/** 从句子计算 key，与运行时的算法一致；给出 context 时返回 `k_xxxxxxx_context` */
+export declare function hashKey(value: string, context?: string | undefined | null): string
+export interface JsSisulizerProjectOptions {
+  /** 本地语言，默认为 `zh` */
+  nativeLang?: string
+  /** 要国际化的语言，默认为 `['en']` */
+  langs?: Array<string>
+  /** 生成 key 的策略，与配置中的 `keyStrategy` 相同，用于识别 key 上的 context 与复数后缀 */
+  keyStrategy?: { type: 'legacy', prefix?: string } | { type: 'hash64', prefix?: string, length?: number } | { type: 'slug', prefix?: string, maxLength?: number }
+}
+export interface JsProjectStats {
+  totalKeys: number
+  activeKeys: number
+  obsoletedKeys: number
+  langStats: Record<string, number>
+}
+/** Sisulizer 项目，在 Node 中的名称为 `SisulizerProject` */
+export class SisulizerProject {
+  constructor(options?: JsSisulizerProjectOptions | undefined | null)
+  /** 从目录中加载 `${lang}.json` */
+  load(sourcePath: string, lang: string): void
+  add(key: string, lang: string, translatedString: string): void
+  addWithContext(baseKey: string, context: string, lang: string, translatedString: string): void
+  get(key: string, lang: string): string | null
+  keys(): Array<string>
+  obsolete(key: string): void
+  /** 合并另一个项目的词条与废弃标记 */
+  merge(other: SisulizerProject): void
+  /** 导出指定语言（默认为本地语言）的 JSON */
+  output(lang?: string | undefined | null): string
+  outputToDirectory(outputDir: string, lang?: string | undefined | null): void
+  getStats(): JsProjectStats
+}
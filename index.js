@@ -310,8 +310,16 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { getVersion, scanFilesJs, scanFileJs } = nativeBinding
+const { getVersion, scanFilesJs, scanFileJs, scanFilesAsync, scanFileAsync, writeResourcesJs, scanSource, scanSources, lintSource, hashKey, SisulizerProject } = nativeBinding
 
 module.exports.getVersion = getVersion
 module.exports.scanFilesJs = scanFilesJs
 module.exports.scanFileJs = scanFileJs
+module.exports.scanFilesAsync = scanFilesAsync
+module.exports.scanFileAsync = scanFileAsync
+module.exports.writeResourcesJs = writeResourcesJs
+module.exports.scanSource = scanSource
+module.exports.scanSources = scanSources
+module.exports.lintSource = lintSource
+module.exports.hashKey = hashKey
+module.exports.SisulizerProject = SisulizerProject
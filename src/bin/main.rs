@@ -1,168 +1,371 @@
-use clap::{Arg, Command};
-use fast_i18n_scan::{scan_files, get_default_config};
+use clap::{Arg, ArgMatches, Command};
+use fast_i18n_scan::scan::config::ScanConfig;
+use fast_i18n_scan::{
+    config_schema, diff_results, discover_packages, findings, load_result, prune_resources, resolve_files,
+    resource_coverage, scan_files_with_config, scan_tree, scan_workspace, sync_resources, write_resources,
+    Baseline, ConfigDiagnostic, ConfigSource, LayeredConfig, ReporterKind, ScanResult, Severity,
+};
+use std::io::IsTerminal;
 use std::process;
-use glob::glob;
+use std::sync::OnceLock;
 
 #[cfg(feature = "cli")]
 use fast_i18n_scan::scan::js_config::JsConfig;
 
-// Function to expand brace patterns like *.{js,jsx,ts,tsx}
-fn expand_braces(pattern: &str) -> Vec<String> {
-    if let Some(start) = pattern.find('{') {
-        if let Some(end) = pattern.find('}') {
-            if start < end {
-                let prefix = &pattern[..start];
-                let suffix = &pattern[end + 1..];
-                let options = &pattern[start + 1..end];
-                
-                return options
-                    .split(',')
-                    .map(|opt| format!("{}{}{}", prefix, opt.trim(), suffix))
-                    .collect();
-            }
-        }
-    }
-    
-    // No braces found, return original pattern
-    vec![pattern.to_string()]
+fn files_arg() -> Arg {
+    Arg::new("files")
+        .help("Files to scan, defaults to `input` in the config file")
+        .num_args(1..)
+        .value_name("FILE")
 }
 
-fn main() {
-    let matches = Command::new("fast-i18n-scan")
+fn output_arg(help: &'static str) -> Arg {
+    Arg::new("output")
+        .short('o')
+        .long("output")
+        .help(help)
+        .value_name("FILE")
+}
+
+fn format_arg(formats: &'static [&'static str], default: &'static str) -> Arg {
+    Arg::new("format")
+        .short('f')
+        .long("format")
+        .help(format!("Output format ({})", formats.join(", ")))
+        .value_parser(clap::builder::PossibleValuesParser::new(formats))
+        .default_value(default)
+}
+
+const EXTRACT_FORMATS: &[&str] = &["json", "directory", "resource"];
+
+fn cli() -> Command {
+    Command::new("fast-i18n-scan")
         .version(fast_i18n_scan::VERSION)
         .about("Fast i18n scanning tool for JavaScript/TypeScript projects")
-        .arg(
-            Arg::new("files")
-                .help("Files to scan")
-                .num_args(1..)
-                .value_name("FILE"),
-        )
+        .after_help("Without a subcommand, behaves like `scan`.")
         .arg(
             Arg::new("config")
                 .short('c')
                 .long("config")
                 .help("Configuration file path")
-                .value_name("CONFIG_FILE"),
-        )
-        .arg(
-            Arg::new("output")
-                .short('o')
-                .long("output")
-                .help("Output file for results")
-                .value_name("FILE"),
-        )
-        .arg(
-            Arg::new("format")
-                .short('f')
-                .long("format")
-                .help("Output format (json, directory)")
-                .value_name("FORMAT")
-                .default_value("json"),
+                .value_name("CONFIG_FILE")
+                .global(true),
         )
         .arg(
             Arg::new("verbose")
                 .short('v')
                 .long("verbose")
                 .help("Verbose output")
-                .action(clap::ArgAction::SetTrue),
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
         )
-        .get_matches();
-
-    // 处理配置文件或命令行参数
-    let file_patterns: Vec<String> = if let Some(config_path) = matches.get_one::<String>("config") {
-        // 从配置文件加载
-        #[cfg(feature = "cli")]
-        {
-            match JsConfig::from_js_file(config_path) {
-                Ok(js_config) => {
-                    println!("Loaded config from: {}", config_path);
-                    if matches.get_flag("verbose") {
-                        println!("Config: {:?}", js_config);
-                    }
-                    js_config.input
-                }
-                Err(e) => {
-                    eprintln!("Error loading config file: {}", e);
-                    std::process::exit(1);
-                }
-            }
-        }
-        #[cfg(not(feature = "cli"))]
-        {
-            eprintln!("Config file support requires 'cli' feature");
-            std::process::exit(1);
-        }
-    } else {
-        // 从命令行参数
-        matches
-            .get_many::<String>("files")
-            .map(|values| values.map(|s| s.to_string()).collect())
-            .unwrap_or_else(|| {
-                eprintln!("Error: Either provide files as arguments or use --config option");
-                std::process::exit(1);
-            })
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .help("Override `input` with semicolon-separated patterns")
+                .value_name("PATTERNS")
+                .global(true),
+        )
+        .arg(
+            Arg::new("output-dir")
+                .long("output-dir")
+                .help("Override `output`, the directory resource files are written to when `resource` is not configured")
+                .value_name("DIR")
+                .global(true),
+        )
+        .arg(
+            Arg::new("append")
+                .short('a')
+                .long("append")
+                .help("With -f resource, merge into existing resource files and keep unused keys, like `sync`")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .help("Override a config value, e.g. --set lngs='zh;en' --set func.list=t; repeatable")
+                .value_name("KEY=VALUE")
+                .action(clap::ArgAction::Append)
+                .global(true),
+        )
+        .arg(
+            Arg::new("print-config")
+                .long("print-config")
+                .help("Print the effective config and where each value came from, then exit")
+                .action(clap::ArgAction::SetTrue)
+                .global(true),
+        )
+        .arg(files_arg())
+        .arg(output_arg("Output file for results"))
+        .arg(format_arg(EXTRACT_FORMATS, "json"))
+        .subcommand(
+            Command::new("scan")
+                .about("Extract keys and lint; exits 1 when the result has any error")
+                .arg(files_arg())
+                .arg(output_arg("Output file for results"))
+                .arg(format_arg(EXTRACT_FORMATS, "json")),
+        )
+        .subcommand(
+            Command::new("lint")
+                .about("Report untranslated text and other findings only")
+                .arg(files_arg())
+                .arg(output_arg("Write the report to a file instead of stdout"))
+                .arg(format_arg(&ReporterKind::NAMES, "text"))
+                .arg(
+                    Arg::new("baseline")
+                        .long("baseline")
+                        .help("Only report findings not recorded in this baseline file")
+                        .value_name("FILE"),
+                )
+                .arg(
+                    Arg::new("write-baseline")
+                        .long("write-baseline")
+                        .help("Record the current findings in the --baseline file and exit")
+                        .requires("baseline")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("fail-on-stale-baseline")
+                        .long("fail-on-stale-baseline")
+                        .help("Exit 1 when the baseline contains findings that have been fixed")
+                        .requires("baseline")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("exit-zero")
+                        .long("exit-zero")
+                        .help("Exit 0 even when there are errors, e.g. to upload a SARIF report in CI")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("max-warnings")
+                        .long("max-warnings")
+                        .help("Exit 1 when there are more warnings than this")
+                        .value_name("N")
+                        .value_parser(clap::value_parser!(usize)),
+                ),
+        )
+        .subcommand(
+            Command::new("extract")
+                .about("Write extracted keys; lint findings do not affect the exit code")
+                .arg(files_arg())
+                .arg(output_arg("Output file for results"))
+                .arg(format_arg(EXTRACT_FORMATS, "json")),
+        )
+        .subcommand(
+            Command::new("sync")
+                .about("Merge extracted keys into the language packs at resource.savePath, keeping obsolete keys")
+                .arg(files_arg()),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Print translation coverage per language and namespace")
+                .arg(files_arg())
+                .arg(format_arg(&["table", "json"], "table"))
+                .arg(
+                    Arg::new("min-coverage")
+                        .long("min-coverage")
+                        .help("Exit 1 when any language is below this percentage")
+                        .value_name("PERCENT")
+                        .value_parser(clap::value_parser!(f64)),
+                ),
+        )
+        .subcommand(
+            Command::new("prune")
+                .about("Remove keys that are no longer used from the language packs")
+                .arg(files_arg())
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("List obsolete keys without writing")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("check")
+                        .long("check")
+                        .help("Like --dry-run, but exit 1 when there are obsolete keys")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Compare two scan results (JSON files from -f json) or two directory trees")
+                .arg(Arg::new("before").help("Scan result JSON or directory before the change").required(true))
+                .arg(Arg::new("after").help("Scan result JSON or directory after the change").required(true))
+                .arg(format_arg(&["table", "json"], "table"))
+                .arg(output_arg("Output file for the diff"))
+                .arg(
+                    Arg::new("exit-code")
+                        .long("exit-code")
+                        .help("Exit 1 when there are differences")
+                        .action(clap::ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("workspace")
+                .about("Scan every package listed in `workspaces` with its own config and write its language packs")
+                .arg(format_arg(&["table", "json"], "table"))
+                .arg(output_arg("Output file for the report"))
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Report without writing language packs")
+                        .action(clap::ArgAction::SetTrue),
+                )
+                .arg(
+                    Arg::new("min-coverage")
+                        .long("min-coverage")
+                        .help("Exit 1 when any language is below this percentage across all packages")
+                        .value_name("PERCENT")
+                        .value_parser(clap::value_parser!(f64)),
+                ),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Inspect the configuration file")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("check")
+                        .about("Validate the file given with -c; exits 1 when there are errors")
+                        .arg(format_arg(&["text", "json"], "text")),
+                )
+                .subcommand(
+                    Command::new("schema")
+                        .about("Print the JSON Schema of the configuration file")
+                        .arg(output_arg("Output file for the schema")),
+                ),
+        )
+}
+
+fn format_config_diagnostic(config_path: &str, diagnostic: &ConfigDiagnostic) -> String {
+    let location = match (diagnostic.line, diagnostic.column) {
+        (Some(line), Some(column)) => format!("{}:{}:{}", config_path, line, column),
+        _ => config_path.to_string(),
     };
+    format!(
+        "{}  {}  {}  {}",
+        location,
+        diagnostic.severity.as_str(),
+        diagnostic.path,
+        diagnostic.message
+    )
+}
 
+/// 当前子命令名，用于选择配置文件中 `command.<name>` 的配置
+static COMMAND: OnceLock<String> = OnceLock::new();
+
+fn main() {
+    let matches = cli().get_matches();
+    let _ = COMMAND.set(matches.subcommand_name().unwrap_or("scan").to_string());
+
+    let code = match matches.subcommand() {
+        Some(("scan", sub)) => run_scan(sub),
+        Some(("lint", sub)) => run_lint(sub),
+        Some(("extract", sub)) => run_extract(sub),
+        Some(("sync", sub)) => run_sync(sub),
+        Some(("stats", sub)) => run_stats(sub),
+        Some(("prune", sub)) => run_prune(sub),
+        Some(("diff", sub)) => run_diff(sub),
+        Some(("workspace", sub)) => run_workspace(sub),
+        Some(("config", sub)) => match sub.subcommand() {
+            Some(("check", check)) => run_config_check(check),
+            Some(("schema", schema)) => run_config_schema(schema),
+            _ => unreachable!("subcommand is required"),
+        },
+        _ => run_scan(&matches),
+    };
+    process::exit(code);
+}
+
+/// 打印错误并退出
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    process::exit(1);
+}
+
+/// 按默认值、`-c` 配置文件、`command.<子命令>`、环境变量、`--input`/`--set` 的顺序合并配置，返回配置与其中的 `input`
+fn load_config(matches: &ArgMatches) -> (ScanConfig, Vec<String>) {
     let verbose = matches.get_flag("verbose");
+    let config_path = matches.get_one::<String>("config");
 
-    // Expand glob patterns to actual file paths
-    let mut files = Vec::new();
-    for pattern in &file_patterns {
-        if verbose {
-            println!("Processing pattern: '{}'", pattern);
+    let mut layered = LayeredConfig::new();
+    if let Some(config_path) = config_path {
+        layered = layered
+            .with_file(config_path)
+            .unwrap_or_else(|e| fail(format!("Error loading config file: {}", e)));
+    }
+    layered = layered
+        .with_command(COMMAND.get().map_or("scan", String::as_str))
+        .with_env(std::env::vars())
+        .unwrap_or_else(|e| fail(format!("Error in environment variable {}", e)));
+    if let Some(input) = matches.get_one::<String>("input") {
+        let patterns: Vec<&str> = input.split(';').map(str::trim).filter(|p| !p.is_empty()).collect();
+        layered = layered.with_value("input", serde_json::json!(patterns), ConfigSource::Cli("--input".to_string()));
+    }
+    if let Some(output) = matches.get_one::<String>("output-dir") {
+        layered = layered.with_value("output", serde_json::json!(output), ConfigSource::Cli("--output-dir".to_string()));
+    }
+    for assignment in matches.get_many::<String>("set").into_iter().flatten() {
+        layered = layered
+            .with_override(assignment, ConfigSource::Cli("--set".to_string()))
+            .unwrap_or_else(|e| fail(format!("Error in --set {}", e)));
+    }
+
+    if matches.get_flag("print-config") {
+        print!("{}", layered.describe());
+        for (source, diagnostic) in layered.unknown_keys() {
+            eprintln!("{}", format_config_diagnostic(&source.to_string(), &diagnostic));
         }
-        
-        // First check if it's a direct file path
-        let path = std::path::Path::new(pattern);
-        if path.is_file() {
-            files.push(pattern.clone());
-            if verbose {
-                println!("  Found direct file: {}", pattern);
-            }
-            continue;
+        process::exit(0);
+    }
+
+    let loaded = layered
+        .build()
+        .unwrap_or_else(|e| fail(format!("Error in configuration: {}", e)));
+    // 命令行指定了文件时，`input` 为空不是问题
+    let has_files = matches.try_get_many::<String>("files").ok().flatten().is_some();
+    let label = config_path.map_or("config", |path| path.as_str());
+    for diagnostic in loaded.check() {
+        if !(has_files && diagnostic.path == "input") {
+            eprintln!("{}", format_config_diagnostic(label, &diagnostic));
         }
-        
-        // Handle brace expansion manually (e.g., *.{js,jsx,ts,tsx})
-        let expanded_patterns = expand_braces(pattern);
-        
-        for expanded_pattern in expanded_patterns {
-            if verbose {
-                println!("  Trying expanded pattern: '{}'", expanded_pattern);
-            }
-            
-            // Try glob expansion
-            match glob(&expanded_pattern) {
-                Ok(paths) => {
-                    let mut pattern_matches = 0;
-                    for entry in paths {
-                        match entry {
-                            Ok(path) => {
-                                if path.is_file() {
-                                    // Accept all files that match the pattern
-                                    files.push(path.to_string_lossy().to_string());
-                                    pattern_matches += 1;
-                                    if verbose {
-                                        println!("    Found file: {}", path.display());
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                if verbose {
-                                    eprintln!("    Error processing path in pattern '{}': {}", expanded_pattern, e);
-                                }
-                            }
-                        }
-                    }
-                    if verbose {
-                        println!("    Pattern '{}' matched {} files", expanded_pattern, pattern_matches);
-                    }
-                }
-                Err(e) => {
-                    if verbose {
-                        eprintln!("  Invalid glob pattern '{}': {}", expanded_pattern, e);
-                    }
-                }
-            }
+    }
+    if verbose {
+        if let Some(config_path) = config_path {
+            eprintln!("Loaded config from: {}", config_path);
+        }
+        eprintln!("Config: {:?}", loaded.config);
+    }
+    (loaded.config.to_scan_config(), loaded.config.input)
+}
+
+/// 加载配置并展开文件模式，命令行指定的文件优先于配置中的 `input`
+fn resolve_input(matches: &ArgMatches) -> (ScanConfig, Vec<String>) {
+    let (scan_config, input) = load_config(matches);
+    let file_patterns: Vec<String> = matches
+        .get_many::<String>("files")
+        .map(|values| values.map(|s| s.to_string()).collect())
+        .unwrap_or(input);
+    if file_patterns.is_empty() {
+        fail("Error: Either provide files as arguments or use --config option".to_string());
+    }
+    let verbose = matches.get_flag("verbose");
+    let files = expand_patterns(&file_patterns, &scan_config, verbose);
+
+    if verbose {
+        eprintln!("Found {} files matching patterns: {:?}", files.len(), file_patterns);
+        eprintln!("Using configuration: {:?}", scan_config);
+    }
+    (scan_config, files)
+}
+
+/// 展开文件模式，没有匹配的文件时打印排查提示并退出
+fn expand_patterns(file_patterns: &[String], scan_config: &ScanConfig, verbose: bool) -> Vec<String> {
+    let files = resolve_files(file_patterns, scan_config);
+    if verbose {
+        for file in &files {
+            eprintln!("  Found file: {}", file);
         }
     }
 
@@ -172,8 +375,9 @@ fn main() {
         eprintln!("  1. The patterns are correct");
         eprintln!("  2. The files exist in the current directory");
         eprintln!("  3. Try using quotes around the pattern to prevent shell expansion");
-        eprintln!("  4. Use --verbose flag to see debug information");
-        
+        eprintln!("  4. Check `exclude`, .i18nignore and the ignore files listed in `ignoreFiles`");
+        eprintln!("  5. Use --verbose flag to see debug information");
+
         // Always show current directory contents for debugging
         eprintln!("\nCurrent directory contents:");
         if let Ok(entries) = std::fs::read_dir(".") {
@@ -184,126 +388,366 @@ fn main() {
                 }
             }
         }
-        
+
         // Also try to show some example patterns
         eprintln!("\nExample patterns:");
         eprintln!("  fast-i18n-scan \"src/**/*.js\"");
         eprintln!("  fast-i18n-scan \"src/**/*.{{js,jsx,ts,tsx}}\"");
         eprintln!("  fast-i18n-scan \"**/*.js\" \"**/*.ts\"");
-        
+        eprintln!("  fast-i18n-scan \"src/**/!(*.test).{{js,ts}}\" \"!src/legacy/**\"");
+
         process::exit(1);
     }
 
-    if verbose {
-        println!("Found {} files matching patterns: {:?}", files.len(), file_patterns);
-        println!("Using configuration: {:?}", get_default_config());
+    files
+}
+
+/// 加载配置、展开文件并扫描
+fn scan(matches: &ArgMatches) -> (ScanConfig, ScanResult) {
+    let (scan_config, files) = resolve_input(matches);
+    let result = scan_files_with_config(&files, scan_config.clone())
+        .unwrap_or_else(|e| fail(format!("Scan failed: {}", e)));
+
+    if matches.get_flag("verbose") {
+        eprintln!("Scan completed successfully!");
+        eprintln!("Files scanned: {}", result.stats.files_scanned);
+        eprintln!("Keys found: {}", result.stats.keys_found);
+        eprintln!("Errors: {}", result.stats.errors_count);
+        eprintln!("Warnings: {}", result.stats.warnings_count);
+        eprintln!("Processing time: {}ms", result.stats.processing_time_ms);
     }
+    (scan_config, result)
+}
 
-    match scan_files(&files) {
-        Ok(result) => {
-            if verbose {
-                println!("Scan completed successfully!");
-                println!("Files scanned: {}", result.stats.files_scanned);
-                println!("Keys found: {}", result.stats.keys_found);
-                println!("Errors: {}", result.stats.errors_count);
-                println!("Warnings: {}", result.stats.warnings_count);
-                println!("Processing time: {}ms", result.stats.processing_time_ms);
+/// 写入 `-o` 指定的文件，未指定时输出到标准输出
+fn emit(matches: &ArgMatches, output: &str) {
+    match matches.get_one::<String>("output") {
+        Some(output_file) => {
+            if let Err(e) = std::fs::write(output_file, output) {
+                fail(format!("Failed to write output file: {}", e));
+            }
+            if matches.get_flag("verbose") {
+                eprintln!("Results written to: {}", output_file);
             }
+        }
+        None => println!("{}", output.trim_end()),
+    }
+}
 
-            // Output results
-            let output_format = matches.get_one::<String>("format").unwrap();
-            match output_format.as_str() {
-                "json" => {
-                    let json_output = serde_json::to_string_pretty(&result).unwrap_or_else(|e| {
-                        eprintln!("Failed to serialize results: {}", e);
-                        process::exit(1);
-                    });
-
-                    if let Some(output_file) = matches.get_one::<String>("output") {
-                        if let Err(e) = std::fs::write(output_file, json_output) {
-                            eprintln!("Failed to write output file: {}", e);
-                            process::exit(1);
-                        }
-                        if verbose {
-                            println!("Results written to: {}", output_file);
-                        }
-                    } else {
-                        println!("{}", json_output);
-                    }
+/// 按 `-f` 输出扫描结果（json、directory 或 resource）
+fn write_scan_output(matches: &ArgMatches, scan_config: &ScanConfig, result: &ScanResult) {
+    let verbose = matches.get_flag("verbose");
+    let output_format = matches.get_one::<String>("format").unwrap();
+    match output_format.as_str() {
+        "json" => {
+            let json_output = serde_json::to_string_pretty(result)
+                .unwrap_or_else(|e| fail(format!("Failed to serialize results: {}", e)));
+            emit(matches, &json_output);
+        }
+        "directory" => {
+            // 简化的目录输出格式
+            let default_output = "./i18n_output".to_string();
+            let output_dir = matches.get_one::<String>("output").unwrap_or(&default_output);
+
+            // 创建输出目录结构
+            for dir in ["context", "source"] {
+                if let Err(e) = std::fs::create_dir_all(format!("{}/{}", output_dir, dir)) {
+                    fail(format!("Failed to create {} directory: {}", dir, e));
                 }
-                "directory" => {
-                    // 简化的目录输出格式
-                    let default_output = "./i18n_output".to_string();
-                    let output_dir = matches.get_one::<String>("output")
-                        .unwrap_or(&default_output);
-                    
-                    // 创建输出目录结构
-                    std::fs::create_dir_all(format!("{}/context", output_dir))
-                        .map_err(|e| {
-                            eprintln!("Failed to create context directory: {}", e);
-                            process::exit(1);
-                        }).ok();
-                    
-                    std::fs::create_dir_all(format!("{}/source", output_dir))
-                        .map_err(|e| {
-                            eprintln!("Failed to create source directory: {}", e);
-                            process::exit(1);
-                        }).ok();
-                    
-                    // 创建 context.json
-                    let context = serde_json::json!({
-                        "active_keys": result.keys.len(),
-                        "generated_at": chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
-                        "language": "zh",
-                        "obsoleted_keys": 0,
-                        "project_info": {
-                            "available_languages": ["zh"],
-                            "native_language": "zh"
-                        },
-                        "total_keys": result.keys.len()
-                    });
-                    
-                    if let Err(e) = std::fs::write(
-                        format!("{}/context/context.json", output_dir),
-                        serde_json::to_string_pretty(&context).unwrap()
-                    ) {
-                        eprintln!("Failed to write context.json: {}", e);
-                        process::exit(1);
-                    }
-                    
-                    // 创建 zh.json
-                    let zh_json = serde_json::to_string_pretty(&result.translations)
-                        .unwrap_or_else(|e| {
-                            eprintln!("Failed to serialize translations: {}", e);
-                            process::exit(1);
-                        });
-                    
-                    if let Err(e) = std::fs::write(
-                        format!("{}/source/zh.json", output_dir),
-                        zh_json
-                    ) {
-                        eprintln!("Failed to write zh.json: {}", e);
-                        process::exit(1);
-                    }
-                    
+            }
+
+            // 创建 context.json
+            let context = serde_json::json!({
+                "active_keys": result.keys.len(),
+                "generated_at": chrono::Utc::now().format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+                "language": scan_config.default_lng,
+                "obsoleted_keys": 0,
+                "project_info": {
+                    "available_languages": scan_config.lngs,
+                    "native_language": scan_config.default_lng
+                },
+                "total_keys": result.keys.len()
+            });
+
+            if let Err(e) = std::fs::write(
+                format!("{}/context/context.json", output_dir),
+                serde_json::to_string_pretty(&context).unwrap(),
+            ) {
+                fail(format!("Failed to write context.json: {}", e));
+            }
+
+            // 创建默认语言的词条文件
+            let lng_json = serde_json::to_string_pretty(&result.translations)
+                .unwrap_or_else(|e| fail(format!("Failed to serialize translations: {}", e)));
+
+            if let Err(e) = std::fs::write(
+                format!("{}/source/{}.json", output_dir, scan_config.default_lng),
+                lng_json,
+            ) {
+                fail(format!("Failed to write {}.json: {}", scan_config.default_lng, e));
+            }
+
+            if verbose {
+                eprintln!("Results written to directory: {}", output_dir);
+            }
+        }
+        "resource" => {
+            // 按配置中的 resource.savePath 写入各语言词条文件，`--append` 时保留已有词条
+            let write = if matches.get_flag("append") { sync_resources } else { write_resources };
+            match write(scan_config, result) {
+                Ok(paths) => {
                     if verbose {
-                        println!("Results written to directory: {}", output_dir);
+                        for path in paths {
+                            eprintln!("Resource written to: {}", path);
+                        }
                     }
                 }
-                _ => {
-                    eprintln!("Unsupported output format: {}", output_format);
-                    process::exit(1);
-                }
+                Err(e) => fail(format!("Failed to write resources: {}", e)),
+            }
+        }
+        _ => fail(format!("Unsupported output format: {}", output_format)),
+    }
+}
+
+/// scan：输出扫描结果，存在任何错误时退出码为 1
+fn run_scan(matches: &ArgMatches) -> i32 {
+    let (scan_config, result) = scan(matches);
+    write_scan_output(matches, &scan_config, &result);
+    i32::from(result.stats.errors_count > 0)
+}
+
+/// extract：输出扫描结果，只有提取错误（解析失败、key 冲突等）时退出码为 1
+fn run_extract(matches: &ArgMatches) -> i32 {
+    let (scan_config, result) = scan(matches);
+    write_scan_output(matches, &scan_config, &result);
+    i32::from(result.errors.iter().any(|e| !e.error_type.is_lint()))
+}
+
+/// lint：只输出错误与警告，有错误或警告超过 `--max-warnings` 时退出码为 1
+///
+/// 指定 `--baseline` 时只报告基线之外的问题，已修复的基线条目输出到标准错误。
+fn run_lint(matches: &ArgMatches) -> i32 {
+    let (_, result) = scan(matches);
+    let mut findings = findings(&result);
+
+    let mut stale = false;
+    if let Some(path) = matches.get_one::<String>("baseline") {
+        if matches.get_flag("write-baseline") {
+            let baseline = Baseline::from_findings(&findings);
+            baseline
+                .save(path)
+                .unwrap_or_else(|e| fail(format!("Failed to write baseline: {}", e)));
+            eprintln!("Baseline written to {} ({} findings)", path, findings.len());
+            return 0;
+        }
+        let baseline = Baseline::load(path).unwrap_or_else(|e| fail(format!("Failed to load baseline: {}", e)));
+        let matched = baseline.apply(&findings);
+        for entry in &matched.fixed {
+            eprintln!("fixed  {}  {}  {} (x{})", entry.filepath, entry.kind, entry.message, entry.count);
+        }
+        if !matched.fixed.is_empty() {
+            eprintln!(
+                "{} baseline entr{} fixed; run with --write-baseline to update {}",
+                matched.fixed.len(),
+                if matched.fixed.len() == 1 { "y" } else { "ies" },
+                path
+            );
+        }
+        if matches.get_flag("verbose") {
+            eprintln!("{} finding(s) suppressed by the baseline", matched.suppressed);
+        }
+        stale = !matched.fixed.is_empty();
+        findings = matched.new;
+    }
+
+    let format = matches.get_one::<String>("format").unwrap();
+    let kind = ReporterKind::from_name(format).unwrap_or_else(|| fail(format!("Unsupported output format: {}", format)));
+    // 只在直接输出到终端时着色，NO_COLOR 可关闭颜色
+    let color = matches.get_one::<String>("output").is_none()
+        && std::io::stdout().is_terminal()
+        && std::env::var_os("NO_COLOR").is_none();
+    let report = kind.build(color).report(&findings);
+    if !report.is_empty() || matches.get_one::<String>("output").is_some() {
+        emit(matches, &report);
+    }
+
+    if matches.get_flag("exit-zero") {
+        return 0;
+    }
+    let errors = findings.iter().filter(|finding| finding.severity == Severity::Error).count();
+    let warnings = findings.len() - errors;
+    let too_many_warnings = matches
+        .get_one::<usize>("max-warnings")
+        .is_some_and(|max| warnings > *max);
+    let stale_baseline = stale && matches.get_flag("fail-on-stale-baseline");
+    i32::from(errors > 0 || too_many_warnings || stale_baseline)
+}
+
+/// sync：把扫描结果合并进语言包
+fn run_sync(matches: &ArgMatches) -> i32 {
+    let (scan_config, result) = scan(matches);
+    match sync_resources(&scan_config, &result) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Synced: {}", path);
             }
+            0
+        }
+        Err(e) => fail(format!("Failed to sync resources: {}", e)),
+    }
+}
+
+/// stats：输出各语言的翻译覆盖率，低于 `--min-coverage` 时退出码为 1
+fn run_stats(matches: &ArgMatches) -> i32 {
+    let (scan_config, result) = scan(matches);
+    let coverage = resource_coverage(&scan_config, &result)
+        .unwrap_or_else(|e| fail(format!("Failed to compute coverage: {}", e)));
 
-            // Exit with error code if there are errors
-            if result.stats.errors_count > 0 {
-                process::exit(1);
+    if matches.get_one::<String>("format").unwrap() == "json" {
+        let json_output = serde_json::to_string_pretty(&coverage)
+            .unwrap_or_else(|e| fail(format!("Failed to serialize coverage: {}", e)));
+        println!("{}", json_output);
+    } else {
+        println!("{:<8} {:<16} {:>10} {:>8}", "lng", "ns", "translated", "coverage");
+        for item in &coverage {
+            println!(
+                "{:<8} {:<16} {:>10} {:>7.1}%",
+                item.lng,
+                item.ns,
+                format!("{}/{}", item.translated, item.total),
+                item.percent()
+            );
+        }
+    }
+
+    let below = matches
+        .get_one::<f64>("min-coverage")
+        .is_some_and(|min| coverage.iter().any(|item| item.percent() < *min));
+    i32::from(below)
+}
+
+/// prune：移除语言包中不再使用的词条，`--check` 时有待移除的词条则退出码为 1
+fn run_prune(matches: &ArgMatches) -> i32 {
+    let check = matches.get_flag("check");
+    let dry_run = check || matches.get_flag("dry-run");
+    let (scan_config, result) = scan(matches);
+    let pruned = prune_resources(&scan_config, &result, dry_run)
+        .unwrap_or_else(|e| fail(format!("Failed to prune resources: {}", e)));
+
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    for resource in &pruned {
+        println!("{} {} key(s) from {}", verb, resource.removed.len(), resource.path);
+        for key in &resource.removed {
+            println!("  - {}", key);
+        }
+    }
+    i32::from(check && !pruned.is_empty())
+}
+
+/// 扫描结果来源：目录按配置扫描，其他路径作为 `-f json` 的输出读取
+fn load_diff_side(path: &str, config: &ScanConfig) -> ScanResult {
+    let result = if std::path::Path::new(path).is_dir() {
+        scan_tree(path, config)
+    } else {
+        load_result(path)
+    };
+    result.unwrap_or_else(|e| fail(format!("Failed to load {}: {}", path, e)))
+}
+
+/// diff：比较两次扫描，`--exit-code` 时有差异则退出码为 1
+fn run_diff(matches: &ArgMatches) -> i32 {
+    let (config, _) = load_config(matches);
+    let before = load_diff_side(matches.get_one::<String>("before").unwrap(), &config);
+    let after = load_diff_side(matches.get_one::<String>("after").unwrap(), &config);
+    let diff = diff_results(&before, &after);
+
+    let output = match matches.get_one::<String>("format").unwrap().as_str() {
+        "json" => serde_json::to_string_pretty(&diff)
+            .unwrap_or_else(|e| fail(format!("Failed to serialize diff: {}", e))),
+        _ => diff.to_table(),
+    };
+    emit(matches, &output);
+
+    i32::from(matches.get_flag("exit-code") && !diff.is_empty())
+}
+
+/// workspace：按各包的配置分别扫描并写入语言包，任一包有错误或覆盖率低于 `--min-coverage` 时退出码为 1
+fn run_workspace(matches: &ArgMatches) -> i32 {
+    let config_path = matches
+        .get_one::<String>("config")
+        .unwrap_or_else(|| fail("Error: workspace requires --config".to_string()));
+    #[cfg(feature = "cli")]
+    {
+        let loaded = JsConfig::load(config_path).unwrap_or_else(|e| fail(format!("Error loading config file: {}", e)));
+        if loaded.config.workspaces.is_empty() {
+            fail(format!("Error: {} has no `workspaces`", config_path));
+        }
+        let packages = discover_packages(config_path, &loaded.config.workspaces, &loaded.config.to_scan_config())
+            .unwrap_or_else(|e| fail(format!("Error loading package config: {}", e)));
+        for package in &packages {
+            for diagnostic in &package.diagnostics {
+                eprintln!("{}", format_config_diagnostic(&package.config_path, diagnostic));
+            }
+            if matches.get_flag("verbose") {
+                eprintln!("Package {}: {:?}", package.name, package.config);
             }
         }
-        Err(e) => {
-            eprintln!("Scan failed: {}", e);
-            process::exit(1);
+
+        let report = scan_workspace(&packages, !matches.get_flag("dry-run"))
+            .unwrap_or_else(|e| fail(format!("Workspace scan failed: {}", e)));
+        let output = match matches.get_one::<String>("format").unwrap().as_str() {
+            "json" => serde_json::to_string_pretty(&report)
+                .unwrap_or_else(|e| fail(format!("Failed to serialize report: {}", e))),
+            _ => report.to_table(),
+        };
+        emit(matches, &output);
+
+        let below = matches
+            .get_one::<f64>("min-coverage")
+            .is_some_and(|min| report.totals.iter().any(|total| total.percent() < *min));
+        i32::from(report.errors_count() > 0 || below)
+    }
+    #[cfg(not(feature = "cli"))]
+    {
+        let _ = config_path;
+        fail("Config file support requires 'cli' feature".to_string())
+    }
+}
+
+/// config check：检查 `-c` 指定的配置文件，有错误时退出码为 1
+fn run_config_check(matches: &ArgMatches) -> i32 {
+    let config_path = matches
+        .get_one::<String>("config")
+        .unwrap_or_else(|| fail("Error: config check requires --config".to_string()));
+    #[cfg(feature = "cli")]
+    {
+        let loaded = JsConfig::load(config_path).unwrap_or_else(|e| fail(format!("Error loading config file: {}", e)));
+        let diagnostics = loaded.check();
+
+        if matches.get_one::<String>("format").unwrap() == "json" {
+            let json_output = serde_json::to_string_pretty(&diagnostics)
+                .unwrap_or_else(|e| fail(format!("Failed to serialize diagnostics: {}", e)));
+            println!("{}", json_output);
+        } else {
+            for diagnostic in &diagnostics {
+                println!("{}", format_config_diagnostic(config_path, diagnostic));
+            }
+            let errors = diagnostics.iter().filter(|d| d.is_error()).count();
+            println!("{} error(s), {} warning(s)", errors, diagnostics.len() - errors);
         }
+        i32::from(diagnostics.iter().any(|d| d.is_error()))
+    }
+    #[cfg(not(feature = "cli"))]
+    {
+        let _ = config_path;
+        fail("Config file support requires 'cli' feature".to_string())
     }
-}
\ No newline at end of file
+}
+
+/// config schema：输出配置文件的 JSON Schema
+fn run_config_schema(matches: &ArgMatches) -> i32 {
+    let schema = serde_json::to_string_pretty(&config_schema())
+        .unwrap_or_else(|e| fail(format!("Failed to serialize schema: {}", e)));
+    emit(matches, &schema);
+    0
+}
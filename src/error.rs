@@ -0,0 +1,90 @@
+//! 库中所有可失败操作的错误类型
+//!
+//! 库本身不向终端输出，也不会退出进程，错误一律通过 `Result` 返回，由调用方（CLI、Node 绑定等）决定如何展示。
+
+use std::path::PathBuf;
+
+/// 库的错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// 读写指定文件失败
+    #[error("{}: {source}", path.display())]
+    File {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    /// 配置文件内容不合法
+    #[error("{0}")]
+    Config(String),
+
+    /// 数据文件内容不合法，如非中文语言包中出现中文、基线或扫描结果格式错误
+    #[error("{}: {message}", path.display())]
+    Resource { path: PathBuf, message: String },
+
+    /// 远程接口返回的内容不符合预期
+    #[error("Unexpected response from {url}: {message}")]
+    Response { url: String, message: String },
+
+    /// workspace 中某个包处理失败
+    #[error("{package}: {source}")]
+    Package {
+        package: String,
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// 扫描被 `CancellationToken` 取消
+    #[error("Scan cancelled")]
+    Cancelled,
+}
+
+impl Error {
+    /// 标明出错的 workspace 包
+    pub fn in_package(self, package: &str) -> Error {
+        Error::Package {
+            package: package.to_string(),
+            source: Box::new(self),
+        }
+    }
+
+    /// 带上文件路径的 IO 错误
+    pub fn file(path: impl Into<PathBuf>, source: std::io::Error) -> Error {
+        Error::File {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+/// 库中使用的 `Result`
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_messages() {
+        let missing = std::fs::read_to_string("/no/such/file.json").unwrap_err();
+        let error = Error::file("/no/such/file.json", missing);
+        assert!(error.to_string().starts_with("/no/such/file.json: "));
+        assert!(std::error::Error::source(&error).is_some());
+
+        let error = Error::Resource {
+            path: "en.json".into(),
+            message: "bad resource".to_string(),
+        };
+        assert_eq!(error.to_string(), "en.json: bad resource");
+    }
+}
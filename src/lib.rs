@@ -5,13 +5,21 @@
 
 #[cfg(all(feature = "napi", not(target_family = "wasm")))]
 use napi_derive::napi;
+#[cfg(feature = "napi")]
+use napi::bindgen_prelude::{AbortSignal, AsyncTask, Either, FromNapiValue};
+#[cfg(feature = "napi")]
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+#[cfg(feature = "napi")]
+use napi::{Env, JsFunction, JsObject, NapiRaw, Task};
 
+pub mod error;
 pub mod scan;
 pub mod utils;
 
 // Re-export commonly used items
 pub use scan::*;
 pub use utils::*;
+pub use error::{Error, Result};
 
 /// Library version
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -27,13 +35,27 @@ pub fn create_scanner() -> scan::Scanner {
 }
 
 /// Scan files for i18n keys
-pub fn scan_files(files: &[String]) -> Result<scan::ScanResult, Box<dyn std::error::Error>> {
+pub fn scan_files(files: &[String]) -> Result<scan::ScanResult> {
     let mut scanner = create_scanner();
     scanner.scan_files(files)
 }
 
+/// Load scan configuration from a JavaScript config file (e.g. `i18n.config.js`)
+pub fn load_config(path: &str) -> Result<scan::config::ScanConfig> {
+    Ok(scan::js_config::JsConfig::from_js_file(path)?.to_scan_config())
+}
+
+/// Scan files for i18n keys with the given configuration
+pub fn scan_files_with_config(
+    files: &[String],
+    config: scan::config::ScanConfig,
+) -> Result<scan::ScanResult> {
+    let mut scanner = scan::Scanner::with_config(config);
+    scanner.scan_files(files)
+}
+
 /// Quick scan function for single file
-pub fn scan_file(filepath: &str) -> Result<scan::ScanResult, Box<dyn std::error::Error>> {
+pub fn scan_file(filepath: &str) -> Result<scan::ScanResult> {
     scan_files(&[filepath.to_string()])
 }
 
@@ -49,6 +71,11 @@ pub fn get_version() -> String {
 pub struct JsScanResult {
     pub keys: Vec<String>,
     pub translations: std::collections::HashMap<String, String>,
+    pub generated_keys: std::collections::HashMap<String, String>,
+    pub namespaces: std::collections::HashMap<String, Vec<String>>,
+    pub contexts: std::collections::HashMap<String, String>,
+    pub plural_keys: Vec<String>,
+    pub plural_translations: std::collections::HashMap<String, String>,
     pub errors: Vec<JsScanError>,
     pub warnings: Vec<JsScanWarning>,
     pub stats: JsScanStats,
@@ -62,6 +89,28 @@ pub struct JsScanError {
     pub column: u32,
     pub message: String,
     pub error_type: String,
+    pub span: Option<JsSpan>,
+}
+
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsSpan {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+#[cfg(feature = "napi")]
+impl From<scan::Span> for JsSpan {
+    fn from(span: scan::Span) -> Self {
+        Self {
+            start_line: span.start_line,
+            start_column: span.start_column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+        }
+    }
 }
 
 #[cfg(feature = "napi")]
@@ -72,6 +121,7 @@ pub struct JsScanWarning {
     pub column: u32,
     pub message: String,
     pub warning_type: String,
+    pub span: Option<JsSpan>,
 }
 
 #[cfg(feature = "napi")]
@@ -90,12 +140,18 @@ impl From<scan::ScanResult> for JsScanResult {
         Self {
             keys: result.keys,
             translations: result.translations,
+            generated_keys: result.generated_keys,
+            namespaces: result.namespaces,
+            contexts: result.contexts,
+            plural_keys: result.plural_keys,
+            plural_translations: result.plural_translations,
             errors: result.errors.into_iter().map(|e| JsScanError {
                 filepath: e.filepath,
                 line: e.line,
                 column: e.column,
                 message: e.message,
                 error_type: format!("{:?}", e.error_type),
+                span: e.span.map(JsSpan::from),
             }).collect(),
             warnings: result.warnings.into_iter().map(|w| JsScanWarning {
                 filepath: w.filepath,
@@ -103,6 +159,7 @@ impl From<scan::ScanResult> for JsScanResult {
                 column: w.column,
                 message: w.message,
                 warning_type: format!("{:?}", w.warning_type),
+                span: w.span.map(JsSpan::from),
             }).collect(),
             stats: JsScanStats {
                 files_scanned: result.stats.files_scanned as u32,
@@ -115,10 +172,98 @@ impl From<scan::ScanResult> for JsScanResult {
     }
 }
 
+/// Node 中传入的配置对象，字段与配置文件（`JsConfig`）相同，未给出的项使用默认值
+#[cfg(feature = "napi")]
+#[napi(object)]
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsScanConfig {
+    pub input: Option<Vec<String>>,
+    pub output: Option<String>,
+    pub debug: Option<bool>,
+    pub sort: Option<bool>,
+    pub remove_unused_keys: Option<bool>,
+    pub lngs: Option<Vec<String>>,
+    pub default_lng: Option<String>,
+    #[napi(ts_type = "'first' | 'last' | 'error'")]
+    pub duplicate_keys: Option<String>,
+    #[napi(ts_type = "'report' | 'disambiguate'")]
+    pub hash_collisions: Option<String>,
+    #[napi(
+        ts_type = "{ type: 'legacy', prefix?: string } | { type: 'hash64', prefix?: string, length?: number } | { type: 'slug', prefix?: string, maxLength?: number }"
+    )]
+    pub key_strategy: Option<serde_json::Value>,
+    pub ns: Option<Vec<String>>,
+    pub default_ns: Option<String>,
+    #[napi(ts_type = "'v3' | 'v4'")]
+    pub plural_style: Option<String>,
+    pub ns_separator: Option<String>,
+    #[napi(ts_type = "Record<string, 'script' | 'vue' | 'svelte' | 'angular' | 'markdown' | 'mdx' | 'html'>")]
+    pub front_ends: Option<std::collections::HashMap<String, String>>,
+    #[napi(ts_type = "{ codeBlocks?: boolean, scripts?: boolean }")]
+    pub static_content: Option<serde_json::Value>,
+    pub exclude: Option<Vec<String>>,
+    pub ignore_files: Option<Vec<String>>,
+    /// 继承的配置文件，相对于当前工作目录
+    #[napi(ts_type = "string | Array<string>")]
+    pub extends: Option<serde_json::Value>,
+    pub workspaces: Option<Vec<String>>,
+    /// 子命令专用的配置，Node 接口按 `scan` 处理
+    #[napi(ts_type = "Record<string, JsScanConfig>")]
+    pub command: Option<std::collections::HashMap<String, serde_json::Value>>,
+    #[napi(ts_type = "{ loadPath?: string, savePath: string }")]
+    pub resource: Option<serde_json::Value>,
+    #[napi(
+        ts_type = "{ list?: Array<string>, hooks?: Array<string>, hocs?: Array<string>, extensions?: Array<string>, babylon?: { plugins: Array<string | [string, any]>, sourceType: string } }"
+    )]
+    pub func: Option<serde_json::Value>,
+    #[napi(
+        ts_type = "{ component?: string, i18nKey?: string, extensions?: Array<string>, babylon?: { plugins: Array<string | [string, any]>, sourceType: string } }"
+    )]
+    pub trans: Option<serde_json::Value>,
+}
+
+#[cfg(feature = "napi")]
+fn to_napi_error(e: Error) -> napi::Error {
+    napi::Error::from_reason(e.to_string())
+}
+
+/// 配置文件路径或配置对象，都未给出时使用默认配置
+#[cfg(feature = "napi")]
+fn resolve_config(config: Option<Either<String, JsScanConfig>>) -> napi::Result<scan::config::ScanConfig> {
+    match config {
+        None => Ok(get_default_config()),
+        Some(Either::A(path)) => load_config(&path).map_err(to_napi_error),
+        Some(Either::B(config)) => {
+            let mut value = serde_json::to_value(config)?;
+            let mut parents = Vec::new();
+            if let Some(object) = value.as_object_mut() {
+                object.retain(|_, value| !value.is_null());
+                parents = match object.remove("extends") {
+                    Some(serde_json::Value::String(parent)) => vec![parent],
+                    Some(extends) => serde_json::from_value(extends)?,
+                    None => Vec::new(),
+                };
+            }
+            let mut layered = scan::LayeredConfig::new();
+            for parent in parents {
+                layered = layered.with_file(parent).map_err(to_napi_error)?;
+            }
+            let loaded = layered
+                .with_object(value, scan::ConfigSource::Api)
+                .with_command("scan")
+                .build()
+                .map_err(to_napi_error)?;
+            Ok(loaded.config.to_scan_config())
+        }
+    }
+}
+
 #[cfg(feature = "napi")]
 #[napi]
-pub fn scan_files_js(files: Vec<String>) -> napi::Result<JsScanResult> {
-    match scan_files(&files) {
+pub fn scan_files_js(files: Vec<String>, config: Option<Either<String, JsScanConfig>>) -> napi::Result<JsScanResult> {
+    let config = resolve_config(config)?;
+    match scan_files_with_config(&files, config) {
         Ok(result) => Ok(result.into()),
         Err(e) => Err(napi::Error::from_reason(e.to_string())),
     }
@@ -126,10 +271,341 @@ pub fn scan_files_js(files: Vec<String>) -> napi::Result<JsScanResult> {
 
 #[cfg(feature = "napi")]
 #[napi]
-pub fn scan_file_js(filepath: String) -> napi::Result<JsScanResult> {
-    match scan_file(&filepath) {
-        Ok(result) => Ok(result.into()),
-        Err(e) => Err(napi::Error::from_reason(e.to_string())),
+pub fn scan_file_js(filepath: String, config: Option<Either<String, JsScanConfig>>) -> napi::Result<JsScanResult> {
+    scan_files_js(vec![filepath], config)
+}
+
+/// 扫描进度
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsScanProgress {
+    pub scanned: u32,
+    pub total: u32,
+    pub filepath: String,
+}
+
+/// 在 libuv 线程池中执行的扫描
+#[cfg(feature = "napi")]
+pub struct ScanTask {
+    files: Vec<String>,
+    config: scan::config::ScanConfig,
+    on_progress: Option<ThreadsafeFunction<JsScanProgress, ErrorStrategy::Fatal>>,
+    cancellation: scan::CancellationToken,
+}
+
+#[cfg(feature = "napi")]
+impl Task for ScanTask {
+    type Output = scan::ScanResult;
+    type JsValue = JsScanResult;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let mut scanner = scan::Scanner::with_config(self.config.clone());
+        if let Some(on_progress) = self.on_progress.clone() {
+            scanner.set_progress_callback(std::sync::Arc::new(move |progress: &scan::ScanProgress| {
+                let progress = JsScanProgress {
+                    scanned: progress.scanned as u32,
+                    total: progress.total as u32,
+                    filepath: progress.filepath.clone(),
+                };
+                on_progress.call(progress, ThreadsafeFunctionCallMode::NonBlocking);
+            }));
+        }
+        scanner.set_cancellation_token(self.cancellation.clone());
+        scanner.scan_files(&self.files).map_err(|e| match e {
+            Error::Cancelled => napi::Error::new(napi::Status::Cancelled, "AbortError".to_string()),
+            e => to_napi_error(e),
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output.into())
+    }
+}
+
+/// `signal` 触发 abort 时取消扫描：尚未开始时由 napi 取消任务，已开始时在下一个文件前停止
+#[cfg(feature = "napi")]
+fn scan_task(
+    env: Env,
+    files: Vec<String>,
+    config: Option<Either<String, JsScanConfig>>,
+    on_progress: Option<ThreadsafeFunction<JsScanProgress, ErrorStrategy::Fatal>>,
+    signal: Option<JsObject>,
+) -> napi::Result<AsyncTask<ScanTask>> {
+    let config = resolve_config(config)?;
+    let cancellation = scan::CancellationToken::new();
+    let abort_signal = match signal {
+        Some(signal) => {
+            if signal.get_named_property::<bool>("aborted")? {
+                cancellation.cancel();
+            }
+            let token = cancellation.clone();
+            let on_abort = env.create_function_from_closure("onAbort", move |_| {
+                token.cancel();
+                Ok(())
+            })?;
+            let add_event_listener: JsFunction = signal.get_named_property("addEventListener")?;
+            add_event_listener.call(
+                Some(&signal),
+                &[env.create_string("abort")?.into_unknown(), on_abort.into_unknown()],
+            )?;
+            // signal 是当前调用传入的 JS 对象，与 env 属于同一环境
+            Some(unsafe { AbortSignal::from_napi_value(env.raw(), signal.raw())? })
+        }
+        None => None,
+    };
+    let task = ScanTask {
+        files,
+        config,
+        on_progress,
+        cancellation,
+    };
+    Ok(AsyncTask::with_optional_signal(task, abort_signal))
+}
+
+/// 异步扫描文件，不阻塞主线程
+#[cfg(feature = "napi")]
+#[napi(
+    ts_args_type = "files: Array<string>, config?: string | JsScanConfig | undefined | null, onProgress?: ((progress: JsScanProgress) => void) | undefined | null, signal?: AbortSignal | undefined | null",
+    ts_return_type = "Promise<JsScanResult>"
+)]
+pub fn scan_files_async(
+    env: Env,
+    files: Vec<String>,
+    config: Option<Either<String, JsScanConfig>>,
+    on_progress: Option<ThreadsafeFunction<JsScanProgress, ErrorStrategy::Fatal>>,
+    signal: Option<JsObject>,
+) -> napi::Result<AsyncTask<ScanTask>> {
+    scan_task(env, files, config, on_progress, signal)
+}
+
+/// 异步扫描单个文件
+#[cfg(feature = "napi")]
+#[napi(
+    ts_args_type = "filepath: string, config?: string | JsScanConfig | undefined | null, onProgress?: ((progress: JsScanProgress) => void) | undefined | null, signal?: AbortSignal | undefined | null",
+    ts_return_type = "Promise<JsScanResult>"
+)]
+pub fn scan_file_async(
+    env: Env,
+    filepath: String,
+    config: Option<Either<String, JsScanConfig>>,
+    on_progress: Option<ThreadsafeFunction<JsScanProgress, ErrorStrategy::Fatal>>,
+    signal: Option<JsObject>,
+) -> napi::Result<AsyncTask<ScanTask>> {
+    scan_task(env, vec![filepath], config, on_progress, signal)
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+pub fn write_resources_js(files: Vec<String>, config: Either<String, JsScanConfig>) -> napi::Result<Vec<String>> {
+    let config = resolve_config(Some(config))?;
+    let result = scan_files_with_config(&files, config.clone()).map_err(to_napi_error)?;
+    match scan::writer::write_resources(&config, &result) {
+        Ok(paths) => Ok(paths),
+        Err(e) => Err(to_napi_error(e)),
+    }
+}
+
+/// 扫描与检查发现的问题，与 `lint` 子命令的输出一致
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsFinding {
+    #[napi(ts_type = "'error' | 'warning'")]
+    pub severity: String,
+    pub kind: String,
+    pub filepath: String,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+    pub span: Option<JsSpan>,
+}
+
+#[cfg(feature = "napi")]
+impl From<scan::Finding> for JsFinding {
+    fn from(finding: scan::Finding) -> Self {
+        Self {
+            severity: finding.severity.as_str().to_string(),
+            kind: finding.kind,
+            filepath: finding.filepath,
+            line: finding.line,
+            column: finding.column,
+            message: finding.message,
+            span: finding.span.map(JsSpan::from),
+        }
+    }
+}
+
+/// 内存中的源码，`filepath` 只用于选择语言前端和报告位置
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsSource {
+    pub filepath: String,
+    pub content: String,
+}
+
+/// 扫描内存中的源码（如打包工具转换后的模块），不读取文件，参数顺序与 `Scanner::scan_source` 相同
+#[cfg(feature = "napi")]
+#[napi]
+pub fn scan_source(
+    filepath: String,
+    source: String,
+    config: Option<Either<String, JsScanConfig>>,
+) -> napi::Result<JsScanResult> {
+    let mut scanner = scan::Scanner::with_config(resolve_config(config)?);
+    let result = scanner.scan_source(&filepath, &source).map_err(to_napi_error)?;
+    Ok(result.into())
+}
+
+/// 一次扫描多份内存中的源码
+#[cfg(feature = "napi")]
+#[napi]
+pub fn scan_sources(
+    sources: Vec<JsSource>,
+    config: Option<Either<String, JsScanConfig>>,
+) -> napi::Result<JsScanResult> {
+    let sources: Vec<(String, String)> = sources
+        .into_iter()
+        .map(|source| (source.filepath, source.content))
+        .collect();
+    let mut scanner = scan::Scanner::with_config(resolve_config(config)?);
+    let result = scanner.scan_sources(&sources).map_err(to_napi_error)?;
+    Ok(result.into())
+}
+
+/// 检查内存中的源码（如编辑器中未保存的内容），`filepath` 只用于选择语言前端和报告位置
+#[cfg(feature = "napi")]
+#[napi]
+pub fn lint_source(
+    filepath: String,
+    source: String,
+    config: Option<Either<String, JsScanConfig>>,
+) -> napi::Result<Vec<JsFinding>> {
+    let mut scanner = scan::Scanner::with_config(resolve_config(config)?);
+    let findings = scanner.lint_source(&filepath, &source).map_err(to_napi_error)?;
+    Ok(findings.into_iter().map(JsFinding::from).collect())
+}
+
+/// 从句子计算 key，与运行时的算法一致；给出 context 时返回 `k_xxxxxxx_context`
+#[cfg(feature = "napi")]
+#[napi(js_name = "hashKey")]
+pub fn hash_key_js(value: String, context: Option<String>) -> String {
+    scan::hash_key_simple(&value, context.as_deref())
+}
+
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsSisulizerProjectOptions {
+    /// 本地语言，默认为 `zh`
+    pub native_lang: Option<String>,
+    /// 要国际化的语言，默认为 `['en']`
+    pub langs: Option<Vec<String>>,
+    /// 生成 key 的策略，与配置中的 `keyStrategy` 相同，用于识别 key 上的 context 与复数后缀
+    #[napi(
+        ts_type = "{ type: 'legacy', prefix?: string } | { type: 'hash64', prefix?: string, length?: number } | { type: 'slug', prefix?: string, maxLength?: number }"
+    )]
+    pub key_strategy: Option<serde_json::Value>,
+}
+
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsProjectStats {
+    pub total_keys: u32,
+    pub active_keys: u32,
+    pub obsoleted_keys: u32,
+    pub lang_stats: std::collections::HashMap<String, u32>,
+}
+
+/// Sisulizer 项目，在 Node 中的名称为 `SisulizerProject`
+#[cfg(feature = "napi")]
+#[napi(js_name = "SisulizerProject")]
+pub struct JsSisulizerProject {
+    inner: scan::SisulizerProject,
+}
+
+#[cfg(feature = "napi")]
+#[napi]
+impl JsSisulizerProject {
+    #[napi(constructor)]
+    pub fn new(options: Option<JsSisulizerProjectOptions>) -> napi::Result<Self> {
+        let defaults = scan::SisulizerProjectOptions::default();
+        let options = match options {
+            Some(options) => Some(scan::SisulizerProjectOptions {
+                native_lang: options.native_lang.unwrap_or(defaults.native_lang),
+                langs: options.langs.unwrap_or(defaults.langs),
+                key_strategy: match options.key_strategy {
+                    Some(key_strategy) => serde_json::from_value(key_strategy)?,
+                    None => defaults.key_strategy,
+                },
+            }),
+            None => None,
+        };
+        Ok(Self {
+            inner: scan::SisulizerProject::new(options),
+        })
+    }
+
+    /// 从目录中加载 `${lang}.json`
+    #[napi]
+    pub fn load(&mut self, source_path: String, lang: String) -> napi::Result<()> {
+        self.inner.load(&source_path, &lang).map_err(to_napi_error)
+    }
+
+    #[napi]
+    pub fn add(&mut self, key: String, lang: String, translated_string: String) {
+        self.inner.add(&key, &lang, &translated_string);
+    }
+
+    #[napi]
+    pub fn add_with_context(&mut self, base_key: String, context: String, lang: String, translated_string: String) {
+        self.inner.add_with_context(&base_key, &context, &lang, &translated_string);
+    }
+
+    #[napi]
+    pub fn get(&self, key: String, lang: String) -> Option<String> {
+        self.inner.get(&key, &lang)
+    }
+
+    #[napi]
+    pub fn keys(&self) -> Vec<String> {
+        self.inner.keys()
+    }
+
+    #[napi]
+    pub fn obsolete(&mut self, key: String) {
+        self.inner.obsolete(&key);
+    }
+
+    /// 合并另一个项目的词条与废弃标记
+    #[napi]
+    pub fn merge(&mut self, other: &JsSisulizerProject) {
+        self.inner.merge(&other.inner);
+    }
+
+    /// 导出指定语言（默认为本地语言）的 JSON
+    #[napi]
+    pub fn output(&self, lang: Option<String>) -> String {
+        self.inner.output(lang.as_deref())
+    }
+
+    #[napi]
+    pub fn output_to_directory(&self, output_dir: String, lang: Option<String>) -> napi::Result<()> {
+        self.inner
+            .output_to_directory(&output_dir, lang.as_deref())
+            .map_err(to_napi_error)
+    }
+
+    #[napi]
+    pub fn get_stats(&self) -> JsProjectStats {
+        let stats = self.inner.get_stats();
+        JsProjectStats {
+            total_keys: stats.total_keys as u32,
+            active_keys: stats.active_keys as u32,
+            obsoleted_keys: stats.obsoleted_keys as u32,
+            lang_stats: stats
+                .lang_stats
+                .into_iter()
+                .map(|(lang, count)| (lang, count as u32))
+                .collect(),
+        }
     }
 }
 
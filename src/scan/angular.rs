@@ -0,0 +1,286 @@
+use regex::Regex;
+
+use super::config::ScanConfig;
+use super::front_end::FrontEnd;
+use super::parse_func_from_string_by_babel::{offset_to_line_column, KeyOccurrence, ParseOptions};
+use super::vue::element_close;
+use super::zh_linter::ZhLinter;
+
+/// Angular 模板前端（ngx-translate 与 Angular 内置 i18n）
+///
+/// - 管道：`{{ 'key' | translate }}`、`[title]="'key' | translate"`
+/// - 指令：`<p translate>key</p>`、`<p translate="key">`、`<p [translate]="'key'">`
+/// - i18n 属性：`<h1 i18n="描述@@id">文本</h1>`、`<img title="文本" i18n-title>`，
+///   有 `@@id` 时以 id 作为 key，否则以文本作为 key，文本同时作为默认值
+pub struct AngularFrontEnd;
+
+impl FrontEnd for AngularFrontEnd {
+    fn extract(&self, content: &str, _filepath: &str, _config: &ScanConfig) -> Vec<KeyOccurrence> {
+        let mut occurrences = parse_translate_pipes(content);
+        for element in parse_elements(content) {
+            occurrences.extend(element.occurrences(content));
+        }
+        occurrences
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, _config: &ScanConfig) {
+        // 已标记翻译的元素内容不算未翻译的文本
+        let marked: Vec<_> = parse_elements(content)
+            .into_iter()
+            .filter(|element| element.is_marked())
+            .filter_map(|element| element.content)
+            .collect();
+        let masked: String = content
+            .char_indices()
+            .map(|(i, ch)| {
+                if ch != '\n' && ch != '\r' && marked.iter().any(|r| r.contains(&i)) {
+                    ' '
+                } else {
+                    ch
+                }
+            })
+            .collect();
+        linter.verify_template(&masked, filepath);
+    }
+}
+
+/// 提取 `'key' | translate` 管道
+pub fn parse_translate_pipes(content: &str) -> Vec<KeyOccurrence> {
+    let pipe = Regex::new(r#"(?:'([^'\n]*)'|"([^"\n]*)")\s*\|\s*translate\b"#).unwrap();
+    pipe.captures_iter(content)
+        .filter_map(|captures| {
+            let key = captures.get(1).or_else(|| captures.get(2))?;
+            literal_occurrence(content, key.as_str(), key.start() - 1, None)
+        })
+        .collect()
+}
+
+/// 开始标签中的属性
+#[derive(Debug, Clone)]
+struct Attribute {
+    name: String,
+    value: Option<String>,
+    /// 属性值（引号）在原文件中的字节偏移
+    value_offset: usize,
+}
+
+/// 模板中的元素：开始标签的属性与内容范围
+#[derive(Debug, Clone)]
+struct Element {
+    attributes: Vec<Attribute>,
+    content: Option<std::ops::Range<usize>>,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&Attribute> {
+        self.attributes.iter().find(|a| a.name == name)
+    }
+
+    fn is_marked(&self) -> bool {
+        ["translate", "[translate]", "i18n"]
+            .iter()
+            .any(|name| self.attribute(name).is_some())
+    }
+
+    /// 元素的文本内容（合并空白）及其第一个非空白字符的偏移
+    fn text(&self, content: &str) -> Option<(String, usize)> {
+        let range = self.content.clone()?;
+        let raw = &content[range.clone()];
+        let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.is_empty() {
+            return None;
+        }
+        Some((text, range.start + (raw.len() - raw.trim_start().len())))
+    }
+
+    fn occurrences(&self, content: &str) -> Vec<KeyOccurrence> {
+        let mut occurrences = Vec::new();
+
+        if let Some(attribute) = self.attribute("[translate]") {
+            let literal = Regex::new(r#"^\s*(?:'([^']*)'|"([^"]*)")\s*$"#).unwrap();
+            let value = attribute.value.as_deref().unwrap_or("");
+            if let Some(key) = literal.captures(value).and_then(|c| c.get(1).or_else(|| c.get(2))) {
+                let offset = attribute.value_offset + key.start();
+                occurrences.extend(literal_occurrence(content, key.as_str(), offset, None));
+            }
+        } else if let Some(attribute) = self.attribute("translate") {
+            match attribute.value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                Some(key) => occurrences.extend(literal_occurrence(content, key, attribute.value_offset, None)),
+                None => {
+                    if let Some((text, offset)) = self.text(content) {
+                        occurrences.extend(literal_occurrence(content, &text, offset, None));
+                    }
+                }
+            }
+        }
+
+        if let Some(attribute) = self.attribute("i18n") {
+            let id = attribute.value.as_deref().and_then(custom_id);
+            if let Some((text, offset)) = self.text(content) {
+                let (key, offset) = match id {
+                    Some(id) => (id, attribute.value_offset),
+                    None => (text.clone(), offset),
+                };
+                occurrences.extend(literal_occurrence(content, &key, offset, Some(text)));
+            }
+        }
+
+        for marker in &self.attributes {
+            let target = match marker.name.strip_prefix("i18n-") {
+                Some(target) => target,
+                None => continue,
+            };
+            let attribute = match self.attribute(target) {
+                Some(attribute) => attribute,
+                None => continue,
+            };
+            let text = attribute.value.clone().unwrap_or_default();
+            let key = marker.value.as_deref().and_then(custom_id).unwrap_or_else(|| text.clone());
+            occurrences.extend(literal_occurrence(content, &key, attribute.value_offset, Some(text)));
+        }
+
+        occurrences
+    }
+}
+
+/// 解析模板中所有带属性的开始标签
+fn parse_elements(content: &str) -> Vec<Element> {
+    let open = Regex::new(
+        r#"<([A-Za-z][\w.-]*)((?:\s+[^\s"'>/=]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'>]+))?)*)\s*(/?)>"#,
+    )
+    .unwrap();
+    let attribute = Regex::new(r#"([^\s"'>/=]+)(?:\s*=\s*(?:("[^"]*"|'[^']*')|([^\s"'>]+)))?"#).unwrap();
+
+    let mut elements = Vec::new();
+    for captures in open.captures_iter(content) {
+        let attrs = match captures.get(2) {
+            Some(attrs) if !attrs.as_str().trim().is_empty() => attrs,
+            _ => continue,
+        };
+        let attributes: Vec<Attribute> = attribute
+            .captures_iter(attrs.as_str())
+            .map(|c| {
+                let name = c.get(1).unwrap().as_str().to_string();
+                match (c.get(2), c.get(3)) {
+                    (Some(quoted), _) => Attribute {
+                        name,
+                        value: Some(quoted.as_str()[1..quoted.len() - 1].to_string()),
+                        value_offset: attrs.start() + quoted.start(),
+                    },
+                    (None, Some(bare)) => Attribute {
+                        name,
+                        value: Some(bare.as_str().to_string()),
+                        value_offset: attrs.start() + bare.start(),
+                    },
+                    _ => Attribute {
+                        name,
+                        value: None,
+                        value_offset: attrs.start() + c.get(0).unwrap().start(),
+                    },
+                }
+            })
+            .collect();
+
+        let mut element = Element {
+            attributes,
+            content: None,
+        };
+        // 只有标记了翻译的元素需要内容，避免为每个元素查找结束标签
+        let whole = captures.get(0).unwrap();
+        if element.is_marked() && captures[3].is_empty() {
+            element.content = Some(whole.end()..element_close(content, whole.end(), &captures[1]));
+        }
+        elements.push(element);
+    }
+    elements
+}
+
+/// `meaning|description@@id` 中的 id
+fn custom_id(value: &str) -> Option<String> {
+    value
+        .split_once("@@")
+        .map(|(_, id)| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+fn literal_occurrence(content: &str, key: &str, offset: usize, default_value: Option<String>) -> Option<KeyOccurrence> {
+    let key = key.trim();
+    if key.is_empty() {
+        return None;
+    }
+    let (line, column) = offset_to_line_column(content, offset);
+    Some(KeyOccurrence {
+        key: key.to_string(),
+        options: ParseOptions {
+            default_value: default_value.filter(|value| !value.is_empty() && value != key),
+            ..Default::default()
+        },
+        line,
+        column,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::zh_linter::get_result;
+
+    const TEMPLATE: &str = r#"<h1>{{ 'home.title' | translate }}</h1>
+<img [alt]="'home.logo' | translate" src="logo.png">
+<p translate>home.intro</p>
+<p translate="home.attr"></p>
+<span [translate]="'home.bound'" [translateParams]="{ n: 1 }"></span>
+<h2 i18n="页面标题@@homeHeader">欢迎回来</h2>
+<button title="保存修改" i18n-title>确定</button>
+<p i18n>
+  使用 {{ name }} 登录
+</p>
+"#;
+
+    fn extract(content: &str) -> Vec<KeyOccurrence> {
+        let mut occurrences = AngularFrontEnd.extract(content, "app.component.html", &ScanConfig::default());
+        occurrences.sort_by_key(|o| (o.line, o.column));
+        occurrences
+    }
+
+    #[test]
+    fn test_angular_extract() {
+        let occurrences = extract(TEMPLATE);
+        let found: Vec<_> = occurrences.iter().map(|o| (o.key.as_str(), o.line, o.column)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("home.title", 1, 8),
+                ("home.logo", 2, 13),
+                ("home.intro", 3, 14),
+                ("home.attr", 4, 14),
+                ("home.bound", 5, 20),
+                ("homeHeader", 6, 10),
+                ("保存修改", 7, 15),
+                ("使用 {{ name }} 登录", 9, 3),
+            ]
+        );
+        assert_eq!(occurrences[5].options.default_value.as_deref(), Some("欢迎回来"));
+        assert_eq!(occurrences[7].options.default_value, None);
+    }
+
+    #[test]
+    fn test_angular_lint_skips_marked_elements() {
+        let _guard = crate::scan::lock_scan();
+        AngularFrontEnd.lint(&ZhLinter::new(), TEMPLATE, "lint-test.component.html", &ScanConfig::default());
+        let values: Vec<_> = get_result()
+            .into_iter()
+            .filter(|r| r.filepath == "lint-test.component.html")
+            .map(|r| r.value)
+            .collect();
+        assert_eq!(values, vec!["确定"]);
+    }
+
+    #[test]
+    fn test_angular_has_no_parse_errors() {
+        // 模板中的 key 都是字面量，不解析选项对象
+        let (occurrences, errors) = AngularFrontEnd.extract_with_errors(TEMPLATE, "app.component.html", &ScanConfig::default());
+        assert_eq!(occurrences.len(), extract(TEMPLATE).len());
+        assert!(errors.is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::error::{Error, Result};
+
+use super::diff::Finding;
+use super::key_strategy::{Hash64Key, KeyStrategy};
+
+lazy_static! {
+    /// 信息中引用的其他位置，如 `src/a.js:3:4`
+    static ref POSITION: Regex = Regex::new(r":\d+:\d+").unwrap();
+}
+
+/// 基线文件格式版本，格式不兼容时递增
+pub const BASELINE_VERSION: u32 = 1;
+
+/// 已接受的历史问题，只报告基线之外新增的问题
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// 基线中的一条问题，同一文件中相同的问题合并计数
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub filepath: String,
+    pub kind: String,
+    /// 便于审阅的原始信息，匹配时只看 `fingerprint`
+    pub message: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+/// 按基线过滤后的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BaselineMatch {
+    /// 基线之外的问题
+    pub new: Vec<Finding>,
+    /// 被基线忽略的问题数
+    pub suppressed: usize,
+    /// 已修复的基线条目，`count` 为减少的次数
+    pub fixed: Vec<BaselineEntry>,
+}
+
+/// 统一路径分隔符，去掉 `./` 前缀
+fn normalize_filepath(filepath: &str) -> String {
+    let path = filepath.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+/// 问题的指纹：由文件、类型和去掉空白与 `行:列` 位置的信息计算，不依赖行号
+pub fn fingerprint(finding: &Finding) -> String {
+    let message = POSITION.replace_all(&finding.message, "");
+    let hasher = Hash64Key {
+        prefix: String::new(),
+        length: 13,
+    };
+    hasher.generate(&format!("{}\u{0}{}\u{0}{}", normalize_filepath(&finding.filepath), finding.kind, message))
+}
+
+impl Baseline {
+    /// 以当前的问题建立基线，条目按文件、类型、信息排序以便审阅差异
+    pub fn from_findings(findings: &[Finding]) -> Baseline {
+        let mut entries: BTreeMap<(String, String, String, String), usize> = BTreeMap::new();
+        for finding in findings {
+            let id = (
+                normalize_filepath(&finding.filepath),
+                finding.kind.clone(),
+                finding.message.clone(),
+                fingerprint(finding),
+            );
+            *entries.entry(id).or_default() += 1;
+        }
+        Baseline {
+            version: BASELINE_VERSION,
+            entries: entries
+                .into_iter()
+                .map(|((filepath, kind, message, fingerprint), count)| BaselineEntry {
+                    filepath,
+                    kind,
+                    message,
+                    fingerprint,
+                    count,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Baseline> {
+        let content = fs::read_to_string(path).map_err(|e| Error::file(path, e))?;
+        let baseline: Baseline = serde_json::from_str(&content).map_err(|e| Error::Resource {
+            path: path.into(),
+            message: format!("Invalid baseline: {}", e),
+        })?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(Error::Resource {
+                path: path.into(),
+                message: format!("Unsupported baseline version {}", baseline.version),
+            });
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        fs::write(path, format!("{}\n", serde_json::to_string_pretty(self)?)).map_err(|e| Error::file(path, e))?;
+        Ok(())
+    }
+
+    /// 过滤掉基线中已有的问题
+    ///
+    /// 同一指纹出现的次数超过基线中的计数时，超出的部分按新增报告；少于计数时，差值记为已修复。
+    pub fn apply(&self, findings: &[Finding]) -> BaselineMatch {
+        let mut remaining: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in &self.entries {
+            *remaining.entry(entry.fingerprint.as_str()).or_default() += entry.count;
+        }
+
+        let mut result = BaselineMatch::default();
+        for finding in findings {
+            match remaining.get_mut(fingerprint(finding).as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    result.suppressed += 1;
+                }
+                _ => result.new.push(finding.clone()),
+            }
+        }
+
+        for entry in &self.entries {
+            let Some(count) = remaining.get_mut(entry.fingerprint.as_str()) else {
+                continue;
+            };
+            let fixed = (*count).min(entry.count);
+            if fixed > 0 {
+                *count -= fixed;
+                result.fixed.push(BaselineEntry {
+                    count: fixed,
+                    ..entry.clone()
+                });
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::diff::Severity;
+
+    fn finding(filepath: &str, line: u32, message: &str) -> Finding {
+        Finding {
+            severity: Severity::Error,
+            kind: "HardCodedText".to_string(),
+            filepath: filepath.to_string(),
+            line,
+            column: 1,
+            message: message.to_string(),
+            span: None,
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_position() {
+        let a = finding("./src/a.js", 3, "Hard-coded text: alert('保存')");
+        let moved = finding("src/a.js", 10, "Hard-coded text:   alert( '保存' )");
+        assert_eq!(fingerprint(&a), fingerprint(&moved));
+        assert_eq!(
+            fingerprint(&finding("src/a.js", 1, "Key \"x\" first used at src/a.js:3:4")),
+            fingerprint(&finding("src/a.js", 1, "Key \"x\" first used at src/a.js:9:1"))
+        );
+        assert_ne!(fingerprint(&a), fingerprint(&finding("src/b.js", 3, "Hard-coded text: alert('保存')")));
+    }
+
+    #[test]
+    fn test_apply_baseline() {
+        let before = vec![
+            finding("src/a.js", 1, "保存"),
+            finding("src/a.js", 5, "保存"),
+            finding("src/a.js", 8, "取消"),
+            finding("src/b.js", 2, "确定"),
+        ];
+        let baseline = Baseline::from_findings(&before);
+        assert_eq!(baseline.entries.len(), 3);
+        assert_eq!(baseline.entries[0].count, 2);
+
+        // 一处“保存”与 b.js 已修复，代码整体下移，新增一处“删除”
+        let after = vec![
+            finding("src/a.js", 11, "保存"),
+            finding("src/a.js", 18, "取消"),
+            finding("src/a.js", 20, "删除"),
+        ];
+        let matched = baseline.apply(&after);
+        assert_eq!(matched.suppressed, 2);
+        assert_eq!(matched.new, vec![finding("src/a.js", 20, "删除")]);
+        let fixed: Vec<_> = matched.fixed.iter().map(|e| (e.message.as_str(), e.count)).collect();
+        assert_eq!(fixed, vec![("保存", 1), ("确定", 1)]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("baseline-test-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let baseline = Baseline::from_findings(&[finding("src/a.js", 1, "保存")]);
+        baseline.save(path).unwrap();
+        assert_eq!(Baseline::load(path).unwrap(), baseline);
+
+        fs::write(path, "{\"version\": 99, \"entries\": []}").unwrap();
+        assert!(Baseline::load(path).unwrap_err().to_string().contains("Unsupported baseline version 99"));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,358 @@
+use regex::Regex;
+
+use super::parse_func_from_string_by_babel::{func_pattern, KeyOccurrence};
+
+/// 源码中绑定到翻译函数的名称，如 `const { t } = useTranslation('common')` 中的 `t`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationBinding {
+    /// 调用时使用的名称，可以是成员表达式，如 `props.t`
+    pub name: String,
+    /// hook 或 HOC 参数中的命名空间
+    pub ns: Option<String>,
+    /// 绑定生效的字节范围：从声明处到所在代码块结束
+    pub start: usize,
+    pub end: usize,
+}
+
+impl TranslationBinding {
+    fn matches(&self, callee: &str, offset: usize) -> bool {
+        if offset < self.start || offset >= self.end {
+            return false;
+        }
+        // 成员表达式允许带前缀，如 `this.props.t` 匹配 `props.t`
+        callee == self.name || (self.name.contains('.') && callee.ends_with(&format!(".{}", self.name)))
+    }
+}
+
+/// 查找 hook、HOC 及别名创建的翻译函数绑定
+///
+/// - `const { t } = useTranslation('ns')`、`const { t: tr } = useTranslation(['ns', 'other'])`、
+///   `const [t] = useTranslation('ns')`、`const i18n = useTranslation('ns')`（之后调用 `i18n.t`）
+/// - `withTranslation('ns')(Component)`：文件中的 `props.t`、`this.props.t` 以及
+///   `const { t } = this.props` 使用该命名空间
+/// - `const tr = i18n.t.bind(i18n)`、`const tr = t`：别名继承原函数的命名空间
+pub fn find_bindings(content: &str, funcs: &[String], hooks: &[String], hocs: &[String]) -> Vec<TranslationBinding> {
+    let mut bindings = Vec::new();
+
+    if !hooks.is_empty() {
+        let hook_names = alternation(hooks);
+        let destructure = Regex::new(&format!(
+            r"(?:const|let|var)\s*\{{([^}}]*)\}}\s*=\s*(?:{})\s*\(([^)]*)\)",
+            hook_names
+        ))
+        .unwrap();
+        for captures in destructure.captures_iter(content) {
+            let whole = captures.get(0).unwrap();
+            if let Some(name) = destructured_name(&captures[1], "t") {
+                bindings.push(scoped(content, name, first_string_literal(&captures[2]), whole.start()));
+            }
+        }
+
+        let array = Regex::new(&format!(
+            r"(?:const|let|var)\s*\[\s*([\w$]+)[^\]]*\]\s*=\s*(?:{})\s*\(([^)]*)\)",
+            hook_names
+        ))
+        .unwrap();
+        for captures in array.captures_iter(content) {
+            let whole = captures.get(0).unwrap();
+            bindings.push(scoped(content, captures[1].to_string(), first_string_literal(&captures[2]), whole.start()));
+        }
+
+        let object = Regex::new(&format!(
+            r"(?:const|let|var)\s+([\w$]+)\s*=\s*(?:{})\s*\(([^)]*)\)",
+            hook_names
+        ))
+        .unwrap();
+        for captures in object.captures_iter(content) {
+            let whole = captures.get(0).unwrap();
+            let name = format!("{}.t", &captures[1]);
+            bindings.push(scoped(content, name, first_string_literal(&captures[2]), whole.start()));
+        }
+    }
+
+    if !hocs.is_empty() {
+        let hoc = Regex::new(&format!(r"(?:{})\s*\(([^)]*)\)\s*\(", alternation(hocs))).unwrap();
+        if let Some(captures) = hoc.captures(content) {
+            let ns = first_string_literal(&captures[1]);
+            bindings.push(TranslationBinding {
+                name: "props.t".to_string(),
+                ns: ns.clone(),
+                start: 0,
+                end: content.len(),
+            });
+            let from_props = Regex::new(r"(?:const|let|var)\s*\{([^}]*)\}\s*=\s*(?:this\.)?props\b").unwrap();
+            for captures in from_props.captures_iter(content) {
+                let whole = captures.get(0).unwrap();
+                if let Some(name) = destructured_name(&captures[1], "t") {
+                    bindings.push(scoped(content, name, ns.clone(), whole.start()));
+                }
+            }
+        }
+    }
+
+    let alias = Regex::new(r"(?:const|let|var)\s+([\w$]+)\s*=\s*([\w$.]+?)(?:\.bind\s*\([^)]*\))?\s*[;\n]").unwrap();
+    for captures in alias.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let target = &captures[2];
+        let ns = if listed_exactly(target, funcs) {
+            None
+        } else {
+            match bindings.iter().rev().find(|b| b.matches(target, whole.start())) {
+                Some(binding) => binding.ns.clone(),
+                None => continue,
+            }
+        };
+        bindings.push(scoped(content, captures[1].to_string(), ns, whole.start()));
+    }
+
+    bindings
+}
+
+/// 按绑定补充命名空间，并丢弃在绑定作用域之外、又不在函数列表中的调用
+pub fn apply_bindings(
+    content: &str,
+    occurrences: Vec<KeyOccurrence>,
+    bindings: &[TranslationBinding],
+    funcs: &[String],
+) -> Vec<KeyOccurrence> {
+    let listed = listed_regex(funcs);
+    occurrences
+        .into_iter()
+        .filter_map(|mut occurrence| {
+            let offset = line_column_to_offset(content, occurrence.line, occurrence.column);
+            let callee = callee_before(content, offset);
+            // 声明在后的绑定覆盖外层的同名绑定
+            match bindings.iter().rev().find(|b| b.matches(callee, offset)) {
+                Some(binding) => {
+                    if occurrence.options.ns.is_none() {
+                        occurrence.options.ns = binding.ns.clone();
+                    }
+                    Some(occurrence)
+                }
+                None if listed.as_ref().is_some_and(|listed| listed.is_match(callee)) => Some(occurrence),
+                None => None,
+            }
+        })
+        .collect()
+}
+
+/// 参与匹配的函数名：配置中的列表加上所有绑定名称
+pub fn binding_funcs(funcs: &[String], bindings: &[TranslationBinding]) -> Vec<String> {
+    let mut list = funcs.to_vec();
+    for binding in bindings {
+        if !list.contains(&binding.name) {
+            list.push(binding.name.clone());
+        }
+    }
+    list
+}
+
+/// 匹配函数列表中任一项的调用名，与 `Parser` 的正则一致：按后缀匹配，前一个字符不是 `[A-Za-z0-9_]` 即可
+/// （`props.t`、`$t` 都会匹配列表中的 `t`），正则形式的项见 [`func_pattern`]
+fn listed_regex(funcs: &[String]) -> Option<Regex> {
+    if funcs.is_empty() {
+        return None;
+    }
+    let patterns: Vec<String> = funcs.iter().map(|func| format!("(?:{})", func_pattern(func))).collect();
+    Regex::new(&format!(r"(?:^|[^A-Za-z0-9_])(?:{})$", patterns.join("|"))).ok()
+}
+
+/// 名称与函数列表中的某一项完全匹配
+fn listed_exactly(name: &str, funcs: &[String]) -> bool {
+    funcs.iter().any(|func| {
+        Regex::new(&format!("^(?:{})$", func_pattern(func))).is_ok_and(|pattern| pattern.is_match(name))
+    })
+}
+
+fn alternation(names: &[String]) -> String {
+    names.iter().map(|name| regex::escape(name)).collect::<Vec<_>>().join("|")
+}
+
+/// 从解构列表中找到属性对应的本地名称，如 `t: translate` → `translate`
+fn destructured_name(pattern: &str, property: &str) -> Option<String> {
+    pattern.split(',').find_map(|entry| {
+        let mut parts = entry.splitn(2, ':').map(str::trim);
+        let key = parts.next()?;
+        if key != property {
+            return None;
+        }
+        let local = parts.next().unwrap_or(key);
+        let local = local.split('=').next().unwrap_or(local).trim();
+        (!local.is_empty()).then(|| local.to_string())
+    })
+}
+
+/// 取参数中的第一个字符串字面量，数组参数取第一个元素
+fn first_string_literal(args: &str) -> Option<String> {
+    let literal = Regex::new(r#"^\s*\[?\s*(?:'([^']*)'|"([^"]*)"|`([^`$]*)`)"#).unwrap();
+    let captures = literal.captures(args)?;
+    (1..=3).find_map(|i| captures.get(i)).map(|m| m.as_str().to_string())
+}
+
+fn scoped(content: &str, name: String, ns: Option<String>, start: usize) -> TranslationBinding {
+    TranslationBinding {
+        name,
+        ns,
+        start,
+        end: block_end(content, start),
+    }
+}
+
+/// 从声明处向后找到所在代码块的结束位置，跳过字符串中的括号
+fn block_end(content: &str, start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, ch) in content[start..].char_indices() {
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth < 0 {
+                    return start + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    content.len()
+}
+
+/// 取 key 所在调用的函数名：key 的引号之前跳过空白和 `(`，再读取标识符与 `.`
+fn callee_before(content: &str, offset: usize) -> &str {
+    let before = content[..offset].trim_end();
+    let before = before.strip_suffix('(').unwrap_or(before).trim_end();
+    let start = before
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$' || c == '.'))
+        .map_or(0, |i| i + before[i..].chars().next().map_or(1, char::len_utf8));
+    &before[start..]
+}
+
+/// 将从 1 开始的行列号（列按字符计算）转换为字节偏移
+fn line_column_to_offset(content: &str, line: u32, column: u32) -> usize {
+    let line_start = if line <= 1 {
+        0
+    } else {
+        content
+            .match_indices('\n')
+            .nth(line as usize - 2)
+            .map_or(content.len(), |(i, _)| i + 1)
+    };
+    content[line_start..]
+        .char_indices()
+        .nth((column as usize).saturating_sub(1))
+        .map_or(content.len(), |(i, _)| line_start + i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::parse_func_from_string_by_babel::{Parser, ParserOpts};
+
+    fn extract(content: &str) -> Vec<KeyOccurrence> {
+        let funcs = vec!["i18next.t".to_string(), "i18n.t".to_string(), "t".to_string()];
+        let bindings = find_bindings(
+            content,
+            &funcs,
+            &["useTranslation".to_string()],
+            &["withTranslation".to_string()],
+        );
+        let mut parser = Parser::new();
+        let opts = ParserOpts {
+            list: Some(binding_funcs(&funcs, &bindings)),
+            ..Default::default()
+        };
+        parser.parse_func_from_string_by_babel(content, opts, None, None);
+        apply_bindings(content, parser.occurrences, &bindings, &funcs)
+    }
+
+    fn ns_of(occurrences: &[KeyOccurrence], key: &str) -> Option<String> {
+        occurrences.iter().find(|o| o.key == key).unwrap().options.ns.clone()
+    }
+
+    #[test]
+    fn test_hook_namespace_is_scoped() {
+        let content = r#"
+function A() {
+  const { t } = useTranslation('common');
+  return t('save');
+}
+function B() {
+  const { t: translate } = useTranslation(['errors', 'common']);
+  return translate('failed') + t('plain');
+}
+translate('outside');
+"#;
+        let occurrences = extract(content);
+        assert_eq!(ns_of(&occurrences, "save").as_deref(), Some("common"));
+        assert_eq!(ns_of(&occurrences, "failed").as_deref(), Some("errors"));
+        assert_eq!(ns_of(&occurrences, "plain"), None);
+        assert!(!occurrences.iter().any(|o| o.key == "outside"));
+    }
+
+    #[test]
+    fn test_hoc_and_aliases() {
+        let content = r#"
+class Page extends React.Component {
+  render() {
+    const { t } = this.props;
+    return this.props.t('title') + t('body');
+  }
+}
+export default withTranslation('page')(Page);
+const tr = i18n.t.bind(i18n);
+tr('alias', { ns: 'other' });
+"#;
+        let occurrences = extract(content);
+        assert_eq!(ns_of(&occurrences, "title").as_deref(), Some("page"));
+        assert_eq!(ns_of(&occurrences, "body").as_deref(), Some("page"));
+        assert_eq!(ns_of(&occurrences, "alias").as_deref(), Some("other"));
+    }
+
+    #[test]
+    fn test_line_column_offset() {
+        let content = "ab\n中文t('x')";
+        let offset = line_column_to_offset(content, 2, 5);
+        assert_eq!(&content[offset..offset + 1], "'");
+        assert_eq!(callee_before(content, offset), "t");
+        let listed = listed_regex(&["t".to_string()]).unwrap();
+        assert!(listed.is_match("$t"));
+        assert!(!listed.is_match("at"));
+    }
+
+    #[test]
+    fn test_regex_list_entries() {
+        let funcs = vec![r"i18n\.(t|translate)".to_string(), "$t".to_string()];
+        let content = r#"
+i18n.translate('full');
+i18n.t('short', '默认值');
+$t('dollar');
+i18nXt('escaped');
+const tr = i18n.translate;
+tr('alias');
+"#;
+        let bindings = find_bindings(content, &funcs, &[], &[]);
+        let mut parser = Parser::new();
+        let opts = ParserOpts {
+            list: Some(binding_funcs(&funcs, &bindings)),
+            ..Default::default()
+        };
+        parser.parse_func_from_string_by_babel(content, opts, None, None);
+        let occurrences = apply_bindings(content, parser.occurrences, &bindings, &funcs);
+
+        let keys: Vec<_> = occurrences.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, vec!["full", "short", "dollar", "alias"]);
+        assert_eq!(occurrences[1].options.default_value.as_deref(), Some("默认值"));
+    }
+}
@@ -1,9 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::duplicate_key::DuplicateKeyStrategy;
+use super::front_end::{longest_suffix_match, FrontEndKind, DEFAULT_FRONT_ENDS};
+use super::hash_key::HashCollisionStrategy;
+use super::key_strategy::KeyStrategyConfig;
+use super::plural::PluralSuffixStyle;
 
 /// Babel 解析器选项
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BabelOptions {
     pub plugins: Vec<BabelPlugin>,
+    #[serde(rename = "sourceType", alias = "source_type")]
     pub source_type: String,
 }
 
@@ -18,25 +26,72 @@ pub enum BabelPlugin {
 /// 资源配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceConfig {
+    /// 已有词条文件路径，为空时读取 `save_path`
+    #[serde(default, alias = "loadPath")]
     pub load_path: String,
+    /// 词条文件输出路径，支持 `{{lng}}` 和 `{{ns}}` 占位符
+    #[serde(alias = "savePath")]
     pub save_path: String,
 }
 
 /// 函数配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct FuncConfig {
     pub list: Vec<String>,
+    /// 返回翻译函数的 hook，如 `const { t } = useTranslation('ns')`、vue-i18n 的 `useI18n()`
+    pub hooks: Vec<String>,
+    /// 注入 `props.t` 的高阶组件，如 `withTranslation('ns')(Component)`
+    pub hocs: Vec<String>,
+    /// 需要提取函数调用的文件扩展名，为空时使用 js/jsx/ts/tsx
     pub extensions: Vec<String>,
     pub babylon: BabelOptions,
 }
 
 /// 转换配置
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TransConfig {
+    /// Trans 组件名
+    pub component: String,
+    /// 指定 key 的属性名
+    #[serde(alias = "i18nKey")]
+    pub i18n_key: String,
+    /// 需要提取 Trans 组件的文件扩展名，为空时使用 js/jsx/ts/tsx
     pub extensions: Vec<String>,
     pub babylon: BabelOptions,
 }
 
+/// Markdown、MDX 与静态 HTML 的检查选项
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StaticContentConfig {
+    /// 检查 Markdown 代码块与行内代码中的中文，默认跳过
+    #[serde(alias = "codeBlocks")]
+    pub code_blocks: bool,
+    /// 提取并检查 HTML 中 `<script>` 的内容，默认跳过
+    pub scripts: bool,
+}
+
+/// 未配置 extensions 时扫描的文件扩展名
+pub const DEFAULT_EXTENSIONS: [&str; 4] = ["js", "jsx", "ts", "tsx"];
+
+/// 判断文件扩展名是否在列表中，列表为空时使用 `DEFAULT_EXTENSIONS`
+///
+/// 列表项可以带或不带前导 `.`，如 `.js` 与 `js` 等价。
+pub fn extension_matches(extensions: &[String], filepath: &str) -> bool {
+    let ext = match crate::utils::get_file_extension(filepath) {
+        Some(ext) => ext,
+        None => return false,
+    };
+    if extensions.is_empty() {
+        return DEFAULT_EXTENSIONS.contains(&ext.as_str());
+    }
+    extensions
+        .iter()
+        .any(|e| e.trim_start_matches('.').eq_ignore_ascii_case(&ext))
+}
+
 /// i18next 扫描配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanConfig {
@@ -48,6 +103,46 @@ pub struct ScanConfig {
     pub resource: ResourceConfig,
     pub func: FuncConfig,
     pub trans: TransConfig,
+    /// 同一个 key 的默认值在不同位置冲突时的处理策略
+    #[serde(default)]
+    pub duplicate_keys: DuplicateKeyStrategy,
+    /// 不同句子生成相同 key 时的处理策略
+    #[serde(default)]
+    pub hash_collisions: HashCollisionStrategy,
+    /// 由句子生成 key 的策略，默认与 JS 版本的 hashKey 一致
+    #[serde(default)]
+    pub key_strategy: KeyStrategyConfig,
+    /// 命名空间分隔符，如 `t('common:save')`，为空时不解析 key 中的命名空间
+    #[serde(default = "default_ns_separator", alias = "nsSeparator")]
+    pub ns_separator: String,
+    /// 复数 key 的后缀格式，默认与 i18next v3 一致
+    #[serde(default, alias = "pluralStyle")]
+    pub plural_style: PluralSuffixStyle,
+    /// 文件名后缀 → 语言前端，如 `{ "html": "angular" }`，优先于 `DEFAULT_FRONT_ENDS`
+    #[serde(default, alias = "frontEnds")]
+    pub front_ends: HashMap<String, FrontEndKind>,
+    /// Markdown、MDX 与静态 HTML 的检查选项
+    #[serde(default, alias = "staticContent")]
+    pub static_content: StaticContentConfig,
+    /// 展开输入模式时排除的文件与目录，不含 `/` 的模式匹配任意层级
+    #[serde(default = "default_exclude")]
+    pub exclude: Vec<String>,
+    /// 展开输入模式时读取的忽略文件，如 `.gitignore`，在各级目录中生效
+    #[serde(default = "default_ignore_files", alias = "ignoreFiles")]
+    pub ignore_files: Vec<String>,
+}
+
+fn default_ns_separator() -> String {
+    ":".to_string()
+}
+
+/// 默认跳过依赖目录与类型声明文件
+pub fn default_exclude() -> Vec<String> {
+    vec!["node_modules".to_string(), "*.d.ts".to_string()]
+}
+
+pub fn default_ignore_files() -> Vec<String> {
+    vec![".i18nignore".to_string()]
 }
 
 impl Default for BabelOptions {
@@ -88,10 +183,51 @@ impl Default for BabelOptions {
     }
 }
 
+impl From<&BabelOptions> for super::parse_func_from_string_by_babel::BabylonOptions {
+    fn from(options: &BabelOptions) -> Self {
+        Self {
+            plugins: options
+                .plugins
+                .iter()
+                .map(|plugin| match plugin {
+                    BabelPlugin::Simple(name) | BabelPlugin::WithOptions(name, _) => name.clone(),
+                })
+                .collect(),
+            source_type: options.source_type.clone(),
+        }
+    }
+}
+
+impl Default for FuncConfig {
+    fn default() -> Self {
+        Self {
+            list: vec![
+                "i18next.t".to_string(),
+                "i18n.t".to_string(),
+                "t".to_string(),
+            ],
+            hooks: vec!["useTranslation".to_string(), "useI18n".to_string()],
+            hocs: vec!["withTranslation".to_string()],
+            extensions: vec![], // 避免在 transform 中执行原生的 parseFuncFromString
+            babylon: BabelOptions::default(),
+        }
+    }
+}
+
+impl Default for TransConfig {
+    fn default() -> Self {
+        Self {
+            component: "Trans".to_string(),
+            i18n_key: "i18nKey".to_string(),
+            extensions: vec![], // 避免在 transform 中执行原生的 parseTransFromString
+            babylon: BabelOptions::default(),
+        }
+    }
+}
+
 impl Default for ScanConfig {
     fn default() -> Self {
         let default_lng = "zh".to_string();
-        let babel_options = BabelOptions::default();
 
         Self {
             input: vec!["src/**/*.{js,jsx,ts,tsx}".to_string()],
@@ -103,19 +239,17 @@ impl Default for ScanConfig {
                 load_path: "".to_string(), // 避免 i18next-scanner 读取报错
                 save_path: "i18n/translation/{{lng}}.js".to_string(),
             },
-            func: FuncConfig {
-                list: vec![
-                    "i18next.t".to_string(),
-                    "i18n.t".to_string(),
-                    "t".to_string(),
-                ],
-                extensions: vec![], // 避免在 transform 中执行原生的 parseFuncFromString
-                babylon: babel_options.clone(),
-            },
-            trans: TransConfig {
-                extensions: vec![], // 避免在 transform 中执行原生的 parseTransFromString
-                babylon: babel_options,
-            },
+            func: FuncConfig::default(),
+            trans: TransConfig::default(),
+            duplicate_keys: DuplicateKeyStrategy::default(),
+            hash_collisions: HashCollisionStrategy::default(),
+            key_strategy: KeyStrategyConfig::default(),
+            ns_separator: default_ns_separator(),
+            plural_style: PluralSuffixStyle::default(),
+            front_ends: HashMap::new(),
+            static_content: StaticContentConfig::default(),
+            exclude: default_exclude(),
+            ignore_files: default_ignore_files(),
         }
     }
 }
@@ -126,6 +260,42 @@ impl ScanConfig {
         Self::default()
     }
 
+    /// 文件是否需要扫描（有对应的语言前端）
+    pub fn should_scan(&self, filepath: &str) -> bool {
+        self.front_end_for(filepath).is_some()
+    }
+
+    /// 文件使用的语言前端：先按 `front_ends`、再按 `DEFAULT_FRONT_ENDS` 匹配最长后缀，
+    /// 都不匹配时，扩展名符合函数或 Trans 组件配置的文件使用 JS/TS 前端
+    pub fn front_end_for(&self, filepath: &str) -> Option<FrontEndKind> {
+        longest_suffix_match(self.front_ends.iter().map(|(suffix, kind)| (suffix.as_str(), *kind)), filepath)
+            .or_else(|| longest_suffix_match(DEFAULT_FRONT_ENDS, filepath))
+            .or_else(|| {
+                let is_script = extension_matches(&self.func.extensions, filepath)
+                    || extension_matches(&self.trans.extensions, filepath);
+                is_script.then_some(FrontEndKind::Script)
+            })
+    }
+
+    /// 计算指定语言、命名空间的词条输出路径
+    pub fn save_path(&self, lng: &str, ns: &str) -> String {
+        fill_path(&self.resource.save_path, lng, ns)
+    }
+
+    /// 计算指定语言、命名空间的已有词条路径，未配置 `load_path` 时与输出路径相同
+    pub fn load_path(&self, lng: &str, ns: &str) -> String {
+        if self.resource.load_path.is_empty() {
+            self.save_path(lng, ns)
+        } else {
+            fill_path(&self.resource.load_path, lng, ns)
+        }
+    }
+
+    /// 命名空间是否已在 `ns` 中声明
+    pub fn is_declared_namespace(&self, ns: &str) -> bool {
+        ns == self.default_ns || self.ns.iter().any(|declared| declared == ns)
+    }
+
     /// 设置输入文件模式
     pub fn with_input(mut self, input: Vec<String>) -> Self {
         self.input = input;
@@ -173,6 +343,64 @@ impl ScanConfig {
         self.trans = trans;
         self
     }
+
+    /// 设置重复 key 冲突的处理策略
+    pub fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeyStrategy) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// 设置哈希冲突的处理策略
+    pub fn with_hash_collisions(mut self, hash_collisions: HashCollisionStrategy) -> Self {
+        self.hash_collisions = hash_collisions;
+        self
+    }
+
+    /// 设置命名空间分隔符
+    pub fn with_ns_separator(mut self, ns_separator: String) -> Self {
+        self.ns_separator = ns_separator;
+        self
+    }
+
+    /// 为文件名后缀指定语言前端
+    pub fn with_front_end(mut self, suffix: &str, kind: FrontEndKind) -> Self {
+        self.front_ends.insert(suffix.trim_start_matches('.').to_string(), kind);
+        self
+    }
+
+    /// 设置 Markdown、MDX 与静态 HTML 的检查选项
+    pub fn with_static_content(mut self, static_content: StaticContentConfig) -> Self {
+        self.static_content = static_content;
+        self
+    }
+
+    /// 设置排除的文件与目录，替换默认的 `node_modules`、`*.d.ts`
+    pub fn with_exclude(mut self, exclude: Vec<String>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    /// 设置展开输入模式时读取的忽略文件
+    pub fn with_ignore_files(mut self, ignore_files: Vec<String>) -> Self {
+        self.ignore_files = ignore_files;
+        self
+    }
+
+    /// 设置复数 key 的后缀格式
+    pub fn with_plural_style(mut self, plural_style: PluralSuffixStyle) -> Self {
+        self.plural_style = plural_style;
+        self
+    }
+
+    /// 设置 key 生成策略
+    pub fn with_key_strategy(mut self, key_strategy: KeyStrategyConfig) -> Self {
+        self.key_strategy = key_strategy;
+        self
+    }
+}
+
+fn fill_path(template: &str, lng: &str, ns: &str) -> String {
+    template.replace("{{lng}}", lng).replace("{{ns}}", ns)
 }
 
 /// 获取默认的 i18next 扫描配置
@@ -197,6 +425,7 @@ mod tests {
         assert_eq!(config.input, vec!["src/**/*.{js,jsx,ts,tsx}"]);
         assert_eq!(config.lngs, vec!["zh"]);
         assert_eq!(config.ns, vec!["translation"]);
+        assert_eq!(config.duplicate_keys, DuplicateKeyStrategy::Error);
     }
 
     #[test]
@@ -211,6 +440,60 @@ mod tests {
         assert_eq!(config.default_lng, "en");
     }
 
+    #[test]
+    fn test_extension_filter() {
+        let config = get_default_config();
+        assert!(config.should_scan("src/App.tsx"));
+        assert!(config.should_scan("README.md"));
+        assert!(!config.should_scan("notes.txt"));
+
+        let config = config.with_func(FuncConfig {
+            extensions: vec![".vue".to_string()],
+            ..Default::default()
+        });
+        assert!(config.should_scan("src/App.vue"));
+        // Trans 仍使用默认扩展名
+        assert!(config.should_scan("src/App.tsx"));
+    }
+
+    #[test]
+    fn test_front_end_for() {
+        let config = get_default_config();
+        assert_eq!(config.front_end_for("src/App.tsx"), Some(FrontEndKind::Script));
+        assert_eq!(config.front_end_for("src/Page.svelte"), Some(FrontEndKind::Svelte));
+        assert_eq!(config.front_end_for("app/app.component.html"), Some(FrontEndKind::Angular));
+        assert_eq!(config.front_end_for("public/index.html"), Some(FrontEndKind::Html));
+        assert_eq!(config.front_end_for("notes.txt"), None);
+
+        let config = config
+            .with_front_end("html", FrontEndKind::Angular)
+            .with_front_end(".component.html", FrontEndKind::Script);
+        assert_eq!(config.front_end_for("public/index.html"), Some(FrontEndKind::Angular));
+        assert_eq!(config.front_end_for("app/app.component.html"), Some(FrontEndKind::Script));
+    }
+
+    #[test]
+    fn test_deserialize_partial_config() {
+        let config: ScanConfig = serde_json::from_value(serde_json::json!({
+            "input": ["src/**/*.ts"],
+            "lngs": ["zh", "en"],
+            "ns": ["translation"],
+            "default_lng": "zh",
+            "default_ns": "translation",
+            "resource": { "loadPath": "", "savePath": "i18n/{{lng}}.json" },
+            "func": { "list": ["__"] },
+            "trans": { "component": "I18n" }
+        }))
+        .unwrap();
+
+        assert_eq!(config.func.list, vec!["__"]);
+        assert!(config.func.extensions.is_empty());
+        assert_eq!(config.trans.component, "I18n");
+        assert_eq!(config.trans.i18n_key, "i18nKey");
+        assert_eq!(config.save_path("en", "translation"), "i18n/en.json");
+        assert_eq!(config.load_path("en", "translation"), "i18n/en.json");
+    }
+
     #[test]
     fn test_babel_options() {
         let babel_options = BabelOptions::default();
@@ -0,0 +1,370 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::config::ScanConfig;
+use super::diff::Severity;
+use super::js_object::KeyLocations;
+use super::key_strategy::KeyStrategyConfig;
+
+/// 配置检查的结果，`path` 为配置文件中的配置项路径，如 `resource.savePath`、`func.list[0]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiagnostic {
+    pub severity: Severity,
+    pub path: String,
+    pub message: String,
+    /// 配置项在配置文件中的行号，无法定位时（如 TOML、库中构造的配置）为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+}
+
+impl ConfigDiagnostic {
+    pub fn error(path: &str, message: String) -> Self {
+        Self::new(Severity::Error, path, message)
+    }
+
+    pub fn warning(path: &str, message: String) -> Self {
+        Self::new(Severity::Warning, path, message)
+    }
+
+    fn new(severity: Severity, path: &str, message: String) -> Self {
+        Self {
+            severity,
+            path: path.to_string(),
+            message,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// 填入配置项的位置，路径本身没有位置时使用最近的上级配置项
+    pub fn locate(mut self, locations: &KeyLocations) -> Self {
+        let mut path = self.path.as_str();
+        loop {
+            if let Some((line, column)) = locations.get(path) {
+                self.line = Some(*line);
+                self.column = Some(*column);
+                return self;
+            }
+            match path.rfind(['.', '[']) {
+                Some(end) => path = &path[..end],
+                None => return self,
+            }
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// 检查配置中会导致扫描或输出结果异常的取值
+pub fn validate_config(config: &ScanConfig) -> Vec<ConfigDiagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if config.input.is_empty() {
+        diagnostics.push(ConfigDiagnostic::warning(
+            "input",
+            "no input patterns, files must be given on the command line".to_string(),
+        ));
+    }
+
+    if config.lngs.is_empty() {
+        diagnostics.push(ConfigDiagnostic::error("lngs", "at least one language is required".to_string()));
+    }
+    for (i, lng) in config.lngs.iter().enumerate() {
+        if lng.trim().is_empty() {
+            diagnostics.push(ConfigDiagnostic::error(&format!("lngs[{}]", i), "language is empty".to_string()));
+        } else if config.lngs[..i].contains(lng) {
+            diagnostics.push(ConfigDiagnostic::warning(
+                &format!("lngs[{}]", i),
+                format!("language \"{}\" is listed more than once", lng),
+            ));
+        }
+    }
+    if !config.lngs.is_empty() && !config.lngs.contains(&config.default_lng) {
+        diagnostics.push(ConfigDiagnostic::error(
+            "defaultLng",
+            format!("default language \"{}\" is not in lngs", config.default_lng),
+        ));
+    }
+
+    if config.default_ns.trim().is_empty() {
+        diagnostics.push(ConfigDiagnostic::error("defaultNs", "default namespace is empty".to_string()));
+    }
+    for (i, ns) in config.ns.iter().enumerate() {
+        if ns.trim().is_empty() {
+            diagnostics.push(ConfigDiagnostic::error(&format!("ns[{}]", i), "namespace is empty".to_string()));
+        }
+    }
+
+    let save_path = &config.resource.save_path;
+    if save_path.trim().is_empty() {
+        diagnostics.push(ConfigDiagnostic::error("resource.savePath", "save path is empty".to_string()));
+    } else {
+        if config.lngs.len() > 1 && !save_path.contains("{{lng}}") {
+            diagnostics.push(ConfigDiagnostic::error(
+                "resource.savePath",
+                format!("\"{}\" has no {{{{lng}}}}, every language would be written to the same file", save_path),
+            ));
+        }
+        if namespace_count(config) > 1 && !save_path.contains("{{ns}}") {
+            diagnostics.push(ConfigDiagnostic::error(
+                "resource.savePath",
+                format!("\"{}\" has no {{{{ns}}}}, but more than one namespace is declared", save_path),
+            ));
+        }
+    }
+    let load_path = &config.resource.load_path;
+    if !load_path.is_empty() && config.lngs.len() > 1 && !load_path.contains("{{lng}}") {
+        diagnostics.push(ConfigDiagnostic::warning(
+            "resource.loadPath",
+            format!("\"{}\" has no {{{{lng}}}}, every language would be read from the same file", load_path),
+        ));
+    }
+
+    if config.func.list.is_empty() {
+        diagnostics.push(ConfigDiagnostic::error(
+            "func.list",
+            "no translation functions, no t() calls would be extracted".to_string(),
+        ));
+    }
+    for (i, name) in config.func.list.iter().enumerate() {
+        if name.trim().is_empty() {
+            diagnostics.push(ConfigDiagnostic::error(&format!("func.list[{}]", i), "function name is empty".to_string()));
+        }
+    }
+    if config.trans.component.trim().is_empty() {
+        diagnostics.push(ConfigDiagnostic::error("trans.component", "component name is empty".to_string()));
+    }
+    if config.trans.i18n_key.trim().is_empty() {
+        diagnostics.push(ConfigDiagnostic::error("trans.i18nKey", "attribute name is empty".to_string()));
+    }
+
+    match &config.key_strategy {
+        KeyStrategyConfig::Hash64 { length, .. } if !(1..=13).contains(length) => {
+            diagnostics.push(ConfigDiagnostic::error(
+                "keyStrategy.length",
+                format!("length {} is out of range 1..=13", length),
+            ));
+        }
+        KeyStrategyConfig::Slug { max_length: 0, .. } => {
+            diagnostics.push(ConfigDiagnostic::error("keyStrategy.maxLength", "maxLength must be positive".to_string()));
+        }
+        _ => {}
+    }
+
+    if config.front_ends.keys().any(|suffix| suffix.is_empty()) {
+        diagnostics.push(ConfigDiagnostic::error("frontEnds", "file suffix is empty".to_string()));
+    }
+
+    diagnostics
+}
+
+/// 声明的命名空间数量，默认命名空间总是计入
+fn namespace_count(config: &ScanConfig) -> usize {
+    let mut namespaces: Vec<&String> = config.ns.iter().collect();
+    namespaces.push(&config.default_ns);
+    namespaces.sort();
+    namespaces.dedup();
+    namespaces.len()
+}
+
+fn string_array(description: &str) -> Value {
+    json!({ "type": "array", "items": { "type": "string" }, "description": description })
+}
+
+fn string_enum(values: &[&str], default: &str, description: &str) -> Value {
+    json!({ "type": "string", "enum": values, "default": default, "description": description })
+}
+
+/// 配置文件的 JSON Schema（draft-07），供编辑器补全与校验
+pub fn config_schema() -> Value {
+    let babylon = json!({
+        "type": "object",
+        "required": ["plugins", "sourceType"],
+        "properties": {
+            "plugins": { "type": "array" },
+            "sourceType": { "type": "string" }
+        }
+    });
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "fast-i18n-scan config",
+        "type": "object",
+        "required": ["input", "lngs", "defaultLng"],
+        "additionalProperties": false,
+        "properties": {
+            "extends": {
+                "description": "Config files to inherit from, relative to this file; objects are merged, other values are replaced",
+                "oneOf": [{ "type": "string" }, { "type": "array", "items": { "type": "string" } }]
+            },
+            "command": {
+                "type": "object",
+                "description": "Per-subcommand overrides, e.g. { lint: { lngs: ['zh'] } }",
+                "additionalProperties": { "type": "object" }
+            },
+            "workspaces": string_array("Package directories of a monorepo, each with its own i18n.config file"),
+            "input": string_array("File patterns to scan; patterns starting with `!` exclude files"),
+            "output": { "type": "string", "description": "Output directory, the default savePath is `${output}/{{lng}}.json`" },
+            "debug": { "type": "boolean", "default": false },
+            "sort": { "type": "boolean", "default": false },
+            "removeUnusedKeys": { "type": "boolean", "default": false },
+            "lngs": { "type": "array", "items": { "type": "string" }, "minItems": 1, "description": "Languages to write" },
+            "defaultLng": { "type": "string", "description": "Language whose resource file receives the default values; must be in lngs" },
+            "ns": string_array("Declared namespaces"),
+            "defaultNs": { "type": "string", "default": "translation" },
+            "nsSeparator": { "type": "string", "default": ":", "description": "Namespace separator in keys, empty to disable" },
+            "duplicateKeys": string_enum(&["first", "last", "error"], "error", "How to handle a key with different default values"),
+            "hashCollisions": string_enum(&["report", "disambiguate"], "report", "How to handle two sentences hashing to the same key"),
+            "pluralStyle": string_enum(&["v3", "v4"], "v3", "Plural key suffixes: i18next v3 (`_plural`) or v4 (`_one`/`_other`)"),
+            "keyStrategy": {
+                "description": "How keys are generated for sentences without an explicit key",
+                "oneOf": [
+                    {
+                        "type": "object",
+                        "required": ["type"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "type": { "const": "legacy" },
+                            "prefix": { "type": "string", "default": "k_" }
+                        }
+                    },
+                    {
+                        "type": "object",
+                        "required": ["type"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "type": { "const": "hash64" },
+                            "prefix": { "type": "string", "default": "k_" },
+                            "length": { "type": "integer", "minimum": 1, "maximum": 13, "default": 11 }
+                        }
+                    },
+                    {
+                        "type": "object",
+                        "required": ["type"],
+                        "additionalProperties": false,
+                        "properties": {
+                            "type": { "const": "slug" },
+                            "prefix": { "type": "string", "default": "" },
+                            "maxLength": { "type": "integer", "minimum": 1 }
+                        }
+                    }
+                ]
+            },
+            "frontEnds": {
+                "type": "object",
+                "description": "File name suffix to language front end",
+                "additionalProperties": {
+                    "enum": ["script", "vue", "svelte", "angular", "markdown", "mdx", "html"]
+                }
+            },
+            "staticContent": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "codeBlocks": { "type": "boolean", "default": false },
+                    "scripts": { "type": "boolean", "default": false }
+                }
+            },
+            "exclude": string_array("Files and directories to skip; patterns without `/` match at any depth"),
+            "ignoreFiles": string_array("Ignore files read in every directory, in .gitignore syntax"),
+            "resource": {
+                "type": "object",
+                "required": ["savePath"],
+                "additionalProperties": false,
+                "properties": {
+                    "savePath": { "type": "string", "description": "Resource file path with {{lng}} and {{ns}}" },
+                    "loadPath": { "type": "string", "description": "Existing resource file path, defaults to savePath" }
+                }
+            },
+            "func": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "list": { "type": "array", "items": { "type": "string", "minLength": 1 }, "minItems": 1 },
+                    "hooks": string_array("Hooks returning a translation function"),
+                    "hocs": string_array("Higher-order components injecting a translation function"),
+                    "extensions": string_array("File extensions to extract function calls from"),
+                    "babylon": babylon
+                }
+            },
+            "trans": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "component": { "type": "string", "minLength": 1, "default": "Trans" },
+                    "i18nKey": { "type": "string", "minLength": 1, "default": "i18nKey" },
+                    "extensions": string_array("File extensions to extract Trans components from"),
+                    "babylon": babylon
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::config::ResourceConfig;
+    use crate::scan::js_config::JsConfig;
+
+    #[test]
+    fn test_validate_config() {
+        assert!(validate_config(&ScanConfig::default()).is_empty());
+
+        let mut config = ScanConfig::default()
+            .with_languages(vec!["zh".to_string(), "en".to_string(), "en".to_string()])
+            .with_default_language("ja".to_string())
+            .with_resource(ResourceConfig {
+                load_path: String::new(),
+                save_path: "i18n/messages.json".to_string(),
+            })
+            .with_key_strategy(KeyStrategyConfig::Hash64 {
+                prefix: "k_".to_string(),
+                length: 20,
+            });
+        config.func.list.clear();
+        let paths: Vec<_> = validate_config(&config)
+            .into_iter()
+            .map(|d| (d.severity.as_str(), d.path))
+            .collect();
+        let expected = [
+            ("warning", "lngs[2]"),
+            ("error", "defaultLng"),
+            ("error", "resource.savePath"),
+            ("error", "func.list"),
+            ("error", "keyStrategy.length"),
+        ];
+        let expected: Vec<_> = expected.iter().map(|(s, p)| (*s, p.to_string())).collect();
+        assert_eq!(paths, expected);
+    }
+
+    #[test]
+    fn test_schema_covers_config_keys() {
+        let schema = config_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        // 每个 schema 中的顶层配置项都能被识别
+        let mut sample = serde_json::Map::new();
+        for (key, property) in properties {
+            let value = match property["type"].as_str() {
+                Some("array") => json!([]),
+                Some("boolean") => json!(false),
+                Some("object") => json!({}),
+                _ => property.get("default").cloned().unwrap_or(json!("x")),
+            };
+            sample.insert(key.clone(), value);
+        }
+        sample.insert("keyStrategy".to_string(), json!({ "type": "slug", "maxLength": 8 }));
+        sample.insert("extends".to_string(), json!("base.json"));
+        sample.insert("resource".to_string(), json!({ "savePath": "{{lng}}.json", "loadPath": "" }));
+        let loaded = JsConfig::from_value(Value::Object(sample)).unwrap();
+        assert!(loaded.unknown_keys.is_empty(), "{:?}", loaded.unknown_keys);
+        // 每个配置项都在 schema 中
+        let serialized = serde_json::to_value(JsConfig::default()).unwrap();
+        for key in serialized.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "{} is missing from the schema", key);
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use super::config::ScanConfig;
+use super::config_check::{config_schema, ConfigDiagnostic};
+use super::js_config::{extends_layers, merge_values, read_config_value, JsConfig, LoadedJsConfig};
+use super::js_object::KeyLocations;
+
+/// 环境变量前缀，嵌套的配置项用 `__` 连接，如 `FAST_I18N_SCAN_DEFAULT_LNG`、`FAST_I18N_SCAN_FUNC__LIST`
+pub const ENV_PREFIX: &str = "FAST_I18N_SCAN_";
+
+/// 配置项的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    Default,
+    /// 配置文件，`extends` 继承的值记为被继承的文件
+    File(String),
+    /// 配置文件中 `command.<name>` 的子命令配置
+    Command(String),
+    /// 环境变量名
+    Env(String),
+    /// 命令行参数
+    Cli(String),
+    /// 通过库接口（如 Node 绑定）传入的配置对象
+    Api,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "{}", path),
+            ConfigSource::Command(name) => write!(f, "command.{}", name),
+            ConfigSource::Env(name) => write!(f, "env {}", name),
+            ConfigSource::Cli(flag) => write!(f, "{}", flag),
+            ConfigSource::Api => write!(f, "api"),
+        }
+    }
+}
+
+/// 分层配置：默认值、配置文件、子命令配置、环境变量、命令行参数依次覆盖
+///
+/// 各层按配置文件的格式合并（见 `merge_values`），并记录每个配置项最后由哪一层设置。
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    value: Value,
+    sources: BTreeMap<String, ConfigSource>,
+    key_locations: KeyLocations,
+}
+
+impl Default for LayeredConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayeredConfig {
+    /// 只包含默认值的配置
+    pub fn new() -> Self {
+        let mut layered = Self {
+            value: Value::Object(Map::new()),
+            sources: BTreeMap::new(),
+            key_locations: KeyLocations::new(),
+        };
+        layered.apply(default_config_value(), ConfigSource::Default);
+        layered
+    }
+
+    /// 叠加配置文件及其 `extends` 继承的文件
+    pub fn with_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let (value, key_locations) = read_config_value(path).map_err(Error::Config)?;
+        let mut layers = Vec::new();
+        extends_layers(path, value, &mut Vec::new(), &mut layers).map_err(Error::Config)?;
+        for (file, layer) in layers {
+            self.apply(layer, ConfigSource::File(file.display().to_string()));
+        }
+        self.key_locations = key_locations;
+        Ok(self)
+    }
+
+    /// 叠加配置文件中 `command.<name>` 的子命令配置
+    pub fn with_command(mut self, name: &str) -> Self {
+        if let Some(overlay) = self.value.get("command").and_then(|command| command.get(name)).cloned() {
+            self.apply(overlay, ConfigSource::Command(name.to_string()));
+        }
+        self
+    }
+
+    /// 叠加以 `ENV_PREFIX` 开头的环境变量，如 `with_env(std::env::vars())`
+    pub fn with_env<I: IntoIterator<Item = (String, String)>>(mut self, vars: I) -> Result<Self> {
+        let mut vars: Vec<(String, String)> = vars.into_iter().filter(|(name, _)| name.starts_with(ENV_PREFIX)).collect();
+        vars.sort();
+        for (name, raw) in vars {
+            let segments: Vec<String> = name[ENV_PREFIX.len()..].split("__").map(camel_case).collect();
+            let (path, schema) = resolve_path(&segments);
+            let value = parse_value(&raw, schema.as_ref()).map_err(|e| Error::Config(format!("{}: {}", name, e)))?;
+            self = self.with_value(&path.join("."), value, ConfigSource::Env(name));
+        }
+        Ok(self)
+    }
+
+    /// 叠加 `key=value` 形式的覆盖，key 为配置文件中的路径，如 `func.list=t;i18n.t`
+    pub fn with_override(self, assignment: &str, source: ConfigSource) -> Result<Self> {
+        let (key, raw) = assignment
+            .split_once('=')
+            .ok_or_else(|| Error::Config(format!("expected KEY=VALUE, got \"{}\"", assignment)))?;
+        let segments: Vec<String> = key.trim().split('.').map(str::to_string).collect();
+        let (path, schema) = resolve_path(&segments);
+        let value = parse_value(raw, schema.as_ref()).map_err(|e| Error::Config(format!("{}: {}", key, e)))?;
+        Ok(self.with_value(&path.join("."), value, source))
+    }
+
+    /// 叠加一个完整的配置对象，格式与配置文件相同
+    pub fn with_object(mut self, overlay: Value, source: ConfigSource) -> Self {
+        self.apply(overlay, source);
+        self
+    }
+
+    /// 设置一个配置项，`path` 以 `.` 分隔
+    pub fn with_value(mut self, path: &str, value: Value, source: ConfigSource) -> Self {
+        let overlay = path.rsplit('.').fold(value, |value, key| {
+            let mut map = Map::new();
+            map.insert(key.to_string(), value);
+            Value::Object(map)
+        });
+        self.apply(overlay, source);
+        self
+    }
+
+    fn apply(&mut self, overlay: Value, source: ConfigSource) {
+        let mut leaves = Vec::new();
+        collect_leaves(&overlay, String::new(), &mut leaves);
+        for (path, _) in leaves {
+            let nested = [format!("{}.", path), format!("{}[", path)];
+            self.sources.retain(|key, _| !nested.iter().any(|prefix| key.starts_with(prefix.as_str())));
+            self.sources.insert(path, source.clone());
+        }
+        merge_values(&mut self.value, overlay);
+    }
+
+    /// 合并后的配置
+    pub fn build(&self) -> Result<LoadedJsConfig> {
+        let loaded = JsConfig::from_value(self.value.clone())?;
+        Ok(LoadedJsConfig {
+            key_locations: self.key_locations.clone(),
+            ..loaded
+        })
+    }
+
+    /// 配置项最后由哪一层设置，路径本身没有记录时使用最近的上级配置项
+    pub fn source_of(&self, path: &str) -> &ConfigSource {
+        let mut path = path;
+        loop {
+            if let Some(source) = self.sources.get(path) {
+                return source;
+            }
+            match path.rfind(['.', '[']) {
+                Some(end) => path = &path[..end],
+                None => return &ConfigSource::Default,
+            }
+        }
+    }
+
+    /// 逐项列出合并后的配置及其来源，供 `--print-config` 输出
+    pub fn describe(&self) -> String {
+        let mut leaves = Vec::new();
+        collect_leaves(&self.value, String::new(), &mut leaves);
+        let loaded = self.build().ok();
+        // keyStrategy 按生效的变体输出，包括未配置时各变体的默认值
+        if let Some(key_strategy) = loaded.as_ref().and_then(|l| serde_json::to_value(&l.config.key_strategy).ok()) {
+            leaves.retain(|(path, _)| path != "keyStrategy" && !path.starts_with("keyStrategy."));
+            collect_leaves(&key_strategy, "keyStrategy".to_string(), &mut leaves);
+            leaves.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        let mut lines: Vec<(String, String)> = leaves
+            .into_iter()
+            .filter(|(path, _)| path != "command" && !path.starts_with("command."))
+            .map(|(path, value)| {
+                let source = self.source_of(&path).to_string();
+                (format!("{} = {}", path, value), source)
+            })
+            .collect();
+
+        // 未配置 resource 时词条路径由 output 推导
+        if let (None, Some(loaded)) = (self.value.get("resource"), &loaded) {
+            let resource = loaded.config.to_scan_config().resource;
+            let source = if loaded.config.output.is_empty() {
+                ConfigSource::Default.to_string()
+            } else {
+                format!("from output, {}", self.source_of("output"))
+            };
+            lines.push((format!("resource.loadPath = {}", Value::from(resource.load_path)), source.clone()));
+            lines.push((format!("resource.savePath = {}", Value::from(resource.save_path)), source));
+        }
+
+        let width = lines.iter().map(|(line, _)| line.chars().count()).max().unwrap_or(0);
+        lines
+            .iter()
+            .map(|(line, source)| format!("{:<width$}  # {}\n", line, source, width = width))
+            .collect()
+    }
+
+    /// 未识别的配置项及设置它的层，来自配置文件的项带行列号
+    pub fn unknown_keys(&self) -> Vec<(ConfigSource, ConfigDiagnostic)> {
+        let Ok(loaded) = self.build() else {
+            return Vec::new();
+        };
+        loaded
+            .unknown_keys
+            .iter()
+            .map(|key| {
+                let source = self.source_of(key).clone();
+                let mut diagnostic = ConfigDiagnostic::warning(key, "unknown config key, it is ignored".to_string());
+                if let (ConfigSource::File(_), Some((line, column))) = (&source, self.key_locations.get(key)) {
+                    diagnostic.line = Some(*line);
+                    diagnostic.column = Some(*column);
+                }
+                (source, diagnostic)
+            })
+            .collect()
+    }
+}
+
+/// 默认值层，取自 `ScanConfig::default()`，写法与配置文件相同
+///
+/// 不含 `resource` 与 `keyStrategy`：前者未配置时由 `output` 推导，后者的各变体默认值不同，
+/// 不能与文件中的值逐项合并，两者由 `LayeredConfig::describe` 按生效的值输出。
+pub fn default_config_value() -> Value {
+    let defaults = ScanConfig::default();
+    json!({
+        "input": [],
+        "output": "",
+        "lngs": defaults.lngs,
+        "defaultLng": defaults.default_lng,
+        "ns": defaults.ns,
+        "defaultNs": defaults.default_ns,
+        "nsSeparator": defaults.ns_separator,
+        "debug": false,
+        "sort": false,
+        "removeUnusedKeys": false,
+        "duplicateKeys": defaults.duplicate_keys,
+        "hashCollisions": defaults.hash_collisions,
+        "pluralStyle": defaults.plural_style,
+        "frontEnds": {},
+        "staticContent": {
+            "codeBlocks": defaults.static_content.code_blocks,
+            "scripts": defaults.static_content.scripts
+        },
+        "exclude": defaults.exclude,
+        "ignoreFiles": defaults.ignore_files,
+        "func": {
+            "list": defaults.func.list,
+            "hooks": defaults.func.hooks,
+            "hocs": defaults.func.hocs,
+            "extensions": defaults.func.extensions
+        },
+        "trans": {
+            "component": defaults.trans.component,
+            "i18nKey": defaults.trans.i18n_key,
+            "extensions": defaults.trans.extensions
+        }
+    })
+}
+
+/// 列出值中的叶子（非空对象以外的值）及其路径
+fn collect_leaves(value: &Value, path: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let child = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                collect_leaves(value, child, out);
+            }
+        }
+        _ => out.push((path, value.clone())),
+    }
+}
+
+/// `DEFAULT_LNG` → `defaultLng`
+fn camel_case(segment: &str) -> String {
+    let mut out = String::new();
+    for (i, word) in segment.split('_').filter(|w| !w.is_empty()).enumerate() {
+        let word = word.to_lowercase();
+        if i == 0 {
+            out.push_str(&word);
+        } else {
+            let mut chars = word.chars();
+            out.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// 按 JSON Schema 把路径中的各段对应到配置项的实际写法（忽略大小写与 `_`），并返回该项的 schema
+fn resolve_path(segments: &[String]) -> (Vec<String>, Option<Value>) {
+    let normalize = |key: &str| key.to_lowercase().replace('_', "");
+    let mut node = Some(config_schema());
+    let mut path = Vec::new();
+    for segment in segments {
+        let candidates: Vec<&Map<String, Value>> = match &node {
+            Some(node) => std::iter::once(node)
+                .chain(node["oneOf"].as_array().into_iter().flatten())
+                .filter_map(|n| n["properties"].as_object())
+                .collect(),
+            None => Vec::new(),
+        };
+        let found = candidates
+            .iter()
+            .flat_map(|properties| properties.iter())
+            .find(|(key, _)| normalize(key) == normalize(segment));
+        match found {
+            Some((key, schema)) => {
+                path.push(key.clone());
+                node = Some(schema.clone());
+            }
+            None => {
+                path.push(segment.clone());
+                node = node.and_then(|n| n.get("additionalProperties").filter(|a| a.is_object()).cloned());
+            }
+        }
+    }
+    (path, node)
+}
+
+/// 按 schema 中的类型解析字符串：以 `[` 或 `{` 开头的按 JSON 解析，列表以 `;` 分隔
+fn parse_value(raw: &str, schema: Option<&Value>) -> Result<Value, String> {
+    let raw = raw.trim();
+    if raw.starts_with('[') || raw.starts_with('{') {
+        return serde_json::from_str(raw).map_err(|e| format!("invalid JSON: {}", e));
+    }
+    let kind = schema.and_then(|s| s["type"].as_str()).unwrap_or("string");
+    match kind {
+        "array" => Ok(Value::from(
+            raw.split(';').map(str::trim).filter(|s| !s.is_empty()).collect::<Vec<_>>(),
+        )),
+        "boolean" => match raw {
+            "true" | "1" => Ok(Value::Bool(true)),
+            "false" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("expected true or false, got \"{}\"", raw)),
+        },
+        "integer" | "number" => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("expected a number, got \"{}\"", raw)),
+        "object" => Err("expected a JSON object".to_string()),
+        _ => Ok(Value::from(raw)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_defaults_match_scan_config() {
+        let config = LayeredConfig::new().build().unwrap().config.to_scan_config();
+        let defaults = ScanConfig::default();
+        assert!(config.input.is_empty());
+        assert_eq!(config.lngs, defaults.lngs);
+        assert_eq!(config.resource.save_path, defaults.resource.save_path);
+        assert_eq!(config.func.list, defaults.func.list);
+        assert_eq!(config.exclude, defaults.exclude);
+    }
+
+    #[test]
+    fn test_layers_and_sources() {
+        let dir = std::env::temp_dir().join(format!("config-layers-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("i18n.config.js");
+        fs::write(
+            &path,
+            "module.exports = {\n  input: ['src/**/*.js'],\n  output: 'i18n',\n  lngs: ['zh', 'en'],\n  defaultLng: 'zh',\n  keyStrategy: { type: 'hash64' },\n  command: { lint: { lngs: ['zh'] } },\n};\n",
+        )
+        .unwrap();
+
+        let env = vec![
+            ("FAST_I18N_SCAN_FUNC__LIST".to_string(), "t;i18n.t".to_string()),
+            ("FAST_I18N_SCAN_STATIC_CONTENT__CODE_BLOCKS".to_string(), "true".to_string()),
+            ("FAST_I18N_SCAN_KEY_STRATEGY__LENGTH".to_string(), "8".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ];
+        let layered = LayeredConfig::new()
+            .with_file(&path)
+            .unwrap()
+            .with_command("lint")
+            .with_env(env)
+            .unwrap()
+            .with_override("output=dist", ConfigSource::Cli("--set".to_string()))
+            .unwrap()
+            .with_override("default_lng=en", ConfigSource::Cli("--set".to_string()))
+            .unwrap();
+        let loaded = layered.build().unwrap();
+        assert!(loaded.unknown_keys.is_empty(), "{:?}", loaded.unknown_keys);
+        let config = loaded.config.to_scan_config();
+        assert_eq!(config.lngs, vec!["zh"]);
+        assert_eq!(config.default_lng, "en");
+        assert_eq!(config.func.list, vec!["t", "i18n.t"]);
+        assert!(config.static_content.code_blocks);
+        assert_eq!(config.save_path("en", "translation"), "dist/en.json");
+        assert_eq!(
+            config.key_strategy,
+            super::super::key_strategy::KeyStrategyConfig::Hash64 {
+                prefix: "k_".to_string(),
+                length: 8
+            }
+        );
+
+        assert_eq!(layered.source_of("lngs"), &ConfigSource::Command("lint".to_string()));
+        assert_eq!(layered.source_of("input"), &ConfigSource::File(path.display().to_string()));
+        assert_eq!(layered.source_of("func.list"), &ConfigSource::Env("FAST_I18N_SCAN_FUNC__LIST".to_string()));
+        assert_eq!(layered.source_of("func.hooks"), &ConfigSource::Default);
+        let described = layered.describe();
+        assert!(described.contains("defaultLng = \"en\""));
+        assert!(described.lines().any(|line| line.starts_with("resource.savePath = \"dist/{{lng}}.json\"") && line.ends_with("# from output, --set")));
+        assert!(described.lines().any(|line| line.starts_with("lngs = [\"zh\"]") && line.ends_with("# command.lint")));
+        assert!(!described.lines().any(|line| line.starts_with("command")));
+        assert!(described.lines().any(|line| line.starts_with("keyStrategy.type = \"hash64\"") && line.ends_with(&format!("# {}", path.display()))));
+        assert!(described.lines().any(|line| line.starts_with("keyStrategy.length = 8") && line.ends_with("# env FAST_I18N_SCAN_KEY_STRATEGY__LENGTH")));
+        assert!(described.lines().any(|line| line.starts_with("keyStrategy.prefix = \"k_\"") && line.ends_with("# default")));
+        assert!(described.lines().any(|line| line.starts_with("output = \"dist\"") && line.ends_with("# --set")));
+
+        assert!(LayeredConfig::new().with_override("debug=maybe", ConfigSource::Default).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_describe_defaults() {
+        let described = LayeredConfig::new().describe();
+        for line in [
+            "output = \"\"",
+            "keyStrategy.type = \"legacy\"",
+            "keyStrategy.prefix = \"k_\"",
+            "resource.loadPath = ",
+            "resource.savePath = ",
+        ] {
+            assert!(described.lines().any(|l| l.starts_with(line) && l.ends_with("# default")), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys_with_source() {
+        let dir = std::env::temp_dir().join(format!("config-layers-unknown-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("i18n.config.js");
+        fs::write(&path, "module.exports = {\n  input: [],\n  foo: 1,\n};\n").unwrap();
+
+        let layered = LayeredConfig::new()
+            .with_file(&path)
+            .unwrap()
+            .with_override("bogus=1", ConfigSource::Cli("--set".to_string()))
+            .unwrap();
+        let unknown: Vec<_> = layered
+            .unknown_keys()
+            .into_iter()
+            .map(|(source, d)| (source.to_string(), d.path, d.line))
+            .collect();
+        assert_eq!(
+            unknown,
+            vec![
+                ("--set".to_string(), "bogus".to_string(), None),
+                (path.display().to_string(), "foo".to_string(), Some(3)),
+            ]
+        );
+        assert!(LayeredConfig::new().unknown_keys().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_object() {
+        let layered = LayeredConfig::new().with_object(
+            json!({ "lngs": ["zh", "ja"], "func": { "list": ["$t"] }, "resource": { "savePath": "locales/{{lng}}.json" } }),
+            ConfigSource::Api,
+        );
+        let config = layered.build().unwrap().config.to_scan_config();
+        assert_eq!(config.lngs, vec!["zh", "ja"]);
+        assert_eq!(config.func.list, vec!["$t"]);
+        assert_eq!(config.func.extensions, ScanConfig::default().func.extensions);
+        assert_eq!(config.save_path("ja", "translation"), "locales/ja.json");
+        assert_eq!(layered.source_of("func.list"), &ConfigSource::Api);
+    }
+}
@@ -0,0 +1,51 @@
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+
+/// context 与 key 之间的分隔符，与 i18next 默认的 `contextSeparator` 及 `hashKey` 一致
+pub const CONTEXT_SEPARATOR: &str = "_";
+
+/// 为带 context 的调用生成独立的 key，如 `t('打开', { context: 'verb' })` → `打开_verb`
+///
+/// 空字符串的 context 与 i18next 一样视为未指定。
+pub fn resolve_context(occurrence: &mut KeyOccurrence) {
+    occurrence.options.context = occurrence.options.context.take().filter(|ctx| !ctx.is_empty());
+    if let Some(context) = &occurrence.options.context {
+        occurrence.key = format!("{}{}{}", occurrence.key, CONTEXT_SEPARATOR, context);
+    }
+}
+
+/// 去掉 `resolve_context` 添加的 context 后缀
+pub fn strip_context<'a>(key: &'a str, context: Option<&str>) -> &'a str {
+    context
+        .and_then(|ctx| key.strip_suffix(ctx))
+        .and_then(|rest| rest.strip_suffix(CONTEXT_SEPARATOR))
+        .unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::parse_func_from_string_by_babel::ParseOptions;
+
+    #[test]
+    fn test_resolve_and_strip_context() {
+        let mut occurrence = KeyOccurrence {
+            key: "打开".to_string(),
+            options: ParseOptions {
+                context: Some("verb".to_string()),
+                ..Default::default()
+            },
+            line: 1,
+            column: 1,
+        };
+        resolve_context(&mut occurrence);
+        assert_eq!(occurrence.key, "打开_verb");
+        assert_eq!(strip_context(&occurrence.key, Some("verb")), "打开");
+        assert_eq!(strip_context("打开", None), "打开");
+
+        occurrence.key = "打开".to_string();
+        occurrence.options.context = Some(String::new());
+        resolve_context(&mut occurrence);
+        assert_eq!(occurrence.key, "打开");
+        assert_eq!(occurrence.options.context, None);
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Mutex;
+
+use super::diff::{Finding, Severity};
+use super::span::Span;
+
+/// 接收扫描过程中发现的问题
+///
+/// 库本身不向终端输出，CLI 与嵌入方通过实现该 trait 决定如何展示，闭包 `Fn(&Finding)` 可直接使用。
+pub trait DiagnosticSink: Send + Sync {
+    fn report(&self, finding: &Finding);
+}
+
+impl<F> DiagnosticSink for F
+where
+    F: Fn(&Finding) + Send + Sync,
+{
+    fn report(&self, finding: &Finding) {
+        self(finding)
+    }
+}
+
+/// 把问题收集到内存中
+#[derive(Debug, Default)]
+pub struct CollectingSink {
+    findings: Mutex<Vec<Finding>>,
+}
+
+impl CollectingSink {
+    pub fn new() -> CollectingSink {
+        CollectingSink::default()
+    }
+
+    /// 取出已收集的问题
+    pub fn take(&self) -> Vec<Finding> {
+        std::mem::take(&mut *self.findings.lock().unwrap())
+    }
+}
+
+impl DiagnosticSink for CollectingSink {
+    fn report(&self, finding: &Finding) {
+        self.findings.lock().unwrap().push(finding.clone());
+    }
+}
+
+/// 源码中某处的错误
+pub(crate) fn error_at(kind: &str, filepath: &str, span: Span, message: &str) -> Finding {
+    Finding {
+        severity: Severity::Error,
+        kind: kind.to_string(),
+        filepath: filepath.to_string(),
+        line: span.start_line,
+        column: span.start_column,
+        message: message.to_string(),
+        span: (!span.is_unknown()).then_some(span),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collecting_sink() {
+        let sink = CollectingSink::new();
+        sink.report(&error_at("ParseError", "a.js", Span::new(1, 2, 1, 5), "Unexpected \"}\""));
+        sink.report(&error_at("HashCollision", "", Span::default(), "collision"));
+
+        let findings = sink.take();
+        assert_eq!(findings.len(), 2);
+        assert_eq!((findings[0].line, findings[0].column), (1, 2));
+        assert_eq!(findings[1].span, None);
+        assert!(sink.take().is_empty());
+    }
+
+    #[test]
+    fn test_closure_sink() {
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        let sink = |_: &Finding| {
+            count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        };
+        sink.report(&error_at("ParseError", "a.js", Span::point(1, 1), "x"));
+        assert_eq!(count.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
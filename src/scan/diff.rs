@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use super::config::ScanConfig;
+use super::globs::resolve_files;
+use super::span::Span;
+use super::{ScanResult, Scanner};
+
+/// 两次扫描结果之间的差异
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanDiff {
+    /// 新增的 key，按新结果中的顺序
+    pub added_keys: Vec<String>,
+    /// 删除的 key，按旧结果中的顺序
+    pub removed_keys: Vec<String>,
+    /// 两次都存在但默认值不同的 key
+    pub changed_defaults: Vec<DefaultValueChange>,
+    /// 新结果中新出现的错误与警告
+    pub new_findings: Vec<Finding>,
+    /// 旧结果中存在、新结果中已修复的错误与警告
+    pub fixed_findings: Vec<Finding>,
+}
+
+/// 默认值变化，`None` 表示没有默认值（以 key 作为句子）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DefaultValueChange {
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// 扫描结果中的一条错误或警告
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+    /// 错误或警告类型，如 `HardCodedText`
+    pub kind: String,
+    pub filepath: String,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+    /// 问题所在的源码范围，只有起始位置时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+impl Finding {
+    /// 问题所在的范围，没有范围时取起始位置
+    pub fn span(&self) -> Span {
+        self.span.unwrap_or(Span::point(self.line, self.column))
+    }
+
+    /// 比较时忽略行列号，代码上方的增删不会让已有问题变成“新增”
+    fn identity(&self) -> (Severity, &str, &str, &str) {
+        (self.severity, &self.kind, &self.filepath, &self.message)
+    }
+}
+
+/// 列出扫描结果中的所有错误与警告
+pub fn findings(result: &ScanResult) -> Vec<Finding> {
+    let errors = result.errors.iter().map(|e| Finding {
+        severity: Severity::Error,
+        kind: format!("{:?}", e.error_type),
+        filepath: e.filepath.clone(),
+        line: e.line,
+        column: e.column,
+        message: e.message.clone(),
+        span: e.span,
+    });
+    let warnings = result.warnings.iter().map(|w| Finding {
+        severity: Severity::Warning,
+        kind: format!("{:?}", w.warning_type),
+        filepath: w.filepath.clone(),
+        line: w.line,
+        column: w.column,
+        message: w.message.clone(),
+        span: w.span,
+    });
+    errors.chain(warnings).collect()
+}
+
+/// 比较两次扫描结果
+///
+/// 错误与警告按（级别、类型、文件、信息）匹配，同一问题出现多次时按次数比较。
+pub fn diff_results(before: &ScanResult, after: &ScanResult) -> ScanDiff {
+    let before_keys: HashSet<&str> = before.keys.iter().map(|k| k.as_str()).collect();
+    let after_keys: HashSet<&str> = after.keys.iter().map(|k| k.as_str()).collect();
+
+    let added_keys = after
+        .keys
+        .iter()
+        .filter(|key| !before_keys.contains(key.as_str()))
+        .cloned()
+        .collect();
+    let removed_keys = before
+        .keys
+        .iter()
+        .filter(|key| !after_keys.contains(key.as_str()))
+        .cloned()
+        .collect();
+    let changed_defaults = after
+        .keys
+        .iter()
+        .filter(|key| before_keys.contains(key.as_str()))
+        .filter_map(|key| {
+            let old = before.translations.get(key);
+            let new = after.translations.get(key);
+            (old != new).then(|| DefaultValueChange {
+                key: key.clone(),
+                before: old.cloned(),
+                after: new.cloned(),
+            })
+        })
+        .collect();
+
+    let before_findings = findings(before);
+    let after_findings = findings(after);
+    ScanDiff {
+        added_keys,
+        removed_keys,
+        changed_defaults,
+        new_findings: unmatched(&after_findings, &before_findings),
+        fixed_findings: unmatched(&before_findings, &after_findings),
+    }
+}
+
+/// `findings` 中多于 `others` 的部分
+fn unmatched(findings: &[Finding], others: &[Finding]) -> Vec<Finding> {
+    let mut remaining: HashMap<_, usize> = HashMap::new();
+    for finding in others {
+        *remaining.entry(finding.identity()).or_default() += 1;
+    }
+    findings
+        .iter()
+        .filter(|finding| match remaining.get_mut(&finding.identity()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// 读取 `-f json` 输出的扫描结果
+pub fn load_result(path: &str) -> Result<ScanResult> {
+    let content = fs::read_to_string(path).map_err(|e| Error::file(path, e))?;
+    serde_json::from_str(&content).map_err(|e| Error::Resource {
+        path: path.into(),
+        message: format!("Invalid scan result: {}", e),
+    })
+}
+
+/// 扫描目录树中所有可扫描的文件，结果中的文件路径相对于 `root`
+///
+/// 文件按 `root/**` 展开，遵循配置中的 `exclude` 与忽略文件。
+pub fn scan_tree(root: &str, config: &ScanConfig) -> Result<ScanResult> {
+    let root_path = Path::new(root);
+    if !root_path.is_dir() {
+        return Err(Error::file(root, std::io::Error::other("not a directory")));
+    }
+    let root = root.trim_end_matches('/');
+    let files: Vec<String> = resolve_files(&[format!("{}/**", root)], config)
+        .into_iter()
+        .filter(|file| config.should_scan(file))
+        .collect();
+
+    let mut result = Scanner::with_config(config.clone()).scan_files(&files)?;
+    let prefix = format!("{}/", root);
+    let relative = |path: &str| path.strip_prefix(&prefix).unwrap_or(path).to_string();
+    for error in &mut result.errors {
+        error.filepath = relative(&error.filepath);
+        error.message = error.message.replace(&prefix, "");
+    }
+    for warning in &mut result.warnings {
+        warning.filepath = relative(&warning.filepath);
+        warning.message = warning.message.replace(&prefix, "");
+    }
+    Ok(result)
+}
+
+/// 分别扫描两个目录树（如两个提交的 worktree）并比较结果
+pub fn diff_trees(before: &str, after: &str, config: &ScanConfig) -> Result<ScanDiff> {
+    let before = scan_tree(before, config)?;
+    let after = scan_tree(after, config)?;
+    Ok(diff_results(&before, &after))
+}
+
+impl ScanDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_keys.is_empty()
+            && self.removed_keys.is_empty()
+            && self.changed_defaults.is_empty()
+            && self.new_findings.is_empty()
+            && self.fixed_findings.is_empty()
+    }
+
+    /// 一行摘要，如 `2 added, 1 removed, 0 changed, 1 new finding(s), 3 fixed`
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} changed, {} new finding(s), {} fixed",
+            self.added_keys.len(),
+            self.removed_keys.len(),
+            self.changed_defaults.len(),
+            self.new_findings.len(),
+            self.fixed_findings.len()
+        )
+    }
+
+    /// 便于阅读的表格，空的分组不输出
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+
+        let rows: Vec<Vec<String>> = self.added_keys.iter().map(|key| vec!["+".into(), key.clone()]).collect();
+        push_section(&mut out, "Added keys", &rows);
+
+        let rows: Vec<Vec<String>> = self.removed_keys.iter().map(|key| vec!["-".into(), key.clone()]).collect();
+        push_section(&mut out, "Removed keys", &rows);
+
+        let rows: Vec<Vec<String>> = self
+            .changed_defaults
+            .iter()
+            .map(|change| {
+                vec![
+                    "~".into(),
+                    change.key.clone(),
+                    quote(change.before.as_deref()),
+                    "->".into(),
+                    quote(change.after.as_deref()),
+                ]
+            })
+            .collect();
+        push_section(&mut out, "Changed default values", &rows);
+
+        push_section(&mut out, "New findings", &finding_rows("+", &self.new_findings));
+        push_section(&mut out, "Fixed findings", &finding_rows("-", &self.fixed_findings));
+
+        if self.is_empty() {
+            out.push_str("No changes\n");
+        } else {
+            out.push_str(&self.summary());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn quote(value: Option<&str>) -> String {
+    match value {
+        Some(value) => format!("\"{}\"", value),
+        None => "(none)".to_string(),
+    }
+}
+
+fn finding_rows(marker: &str, findings: &[Finding]) -> Vec<Vec<String>> {
+    findings
+        .iter()
+        .map(|finding| {
+            vec![
+                marker.to_string(),
+                finding.severity.as_str().to_string(),
+                finding.kind.clone(),
+                format!("{}:{}:{}", finding.filepath, finding.line, finding.column),
+                finding.message.clone(),
+            ]
+        })
+        .collect()
+}
+
+/// 输出一个分组：标题行加按列对齐的内容
+pub(super) fn push_section(out: &mut String, title: &str, rows: &[Vec<String>]) {
+    if rows.is_empty() {
+        return;
+    }
+    out.push_str(&format!("{} ({})\n", title, rows.len()));
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| rows.iter().filter_map(|row| row.get(i)).map(|cell| display_width(cell)).max().unwrap_or(0))
+        .collect();
+    for row in rows {
+        let mut line = String::from(" ");
+        for (i, cell) in row.iter().enumerate() {
+            line.push(' ');
+            line.push_str(cell);
+            if i + 1 < row.len() {
+                line.push_str(&" ".repeat(widths[i] - display_width(cell)));
+            }
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+/// 终端显示宽度，中日韩等全角字符按两列计算
+pub(super) fn display_width(s: &str) -> usize {
+    s.chars().map(|c| if (c as u32) >= 0x2E80 { 2 } else { 1 }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::{ErrorType, ScanError, ScanStats};
+
+    fn result(keys: &[(&str, Option<&str>)], hard_coded: &[(&str, u32, &str)]) -> ScanResult {
+        ScanResult {
+            keys: keys.iter().map(|(key, _)| key.to_string()).collect(),
+            translations: keys
+                .iter()
+                .filter_map(|(key, value)| value.map(|v| (key.to_string(), v.to_string())))
+                .collect(),
+            generated_keys: HashMap::new(),
+            namespaces: HashMap::new(),
+            contexts: HashMap::new(),
+            plural_keys: Vec::new(),
+            plural_translations: HashMap::new(),
+            errors: hard_coded
+                .iter()
+                .map(|(filepath, line, value)| ScanError {
+                    filepath: filepath.to_string(),
+                    line: *line,
+                    column: 1,
+                    message: format!("Hard-coded Chinese text found: {}", value),
+                    error_type: ErrorType::HardCodedText,
+                    span: None,
+                })
+                .collect(),
+            warnings: Vec::new(),
+            stats: ScanStats {
+                files_scanned: 0,
+                keys_found: keys.len(),
+                errors_count: hard_coded.len(),
+                warnings_count: 0,
+                processing_time_ms: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_diff_results() {
+        let before = result(
+            &[("save", Some("保存")), ("cancel", None), ("old", None)],
+            &[("a.tsx", 3, "你好"), ("a.tsx", 8, "再见"), ("a.tsx", 9, "再见")],
+        );
+        let after = result(
+            &[("save", Some("保存修改")), ("cancel", None), ("new", Some("新的"))],
+            // 行号变化不算新问题，同一问题少了一次算修复
+            &[("a.tsx", 5, "你好"), ("a.tsx", 10, "再见"), ("b.tsx", 1, "标题")],
+        );
+        let diff = diff_results(&before, &after);
+        assert_eq!(diff.added_keys, vec!["new"]);
+        assert_eq!(diff.removed_keys, vec!["old"]);
+        assert_eq!(
+            diff.changed_defaults,
+            vec![DefaultValueChange {
+                key: "save".to_string(),
+                before: Some("保存".to_string()),
+                after: Some("保存修改".to_string()),
+            }]
+        );
+        assert_eq!(diff.new_findings.len(), 1);
+        assert_eq!(diff.new_findings[0].filepath, "b.tsx");
+        assert_eq!(diff.fixed_findings.len(), 1);
+        assert_eq!(diff.fixed_findings[0].line, 9);
+
+        let table = diff.to_table();
+        assert!(table.contains("Added keys (1)\n  + new\n"));
+        assert!(table.contains("  ~ save \"保存\" -> \"保存修改\"\n"));
+        assert!(table.ends_with("1 added, 1 removed, 1 changed, 1 new finding(s), 1 fixed\n"));
+        assert_eq!(diff_results(&after, &after).to_table(), "No changes\n");
+    }
+
+    #[test]
+    fn test_diff_trees() {
+        let before = tempfile::TempDir::new().unwrap();
+        let after = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(before.path().join("src")).unwrap();
+        fs::create_dir_all(after.path().join("src/node_modules")).unwrap();
+        fs::write(before.path().join("src/a.tsx"), "t('save');\nconst s = '未翻译';\n").unwrap();
+        fs::write(after.path().join("src/a.tsx"), "t('save');\nt('open');\n").unwrap();
+        fs::write(after.path().join("src/node_modules/x.js"), "t('vendor');\n").unwrap();
+
+        let config = ScanConfig::default();
+        let diff = diff_trees(
+            &before.path().to_string_lossy(),
+            &after.path().to_string_lossy(),
+            &config,
+        )
+        .unwrap();
+        assert_eq!(diff.added_keys, vec!["open"]);
+        assert!(diff.removed_keys.is_empty());
+        assert!(diff.new_findings.is_empty());
+        assert_eq!(diff.fixed_findings.len(), 1);
+        assert_eq!(diff.fixed_findings[0].filepath, "src/a.tsx");
+        assert_eq!(diff.fixed_findings[0].kind, "HardCodedText");
+    }
+}
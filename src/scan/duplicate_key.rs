@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+
+/// 同一个 key 在不同位置给出不同默认值时的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicateKeyStrategy {
+    /// 以最先扫描到的值为准
+    First,
+    /// 以最后扫描到的值为准
+    Last,
+    /// 以最先扫描到的值为准，并报告 DuplicateKey 错误
+    #[default]
+    Error,
+}
+
+/// 带文件路径的 key 出现位置
+#[derive(Debug, Clone)]
+pub struct KeyLocation {
+    pub filepath: String,
+    pub occurrence: KeyOccurrence,
+}
+
+/// 同一个 key 的冲突记录
+#[derive(Debug, Clone)]
+pub struct KeyConflict {
+    pub key: String,
+    /// 冲突的字段名：defaultValue 或 defaultValue_plural
+    pub fields: Vec<&'static str>,
+    /// 所有给出了冲突字段的位置，按扫描顺序排列
+    pub locations: Vec<KeyLocation>,
+}
+
+impl KeyConflict {
+    /// 生成错误信息，列出每一个冲突位置及其取值
+    pub fn message(&self) -> String {
+        let locations = self
+            .locations
+            .iter()
+            .map(|loc| {
+                let values = self
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        conflict_field(&loc.occurrence, field).map(|v| format!("{}=\"{}\"", field, v))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "{}:{}:{} ({})",
+                    loc.filepath, loc.occurrence.line, loc.occurrence.column, values
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        format!(
+            "Conflicting {} for key \"{}\": {}",
+            self.fields.join("/"),
+            self.key,
+            locations
+        )
+    }
+}
+
+/// 合并后的 key 集合
+#[derive(Debug, Clone, Default)]
+pub struct MergedKeys {
+    /// 按首次出现顺序去重后的 key
+    pub keys: Vec<String>,
+    /// key 对应的默认值（已按策略解决冲突）
+    pub translations: HashMap<String, String>,
+    /// 带 `count` 的 key，按首次出现顺序排列
+    pub plural_keys: Vec<String>,
+    /// key 对应的复数默认值 `defaultValue_plural`
+    pub plural_translations: HashMap<String, String>,
+    pub conflicts: Vec<KeyConflict>,
+}
+
+/// context 不同的调用在合并前已被 `context::resolve_context` 拆成不同的 key，不会在此冲突
+const CONFLICT_FIELDS: [&str; 2] = ["defaultValue", "defaultValue_plural"];
+
+fn conflict_field<'a>(occurrence: &'a KeyOccurrence, field: &str) -> Option<&'a str> {
+    match field {
+        "defaultValue" => occurrence.options.default_value.as_deref(),
+        "defaultValue_plural" => occurrence.options.default_value_plural.as_deref(),
+        _ => None,
+    }
+}
+
+/// 合并所有文件中的 key 出现位置，按策略得到默认值并找出冲突
+///
+/// 未给出某个字段的调用（如 `t('save')`）不参与该字段的比较。
+pub fn merge_occurrences(locations: &[KeyLocation], strategy: DuplicateKeyStrategy) -> MergedKeys {
+    let mut merged = MergedKeys::default();
+    let mut grouped: HashMap<&str, Vec<&KeyLocation>> = HashMap::new();
+
+    for location in locations {
+        let key = location.occurrence.key.as_str();
+        let group = grouped.entry(key).or_default();
+        if group.is_empty() {
+            merged.keys.push(key.to_string());
+        }
+        group.push(location);
+    }
+
+    for key in &merged.keys {
+        let group = &grouped[key.as_str()];
+
+        let mut values = group
+            .iter()
+            .filter_map(|loc| loc.occurrence.options.default_value.as_ref());
+        let value = match strategy {
+            DuplicateKeyStrategy::Last => values.next_back(),
+            DuplicateKeyStrategy::First | DuplicateKeyStrategy::Error => values.next(),
+        };
+        if let Some(value) = value {
+            merged.translations.insert(key.clone(), value.clone());
+        }
+
+        if group.iter().any(|loc| loc.occurrence.options.count.is_some()) {
+            merged.plural_keys.push(key.clone());
+        }
+        let mut plural_values = group
+            .iter()
+            .filter_map(|loc| loc.occurrence.options.default_value_plural.as_ref());
+        let plural_value = match strategy {
+            DuplicateKeyStrategy::Last => plural_values.next_back(),
+            DuplicateKeyStrategy::First | DuplicateKeyStrategy::Error => plural_values.next(),
+        };
+        if let Some(plural_value) = plural_value {
+            merged.plural_translations.insert(key.clone(), plural_value.clone());
+        }
+
+        if strategy != DuplicateKeyStrategy::Error {
+            continue;
+        }
+
+        let fields: Vec<&'static str> = CONFLICT_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| {
+                let mut distinct = group.iter().filter_map(|loc| conflict_field(&loc.occurrence, field));
+                match distinct.next() {
+                    Some(first) => distinct.any(|v| v != first),
+                    None => false,
+                }
+            })
+            .collect();
+
+        if !fields.is_empty() {
+            let locations = group
+                .iter()
+                .filter(|loc| fields.iter().any(|field| conflict_field(&loc.occurrence, field).is_some()))
+                .map(|loc| (*loc).clone())
+                .collect();
+            merged.conflicts.push(KeyConflict {
+                key: key.clone(),
+                fields,
+                locations,
+            });
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::parse_func_from_string_by_babel::ParseOptions;
+
+    fn location(filepath: &str, key: &str, default_value: Option<&str>) -> KeyLocation {
+        KeyLocation {
+            filepath: filepath.to_string(),
+            occurrence: KeyOccurrence {
+                key: key.to_string(),
+                options: ParseOptions {
+                    default_value: default_value.map(|s| s.to_string()),
+                    ..Default::default()
+                },
+                line: 1,
+                column: 3,
+            },
+        }
+    }
+
+    #[test]
+    fn test_conflicting_default_values() {
+        let locations = vec![
+            location("a.tsx", "save", Some("保存")),
+            location("b.tsx", "save", None),
+            location("c.tsx", "save", Some("存储")),
+        ];
+
+        let merged = merge_occurrences(&locations, DuplicateKeyStrategy::Error);
+        assert_eq!(merged.keys, vec!["save"]);
+        assert_eq!(merged.translations.get("save"), Some(&"保存".to_string()));
+        assert_eq!(merged.conflicts.len(), 1);
+
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.fields, vec!["defaultValue"]);
+        assert_eq!(conflict.locations.len(), 2);
+        let message = conflict.message();
+        assert!(message.contains("a.tsx:1:3"));
+        assert!(message.contains("c.tsx:1:3"));
+        assert!(!message.contains("b.tsx"));
+    }
+
+    #[test]
+    fn test_first_and_last_strategy() {
+        let locations = vec![
+            location("a.tsx", "save", Some("保存")),
+            location("b.tsx", "save", Some("存储")),
+        ];
+
+        let first = merge_occurrences(&locations, DuplicateKeyStrategy::First);
+        assert_eq!(first.translations.get("save"), Some(&"保存".to_string()));
+        assert!(first.conflicts.is_empty());
+
+        let last = merge_occurrences(&locations, DuplicateKeyStrategy::Last);
+        assert_eq!(last.translations.get("save"), Some(&"存储".to_string()));
+        assert!(last.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_same_value_is_not_a_conflict() {
+        let locations = vec![
+            location("a.tsx", "save", Some("保存")),
+            location("b.tsx", "save", Some("保存")),
+        ];
+
+        let merged = merge_occurrences(&locations, DuplicateKeyStrategy::Error);
+        assert!(merged.conflicts.is_empty());
+    }
+}
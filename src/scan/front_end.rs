@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use super::angular::AngularFrontEnd;
+use super::bindings::{self, TranslationBinding};
+use super::config::{extension_matches, FuncConfig, ScanConfig};
+use super::html::HtmlFrontEnd;
+use super::markdown::MarkdownFrontEnd;
+use super::parse_func_from_string_by_babel::{self, KeyOccurrence};
+use super::parse_trans_from_string_by_babel::{parse_trans_with_errors, TransParseOptions};
+use super::span::SourceError;
+use super::svelte::SvelteFrontEnd;
+use super::vue::VueFrontEnd;
+use super::zh_linter::ZhLinter;
+
+/// 语言前端：从一种源文件中提取翻译调用并检查未翻译的中文
+///
+/// 提取结果中的行列号必须指向原文件，命名空间与 context 由扫描器统一解析。
+pub trait FrontEnd: Send + Sync {
+    /// 提取文件中的翻译调用
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence>;
+
+    /// 同 `extract`，并返回无法解析的源码片段，默认没有
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        (self.extract(content, filepath, config), Vec::new())
+    }
+
+    /// 检查文件中未翻译的中文，结果写入 Linter 的全局结果
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, config: &ScanConfig);
+}
+
+/// 可在配置中按扩展名选择的内置前端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FrontEndKind {
+    /// JS/TS：函数调用与 Trans 组件
+    Script,
+    /// Vue 单文件组件
+    Vue,
+    /// Svelte 组件
+    Svelte,
+    /// Angular 模板
+    Angular,
+    /// Markdown 文档
+    Markdown,
+    /// MDX 文档，JSX 部分走 JS 提取流程
+    Mdx,
+    /// 静态 HTML 页面
+    Html,
+}
+
+impl FrontEndKind {
+    /// 根据类型创建前端实例
+    pub fn build(&self) -> Box<dyn FrontEnd> {
+        match self {
+            FrontEndKind::Script => Box::new(ScriptFrontEnd),
+            FrontEndKind::Vue => Box::new(VueFrontEnd),
+            FrontEndKind::Svelte => Box::new(SvelteFrontEnd),
+            FrontEndKind::Angular => Box::new(AngularFrontEnd),
+            FrontEndKind::Markdown => Box::new(MarkdownFrontEnd { mdx: false }),
+            FrontEndKind::Mdx => Box::new(MarkdownFrontEnd { mdx: true }),
+            FrontEndKind::Html => Box::new(HtmlFrontEnd),
+        }
+    }
+}
+
+/// 未在 `frontEnds` 中配置时使用的前端，按文件名后缀匹配
+pub const DEFAULT_FRONT_ENDS: [(&str, FrontEndKind); 7] = [
+    ("vue", FrontEndKind::Vue),
+    ("svelte", FrontEndKind::Svelte),
+    ("component.html", FrontEndKind::Angular),
+    ("md", FrontEndKind::Markdown),
+    ("markdown", FrontEndKind::Markdown),
+    ("mdx", FrontEndKind::Mdx),
+    ("html", FrontEndKind::Html),
+];
+
+/// 文件名是否以 `.suffix` 结尾（忽略大小写），后缀可以带或不带前导 `.`
+pub fn suffix_matches(suffix: &str, filepath: &str) -> bool {
+    let suffix = suffix.trim_start_matches('.');
+    let filepath = filepath.to_ascii_lowercase();
+    !suffix.is_empty()
+        && filepath
+            .strip_suffix(&suffix.to_ascii_lowercase())
+            .is_some_and(|rest| rest.ends_with('.'))
+}
+
+/// 在后缀表中找到最长的匹配项，如 `a.component.html` 优先匹配 `component.html` 而不是 `html`
+pub fn longest_suffix_match<'a, T>(
+    entries: impl IntoIterator<Item = (&'a str, T)>,
+    filepath: &str,
+) -> Option<T> {
+    entries
+        .into_iter()
+        .filter(|(suffix, _)| suffix_matches(suffix, filepath))
+        .max_by_key(|(suffix, _)| suffix.trim_start_matches('.').len())
+        .map(|(_, value)| value)
+}
+
+/// JS/TS 前端：按 `func.extensions` 提取函数调用，按 `trans.extensions` 提取 Trans 组件
+pub struct ScriptFrontEnd;
+
+impl FrontEnd for ScriptFrontEnd {
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence> {
+        self.extract_with_errors(content, filepath, config).0
+    }
+
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        let mut occurrences = Vec::new();
+        let mut errors = Vec::new();
+
+        if extension_matches(&config.func.extensions, filepath) {
+            let (func_occurrences, _, func_errors) = extract_func_calls(content, filepath, &config.func, &[]);
+            occurrences.extend(func_occurrences);
+            errors.extend(func_errors);
+        }
+
+        if extension_matches(&config.trans.extensions, filepath) {
+            let options = TransParseOptions {
+                component: config.trans.component.clone(),
+                i18n_key: config.trans.i18n_key.clone(),
+                ..Default::default()
+            };
+            let (trans_occurrences, trans_errors) = parse_trans_with_errors(content, &options);
+            occurrences.extend(trans_occurrences);
+            errors.extend(trans_errors);
+        }
+
+        (occurrences, errors)
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, _config: &ScanConfig) {
+        linter.verify(content, filepath);
+    }
+}
+
+/// 提取翻译函数调用，返回调用、源码中找到的绑定及无法解析的选项对象
+///
+/// `extra_bindings` 为源码之外声明的绑定，如 Vue 模板中使用的 `<script setup>` 顶层 `t`。
+pub(crate) fn extract_func_calls(
+    content: &str,
+    filepath: &str,
+    func: &FuncConfig,
+    extra_bindings: &[TranslationBinding],
+) -> (Vec<KeyOccurrence>, Vec<TranslationBinding>, Vec<SourceError>) {
+    let mut translation_bindings = extra_bindings.to_vec();
+    translation_bindings.extend(bindings::find_bindings(content, &func.list, &func.hooks, &func.hocs));
+    let mut parser = parse_func_from_string_by_babel::Parser::new();
+    let opts = parse_func_from_string_by_babel::ParserOpts {
+        list: Some(bindings::binding_funcs(&func.list, &translation_bindings)),
+        filepath: Some(filepath.to_string()),
+        babylon_options: Some((&func.babylon).into()),
+        ..Default::default()
+    };
+    parser.parse_func_from_string_by_babel(content, opts, None, None);
+    let occurrences = bindings::apply_bindings(content, parser.occurrences, &translation_bindings, &func.list);
+    (occurrences, translation_bindings, parser.errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_suffix_match() {
+        let entries = [("html", 1), ("component.html", 2), (".vue", 3)];
+        assert_eq!(longest_suffix_match(entries, "app/app.component.html"), Some(2));
+        assert_eq!(longest_suffix_match(entries, "index.HTML"), Some(1));
+        assert_eq!(longest_suffix_match(entries, "App.vue"), Some(3));
+        assert_eq!(longest_suffix_match(entries, "component.html.bak"), None);
+        assert!(!suffix_matches("vue", "vue"));
+    }
+}
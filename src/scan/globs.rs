@@ -0,0 +1,518 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use super::config::ScanConfig;
+use crate::utils::normalize_path;
+
+/// 展开花括号，支持多组与嵌套，如 `src/{a,b/{c,d}}.js` 展开为 `src/a.js`、`src/b/c.js`、`src/b/d.js`
+///
+/// 不含顶层逗号的 `{a}` 按字面量处理。
+pub fn expand_braces(pattern: &str) -> Vec<String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => {
+                if let Some((close, commas)) = brace_group(&chars, i) {
+                    if !commas.is_empty() {
+                        let prefix: String = chars[..i].iter().collect();
+                        let suffix: String = chars[close + 1..].iter().collect();
+                        let mut bounds = vec![i];
+                        bounds.extend(commas);
+                        bounds.push(close);
+                        return bounds
+                            .windows(2)
+                            .flat_map(|w| {
+                                let alternative: String = chars[w[0] + 1..w[1]].iter().collect();
+                                expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+                            })
+                            .collect();
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    vec![pattern.to_string()]
+}
+
+/// 与 `{` 匹配的 `}` 的位置，以及其中顶层逗号的位置
+fn brace_group(chars: &[char], open: usize) -> Option<(usize, Vec<usize>)> {
+    let mut depth = 0;
+    let mut commas = Vec::new();
+    let mut i = open;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((i, commas));
+                }
+            }
+            ',' if depth == 1 => commas.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExtGlobKind {
+    /// `?(a|b)`
+    ZeroOrOne,
+    /// `@(a|b)`
+    One,
+    /// `*(a|b)`
+    ZeroOrMore,
+    /// `+(a|b)`
+    OneOrMore,
+    /// `!(a|b)`
+    Not,
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Literal(char),
+    /// `?`
+    AnyChar,
+    /// `*`，不跨越 `/`
+    Star,
+    /// 整段的 `**`（含其后的 `/`），匹配零个或多个目录
+    GlobStar,
+    /// `[a-z]`、`[!abc]`
+    Class { negated: bool, ranges: Vec<(char, char)> },
+    ExtGlob { kind: ExtGlobKind, alternatives: Vec<Vec<Token>> },
+}
+
+/// 单个 glob 模式，支持 `*`、`?`、`**`、`[...]` 与 extglob（`!(...)`、`@(...)`、`?(...)`、`*(...)`、`+(...)`）
+///
+/// 花括号需先用 [`expand_braces`] 展开。
+#[derive(Debug, Clone)]
+pub struct Glob {
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Self {
+        let chars: Vec<char> = glob_path(pattern).chars().collect();
+        let mut i = 0;
+        let tokens = parse_sequence(&chars, &mut i, false);
+        Self { tokens }
+    }
+
+    /// 路径是否匹配，路径中的 `\` 视为 `/`，开头的 `./` 忽略
+    pub fn is_match(&self, path: &str) -> bool {
+        let text: Vec<char> = glob_path(path).chars().collect();
+        matches(&self.tokens, &text)
+    }
+}
+
+/// 统一路径分隔符并去掉开头的 `./`
+fn glob_path(path: &str) -> String {
+    let mut path = normalize_path(path);
+    while let Some(rest) = path.strip_prefix("./") {
+        path = rest.to_string();
+    }
+    path
+}
+
+fn ext_glob_kind(ch: char) -> Option<ExtGlobKind> {
+    match ch {
+        '?' => Some(ExtGlobKind::ZeroOrOne),
+        '@' => Some(ExtGlobKind::One),
+        '*' => Some(ExtGlobKind::ZeroOrMore),
+        '+' => Some(ExtGlobKind::OneOrMore),
+        '!' => Some(ExtGlobKind::Not),
+        _ => None,
+    }
+}
+
+/// 解析到结尾，或在 extglob 中解析到 `|`、`)`
+fn parse_sequence(chars: &[char], i: &mut usize, in_group: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    while *i < chars.len() {
+        let ch = chars[*i];
+        if in_group && (ch == '|' || ch == ')') {
+            break;
+        }
+        if let Some(kind) = ext_glob_kind(ch).filter(|_| chars.get(*i + 1) == Some(&'(')) {
+            *i += 2;
+            let mut alternatives = vec![parse_sequence(chars, i, true)];
+            while chars.get(*i) == Some(&'|') {
+                *i += 1;
+                alternatives.push(parse_sequence(chars, i, true));
+            }
+            *i += 1; // `)`
+            tokens.push(Token::ExtGlob { kind, alternatives });
+            continue;
+        }
+        match ch {
+            '\\' => {
+                *i += 1;
+                if let Some(&escaped) = chars.get(*i) {
+                    tokens.push(Token::Literal(escaped));
+                }
+            }
+            '*' if chars.get(*i + 1) == Some(&'*') => {
+                let segment_start = *i == 0 || chars[*i - 1] == '/';
+                let segment_end = matches!(chars.get(*i + 2), None | Some('/'));
+                *i += 1;
+                if segment_start && segment_end {
+                    if chars.get(*i + 1) == Some(&'/') {
+                        *i += 1;
+                    }
+                    tokens.push(Token::GlobStar);
+                } else {
+                    tokens.push(Token::Star);
+                }
+            }
+            '*' => tokens.push(Token::Star),
+            '?' => tokens.push(Token::AnyChar),
+            '[' => match parse_class(chars, *i) {
+                Some((token, end)) => {
+                    tokens.push(token);
+                    *i = end;
+                }
+                None => tokens.push(Token::Literal('[')),
+            },
+            _ => tokens.push(Token::Literal(ch)),
+        }
+        *i += 1;
+    }
+    tokens
+}
+
+/// 解析 `[...]`，返回字符类与 `]` 的位置
+fn parse_class(chars: &[char], open: usize) -> Option<(Token, usize)> {
+    let mut i = open + 1;
+    let negated = matches!(chars.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut ranges = Vec::new();
+    let first = i;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == ']' && i > first {
+            return Some((Token::Class { negated, ranges }, i));
+        }
+        if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|c| *c != ']') {
+            ranges.push((ch, chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((ch, ch));
+            i += 1;
+        }
+    }
+    None
+}
+
+/// 到下一个 `/` 之前的长度
+fn segment_len(text: &[char]) -> usize {
+    text.iter().position(|c| *c == '/').unwrap_or(text.len())
+}
+
+fn matches(tokens: &[Token], text: &[char]) -> bool {
+    let (first, rest) = match tokens.split_first() {
+        Some(split) => split,
+        None => return text.is_empty(),
+    };
+    match first {
+        Token::Literal(ch) => text.first() == Some(ch) && matches(rest, &text[1..]),
+        Token::AnyChar => text.first().is_some_and(|c| *c != '/') && matches(rest, &text[1..]),
+        Token::Star => (0..=segment_len(text)).any(|n| matches(rest, &text[n..])),
+        Token::GlobStar => {
+            rest.is_empty()
+                || (0..=text.len())
+                    .filter(|&n| n == 0 || text[n - 1] == '/')
+                    .any(|n| matches(rest, &text[n..]))
+        }
+        Token::Class { negated, ranges } => {
+            text.first().is_some_and(|c| {
+                *c != '/' && ranges.iter().any(|(lo, hi)| lo <= c && c <= hi) != *negated
+            }) && matches(rest, &text[1..])
+        }
+        Token::ExtGlob { kind, alternatives } => {
+            let segment = segment_len(text);
+            let alternative_matches = |n: usize| alternatives.iter().any(|alt| matches(alt, &text[..n]));
+            match kind {
+                ExtGlobKind::One => (0..=segment).any(|n| alternative_matches(n) && matches(rest, &text[n..])),
+                ExtGlobKind::ZeroOrOne => {
+                    matches(rest, text) || (0..=segment).any(|n| alternative_matches(n) && matches(rest, &text[n..]))
+                }
+                ExtGlobKind::Not => (0..=segment).any(|n| !alternative_matches(n) && matches(rest, &text[n..])),
+                ExtGlobKind::ZeroOrMore | ExtGlobKind::OneOrMore => {
+                    if *kind == ExtGlobKind::ZeroOrMore && matches(rest, text) {
+                        return true;
+                    }
+                    // 匹配一段后，剩余部分继续按 `*(...)` 匹配
+                    let mut repeated = vec![Token::ExtGlob {
+                        kind: ExtGlobKind::ZeroOrMore,
+                        alternatives: alternatives.clone(),
+                    }];
+                    repeated.extend(rest.iter().cloned());
+                    (1..=segment).any(|n| alternative_matches(n) && matches(&repeated, &text[n..]))
+                }
+            }
+        }
+    }
+}
+
+/// 模式中第一个含通配符的段之前的目录，如 `./src/**/*.js` 为 `src`
+fn static_base(pattern: &str) -> String {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let magic = |segment: &str| segment.contains(['*', '?', '[']) || segment.contains("(");
+    let literal: Vec<&str> = segments
+        .iter()
+        .take(segments.len().saturating_sub(1))
+        .take_while(|segment| !magic(segment))
+        .copied()
+        .collect();
+    match literal.join("/") {
+        base if base.is_empty() && pattern.starts_with('/') => "/".to_string(),
+        base if base.is_empty() => ".".to_string(),
+        base => base,
+    }
+}
+
+/// 忽略文件中的一条规则（`.gitignore` 语法的常用子集）
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+}
+
+fn parse_ignore_file(path: &Path, dir: &str) -> Vec<IgnoreRule> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return Vec::new(),
+    };
+    content
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let dir_only = line.ends_with('/');
+            let line = line.trim_end_matches('/');
+            // 含 `/` 的规则相对于忽略文件所在目录，否则匹配任意层级
+            let pattern = match line.strip_prefix('/') {
+                Some(anchored) => anchored.to_string(),
+                None if line.contains('/') => line.to_string(),
+                None => format!("**/{}", line),
+            };
+            let pattern = if dir == "." { pattern } else { format!("{}/{}", dir, pattern) };
+            IgnoreRule {
+                glob: Glob::new(&pattern),
+                negated,
+                dir_only,
+            }
+        })
+        .collect()
+}
+
+/// 按规则顺序判断，后面的规则优先
+fn is_ignored(rules: &[IgnoreRule], path: &str, is_dir: bool) -> bool {
+    let mut ignored = false;
+    for rule in rules {
+        if (!rule.dir_only || is_dir) && rule.glob.is_match(path) {
+            ignored = !rule.negated;
+        }
+    }
+    ignored
+}
+
+/// `exclude` 与 `!pattern` 排除项：不含 `/` 的模式匹配任意层级的文件或目录
+fn exclude_glob(pattern: &str) -> Glob {
+    let pattern = glob_path(pattern);
+    let pattern = pattern.trim_end_matches('/');
+    if pattern.contains('/') {
+        Glob::new(pattern)
+    } else {
+        Glob::new(&format!("**/{}", pattern))
+    }
+}
+
+/// 按输入模式列出待扫描的文件
+///
+/// - 支持嵌套花括号、extglob，以 `!` 开头的模式为排除项
+/// - `config.exclude` 中的模式与排除项一样，匹配到的目录整体跳过
+/// - `config.ignore_files` 中的忽略文件（如 `.gitignore`）在各级目录中生效
+/// - 遍历时跳过以 `.` 开头的文件与目录；直接给出的已存在文件不受以上规则影响
+pub fn resolve_files(patterns: &[String], config: &ScanConfig) -> Vec<String> {
+    let mut excludes: Vec<Glob> = config.exclude.iter().map(|p| exclude_glob(p)).collect();
+    let mut includes = Vec::new();
+    for pattern in patterns {
+        match pattern.strip_prefix('!').filter(|_| !pattern.starts_with("!(")) {
+            Some(negated) => excludes.extend(expand_braces(negated).iter().map(|p| exclude_glob(p))),
+            None => includes.extend(expand_braces(pattern)),
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for pattern in includes {
+        if Path::new(&pattern).is_file() {
+            if seen.insert(glob_path(&pattern)) {
+                files.push(pattern);
+            }
+            continue;
+        }
+
+        let normalized = glob_path(&pattern);
+        let base = static_base(&normalized);
+        let glob = Glob::new(&normalized);
+        let mut walker = Walker {
+            config,
+            excludes: &excludes,
+            rules: ancestor_ignore_rules(&base, &config.ignore_files),
+            found: Vec::new(),
+        };
+        walker.walk(&base);
+        for file in walker.found {
+            if glob.is_match(&file) && seen.insert(file.clone()) {
+                files.push(file);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+/// 当前目录到 `base` 之间（不含 `base`）各级目录的忽略规则，绝对路径或 `..` 开头时不读取
+fn ancestor_ignore_rules(base: &str, ignore_files: &[String]) -> Vec<IgnoreRule> {
+    if base == "." || base.starts_with('/') || base.starts_with("..") {
+        return Vec::new();
+    }
+    let mut dirs = vec![".".to_string()];
+    let segments: Vec<&str> = base.split('/').collect();
+    for end in 1..segments.len() {
+        dirs.push(segments[..end].join("/"));
+    }
+    dirs.iter()
+        .flat_map(|dir| {
+            ignore_files
+                .iter()
+                .flat_map(move |name| parse_ignore_file(&Path::new(dir).join(name), dir))
+        })
+        .collect()
+}
+
+struct Walker<'a> {
+    config: &'a ScanConfig,
+    excludes: &'a [Glob],
+    rules: Vec<IgnoreRule>,
+    found: Vec<String>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, dir: &str) {
+        let rules_before = self.rules.len();
+        for name in &self.config.ignore_files {
+            let rules = parse_ignore_file(&Path::new(dir).join(name), dir);
+            self.rules.extend(rules);
+        }
+
+        let mut entries: Vec<_> = match fs::read_dir(dir) {
+            Ok(entries) => entries.flatten().collect(),
+            Err(_) => Vec::new(),
+        };
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            }
+            let path = if dir == "." { name } else { format!("{}/{}", dir.trim_end_matches('/'), name) };
+            let is_dir = entry.path().is_dir();
+            if self.excludes.iter().any(|glob| glob.is_match(&path)) || is_ignored(&self.rules, &path, is_dir) {
+                continue;
+            }
+            if is_dir {
+                self.walk(&path);
+            } else {
+                self.found.push(path);
+            }
+        }
+
+        self.rules.truncate(rules_before);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_braces() {
+        assert_eq!(expand_braces("*.{js,ts}"), vec!["*.js", "*.ts"]);
+        assert_eq!(
+            expand_braces("src/{a,b/{c,d}}.{js,ts}"),
+            vec!["src/a.js", "src/a.ts", "src/b/c.js", "src/b/c.ts", "src/b/d.js", "src/b/d.ts"]
+        );
+        assert_eq!(expand_braces("{a}.js"), vec!["{a}.js"]);
+    }
+
+    #[test]
+    fn test_glob_match() {
+        let glob = Glob::new("./src/**/!(*.d).ts");
+        assert!(glob.is_match("src/a.ts"));
+        assert!(glob.is_match("src/x/y/a.ts"));
+        assert!(!glob.is_match("src/x/a.d.ts"));
+        assert!(!glob.is_match("lib/a.ts"));
+
+        assert!(Glob::new("src/*.@(js|jsx)").is_match("src/a.jsx"));
+        assert!(!Glob::new("src/*.js").is_match("src/x/a.js"));
+        assert!(Glob::new("a+(b|c).js").is_match("abcb.js"));
+        assert!(Glob::new("a*(b).js").is_match("a.js"));
+        assert!(Glob::new("a?(b).js").is_match("ab.js"));
+        assert!(Glob::new("[!.]x[0-9].js").is_match("ax5.js"));
+        assert!(Glob::new("src/**").is_match("src/a/b.js"));
+    }
+
+    #[test]
+    fn test_resolve_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().to_string_lossy().to_string();
+        for file in [
+            "src/a.ts",
+            "src/types.d.ts",
+            "src/b.js",
+            "src/legacy/c.js",
+            "src/gen/d.js",
+            "src/gen/keep.js",
+            "src/node_modules/e.js",
+            "src/vendor/f.js",
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        fs::write(temp_dir.path().join(".gitignore"), "gen/\n!keep.js\n").unwrap();
+        fs::write(temp_dir.path().join("src/.i18nignore"), "/vendor\n").unwrap();
+
+        let relative = |files: Vec<String>| -> Vec<String> {
+            files.iter().map(|f| f.trim_start_matches(&root).trim_start_matches('/').to_string()).collect()
+        };
+        let config = ScanConfig::default();
+        let patterns = vec![
+            format!("{}/src/**/*.{{js,ts}}", root),
+            format!("!{}/src/legacy/**", root),
+        ];
+        assert_eq!(relative(resolve_files(&patterns, &config)), vec!["src/a.ts", "src/b.js", "src/gen/d.js", "src/gen/keep.js"]);
+
+        let config = ScanConfig::default().with_ignore_files(vec![".gitignore".to_string(), ".i18nignore".to_string()]);
+        let patterns = vec![format!("{}/**/*.js", root)];
+        assert_eq!(relative(resolve_files(&patterns, &config)), vec!["src/b.js", "src/legacy/c.js"]);
+    }
+}
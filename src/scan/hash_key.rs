@@ -1,52 +1,212 @@
 use std::collections::HashMap;
 use std::sync::Mutex;
 use lazy_static::lazy_static;
-use colored::*;
+use serde::{Deserialize, Serialize};
 
-// 全局 key 映射表，使用 Mutex 保证线程安全
+use super::diagnostic::{error_at, DiagnosticSink};
+use super::key_strategy::{KeyStrategy, LegacyHashKey};
+use super::span::Span;
+use crate::utils::remove_whitespace;
+
+// 全局 key → 句子反向索引，使用 Mutex 保证线程安全
 lazy_static! {
-    static ref KEY_MAP: Mutex<HashMap<String, String>> = Mutex::new(HashMap::new());
+    static ref KEY_INDEX: Mutex<KeyIndex> = Mutex::new(KeyIndex::default());
+}
+
+/// 哈希冲突的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HashCollisionStrategy {
+    /// 仅报告冲突，后出现的句子仍然使用冲突的 key
+    #[default]
+    Report,
+    /// 报告冲突，并为后出现的句子加盐重新计算出一个未被占用的 key
+    ///
+    /// 运行时直接对句子计算 key 的代码无法得知加盐后的结果，开启前需确认词条只通过扫描产物查找。
+    Disambiguate,
+}
+
+/// 生成 key 的句子及其出现位置
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeySource {
+    pub sentence: String,
+    pub location: Option<String>,
+}
+
+/// 两个不同句子计算出了同一个 key
+#[derive(Debug, Clone)]
+pub struct HashCollision {
+    pub key: String,
+    /// 先占用该 key 的句子
+    pub existing: KeySource,
+    /// 后出现的句子
+    pub incoming: KeySource,
+    /// 开启消歧时，后出现的句子最终使用的 key
+    pub resolved_key: Option<String>,
+}
+
+impl HashCollision {
+    /// 生成错误信息，包含两个句子及其位置
+    pub fn message(&self) -> String {
+        let describe = |source: &KeySource| match &source.location {
+            Some(location) => format!("\"{}\" at {}", source.sentence, location),
+            None => format!("\"{}\"", source.sentence),
+        };
+        let mut message = format!(
+            "Hash collision on key \"{}\": {} and {}",
+            self.key,
+            describe(&self.existing),
+            describe(&self.incoming)
+        );
+        if let Some(resolved_key) = &self.resolved_key {
+            message.push_str(&format!(", the latter now uses \"{}\"", resolved_key));
+        }
+        message
+    }
+}
+
+/// key → 句子的反向索引，用于发现不同句子哈希到同一个 key 的真实冲突
+pub struct KeyIndex {
+    strategy: HashCollisionStrategy,
+    key_strategy: Box<dyn KeyStrategy>,
+    /// 带 context 的完整 key → 占用它的句子
+    entries: HashMap<String, KeySource>,
+    /// 去除空白后的句子（含 context）→ 已分配的 key，保证同一句子多次出现时结果一致
+    assigned: HashMap<(String, Option<String>), String>,
+}
+
+impl Default for KeyIndex {
+    fn default() -> Self {
+        Self::new(HashCollisionStrategy::default())
+    }
+}
+
+impl std::fmt::Debug for KeyIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyIndex")
+            .field("strategy", &self.strategy)
+            .field("entries", &self.entries)
+            .finish()
+    }
 }
 
-/// 错误回调函数类型
-pub type OnErrorCallback = Box<dyn Fn(&str) + Send + Sync>;
+impl KeyIndex {
+    /// 创建指定冲突策略的索引，使用与 JS 版本一致的 key 算法
+    pub fn new(strategy: HashCollisionStrategy) -> Self {
+        Self {
+            strategy,
+            key_strategy: Box::new(LegacyHashKey::default()),
+            entries: HashMap::new(),
+            assigned: HashMap::new(),
+        }
+    }
+
+    /// 设置 key 生成策略
+    pub fn with_key_strategy(mut self, key_strategy: Box<dyn KeyStrategy>) -> Self {
+        self.key_strategy = key_strategy;
+        self
+    }
+
+    /// 为句子分配 key（有 context 时带 `_context` 后缀），发现冲突时一并返回
+    ///
+    /// 每个句子只会报告一次冲突；开启消歧时，先出现的句子保留原 key。
+    pub fn insert(
+        &mut self,
+        value: &str,
+        context: Option<&str>,
+        location: Option<&str>,
+    ) -> (String, Option<HashCollision>) {
+        let cleaned_value = remove_whitespace(value);
+        let assigned_id = (cleaned_value.clone(), context.map(|c| c.to_string()));
+        if let Some(key) = self.assigned.get(&assigned_id) {
+            return (key.clone(), None);
+        }
+
+        let incoming = KeySource {
+            sentence: value.to_string(),
+            location: location.map(|l| l.to_string()),
+        };
+        let key = with_context(&self.key_strategy.generate(value), context);
+
+        let existing = match self.entries.get(&key) {
+            Some(existing) if remove_whitespace(&existing.sentence) != cleaned_value => existing.clone(),
+            Some(_) => {
+                self.assigned.insert(assigned_id, key.clone());
+                return (key, None);
+            }
+            None => {
+                self.entries.insert(key.clone(), incoming);
+                self.assigned.insert(assigned_id, key.clone());
+                return (key, None);
+            }
+        };
+
+        let mut collision = HashCollision {
+            key: key.clone(),
+            existing,
+            incoming: incoming.clone(),
+            resolved_key: None,
+        };
+
+        let key = match self.strategy {
+            HashCollisionStrategy::Report => key,
+            HashCollisionStrategy::Disambiguate => {
+                let resolved = self.disambiguate(value, context);
+                self.entries.insert(resolved.clone(), incoming);
+                collision.resolved_key = Some(resolved.clone());
+                resolved
+            }
+        };
+        self.assigned.insert(assigned_id, key.clone());
+
+        (key, Some(collision))
+    }
+
+    /// 依次以第 1、2… 个盐值重新生成，直到得到一个未被其他句子占用的 key
+    fn disambiguate(&self, value: &str, context: Option<&str>) -> String {
+        (1u32..)
+            .map(|salt| with_context(&self.key_strategy.salted(value, salt), context))
+            .find(|candidate| !self.entries.contains_key(candidate))
+            .unwrap()
+    }
+
+    /// 查找占用指定 key（含 context 后缀）的句子
+    pub fn get(&self, full_key: &str) -> Option<&KeySource> {
+        self.entries.get(full_key)
+    }
+
+    /// 已占用的 key 数量
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 索引是否为空
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 清空索引
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.assigned.clear();
+    }
+}
 
 /// 从句子计算哈希一个 key 值，该算法需要和 scanner 保持一致
+///
+/// 给出 context 时返回 `k_xxxxxxx_context`。不同句子计算出相同 key 时，向 `sink` 报告 `HashCollision`。
 pub fn hash_key(
     value: &str,
     context: Option<&str>,
-    on_error: Option<&OnErrorCallback>,
+    sink: Option<&dyn DiagnosticSink>,
 ) -> String {
-    // 移除所有空白字符并计算哈希
-    let cleaned_value = value.chars().filter(|c| !c.is_whitespace()).collect::<String>();
-    let hash = hash_string(&cleaned_value);
-    
-    // 转换为 36 进制并格式化为 7 位
-    let hash_str = format!("{:0>7}", radix_36(hash));
-    let key = format!("k_{}", hash_str);
-    
-    // 构建完整的 key（包含 context）
-    let _full_key = if let Some(ctx) = context {
-        format!("{}_{}", key, ctx)
-    } else {
-        key.clone()
-    };
-    
-    // 检查是否存在冲突
-    let mut key_map = KEY_MAP.lock().unwrap();
-    if let Some(existed_key) = key_map.get(value) {
-        // 相同的原始值已经存在，检查是否生成了相同的 key
-        if existed_key != &key {
-            // 这种情况下是哈希冲突，相同的文本生成了不同的 key（理论上不应该发生）
-            if let Some(error_callback) = on_error {
-                error_callback(&format_error_message(existed_key, &key));
-            }
-        }
-        // 如果是相同的值和相同的 key，不需要重复插入
-    } else {
-        key_map.insert(value.to_string(), key.clone());
+    let mut key_index = KEY_INDEX.lock().unwrap();
+    let (key, collision) = key_index.insert(value, context, None);
+
+    if let (Some(collision), Some(sink)) = (collision, sink) {
+        sink.report(&error_at("HashCollision", "", Span::default(), &collision.message()));
     }
-    
+
     key
 }
 
@@ -55,76 +215,27 @@ pub fn hash_key_simple(value: &str, context: Option<&str>) -> String {
     hash_key(value, context, None)
 }
 
-/// 清空 key 映射表（主要用于测试）
+/// 清空 key 索引（主要用于测试）
 pub fn clear_key_map() {
-    let mut key_map = KEY_MAP.lock().unwrap();
-    key_map.clear();
+    KEY_INDEX.lock().unwrap().clear();
 }
 
-/// 获取当前 key 映射表的大小
+/// 获取当前 key 索引的大小
 pub fn get_key_map_size() -> usize {
-    let key_map = KEY_MAP.lock().unwrap();
-    key_map.len()
+    KEY_INDEX.lock().unwrap().len()
 }
 
-/// 简单的字符串哈希函数，模拟 hash-string 库的行为
-fn hash_string(s: &str) -> u32 {
-    let mut hash: u32 = 0;
-    for byte in s.bytes() {
-        hash = hash.wrapping_mul(31).wrapping_add(byte as u32);
+fn with_context(key: &str, context: Option<&str>) -> String {
+    match context {
+        Some(ctx) => format!("{}_{}", key, ctx),
+        None => key.to_string(),
     }
-    hash
-}
-
-/// 将数字转换为 36 进制字符串
-fn radix_36(mut num: u32) -> String {
-    if num == 0 {
-        return "0".to_string();
-    }
-    
-    let mut result = String::new();
-    let chars = "0123456789abcdefghijklmnopqrstuvwxyz";
-    
-    while num > 0 {
-        let remainder = (num % 36) as usize;
-        result.insert(0, chars.chars().nth(remainder).unwrap());
-        num /= 36;
-    }
-    
-    result
-}
-
-/// 格式化错误消息
-fn format_error_message(existed_value: &str, new_value: &str) -> String {
-    format!(
-        "\n{}\n{}\n    \"{}\"\n    \"{}\"",
-        "Same sentence in different forms found:".red(),
-        "",
-        existed_value,
-        new_value
-    )
-}
-
-/// 带文件路径的错误回调函数
-pub fn create_file_error_callback() -> OnErrorCallback {
-    Box::new(|filepath: &str| {
-        eprintln!();
-        eprintln!("{}", filepath.yellow());
-        // 错误信息已经在 format_error_message 中格式化
-        eprintln!("{}", filepath);
-    })
-}
-
-/// 默认错误回调函数
-pub fn default_error_callback(filepath: &str) {
-    eprintln!();
-    eprintln!("{}", filepath.yellow());
-    eprintln!("{}", "Same sentence in different forms found:".red());
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scan::key_strategy::KeyStrategyConfig;
 
     #[test]
     fn test_hash_key_basic() {
@@ -144,11 +255,14 @@ mod tests {
     fn test_hash_key_with_context() {
         clear_key_map();
         
+        let key = hash_key_simple("Hello", None);
         let key1 = hash_key_simple("Hello", Some("context1"));
         let key2 = hash_key_simple("Hello", Some("context2"));
         
-        // 相同的文本在不同 context 下应该生成相同的 key
-        assert_eq!(key1, key2);
+        // 相同的文本在不同 context 下生成带 context 后缀的 key
+        assert_ne!(key1, key2);
+        assert_eq!(key1, format!("{}_context1", key));
+        assert_eq!(key2, format!("{}_context2", key));
         
         clear_key_map(); // 清理状态
     }
@@ -169,20 +283,74 @@ mod tests {
     }
 
     #[test]
-    fn test_radix_36() {
-        assert_eq!(radix_36(0), "0");
-        assert_eq!(radix_36(35), "z");
-        assert_eq!(radix_36(36), "10");
+    fn test_key_index_reports_true_collision() {
+        // "Aa" 和 "BB" 在 31 进制哈希下结果相同
+        let mut index = KeyIndex::new(HashCollisionStrategy::Report);
+        let (key1, collision1) = index.insert("Aa", None, Some("a.tsx:1:3"));
+        let (key2, collision2) = index.insert("BB", None, Some("b.tsx:2:5"));
+
+        assert_eq!(key1, key2);
+        assert!(collision1.is_none());
+        let collision = collision2.unwrap();
+        assert_eq!(collision.key, key1);
+        assert_eq!(collision.existing.sentence, "Aa");
+        assert_eq!(collision.existing.location.as_deref(), Some("a.tsx:1:3"));
+        assert_eq!(collision.incoming.sentence, "BB");
+        assert_eq!(collision.incoming.location.as_deref(), Some("b.tsx:2:5"));
+        assert!(collision.message().contains("b.tsx:2:5"));
+
+        // 同一句子再次出现不重复报告
+        let (_, collision3) = index.insert("BB", None, Some("c.tsx:1:1"));
+        assert!(collision3.is_none());
     }
 
     #[test]
-    fn test_hash_string() {
-        let hash1 = hash_string("test");
-        let hash2 = hash_string("test");
-        let hash3 = hash_string("different");
-        
-        assert_eq!(hash1, hash2);
-        assert_ne!(hash1, hash3);
+    fn test_key_index_whitespace_forms_are_not_collisions() {
+        let mut index = KeyIndex::new(HashCollisionStrategy::Report);
+        index.insert("Hello World", None, None);
+        let (_, collision) = index.insert("Hello   World", None, None);
+        assert!(collision.is_none());
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn test_key_index_collisions_are_per_context() {
+        let mut index = KeyIndex::new(HashCollisionStrategy::Report);
+        index.insert("Aa", Some("verb"), None);
+        let (_, collision) = index.insert("BB", Some("noun"), None);
+        assert!(collision.is_none());
+    }
+
+    #[test]
+    fn test_key_index_disambiguate() {
+        let mut index = KeyIndex::new(HashCollisionStrategy::Disambiguate);
+        let (key1, _) = index.insert("Aa", None, None);
+        let (key2, collision) = index.insert("BB", None, None);
+
+        assert_ne!(key1, key2);
+        assert_eq!(key2, LegacyHashKey::default().salted("BB", 1));
+        assert_eq!(collision.unwrap().resolved_key, Some(key2.clone()));
+        assert_eq!(index.get(&key2).unwrap().sentence, "BB");
+
+        // 结果稳定：同一句子总是得到相同的 key
+        let (key3, collision) = index.insert("BB", None, None);
+        assert_eq!(key3, key2);
+        assert!(collision.is_none());
+        let (key4, _) = index.insert("Aa", None, None);
+        assert_eq!(key4, key1);
+    }
+
+    #[test]
+    fn test_key_index_with_key_strategy() {
+        let strategy = KeyStrategyConfig::Hash64 {
+            prefix: "app_".to_string(),
+            length: 12,
+        };
+        let mut index = KeyIndex::new(HashCollisionStrategy::Report).with_key_strategy(strategy.build());
+        let (key, _) = index.insert("保存", Some("button"), None);
+        assert!(key.starts_with("app_"));
+        assert!(key.ends_with("_button"));
+        assert_eq!(key.len(), "app_".len() + 12 + "_button".len());
     }
 
     #[test]
@@ -0,0 +1,115 @@
+use super::config::ScanConfig;
+use super::front_end::{extract_func_calls, FrontEnd};
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+use super::span::SourceError;
+use super::vue::{blank_sfc, mask_sfc, split_sfc, SfcBlockKind};
+use super::zh_linter::ZhLinter;
+
+/// 静态 HTML 前端：检查文本节点与属性中的中文
+///
+/// `<script>` 与 `<style>` 的内容默认跳过；`staticContent.scripts` 开启时脚本走 JS 提取与检查流程。
+pub struct HtmlFrontEnd;
+
+impl FrontEnd for HtmlFrontEnd {
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence> {
+        self.extract_with_errors(content, filepath, config).0
+    }
+
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        if !config.static_content.scripts {
+            return (Vec::new(), Vec::new());
+        }
+        let (scripts, _) = split_page(content);
+        let (occurrences, _, errors) = extract_func_calls(&scripts, filepath, &config.func, &[]);
+        (occurrences, errors)
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, config: &ScanConfig) {
+        let (scripts, markup) = split_page(content);
+        linter.verify_markup(&markup, filepath, None);
+        linter.verify_attributes(&markup, filepath);
+        if config.static_content.scripts {
+            linter.verify(&scripts, filepath);
+        }
+    }
+}
+
+/// 拆分为与原文件行列对应的（脚本，标记）两部分，标记中 `<script>`、`<style>` 的内容为空白
+fn split_page(content: &str) -> (String, String) {
+    let blocks = split_sfc(content);
+    let scripts = mask_sfc(content, &blocks, SfcBlockKind::Script);
+    let markup = blank_sfc(content, &blocks, &[SfcBlockKind::Script, SfcBlockKind::Style]);
+    (scripts, markup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::config::StaticContentConfig;
+    use crate::scan::zh_linter::get_result;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html>
+<head><title>产品介绍</title></head>
+<body>
+  <!-- 注释中的中文 -->
+  <img src="a.png" alt="示意图">
+  <p>Hello</p>
+  <script>
+    document.title = i18n.t('page.title', { defaultValue: '标题' });
+  </script>
+  <style>.a::after { content: "样式"; }</style>
+</body>
+</html>
+"#;
+
+    fn lint_values(filepath: &str, config: &ScanConfig) -> Vec<(String, u32, u32)> {
+        let _guard = crate::scan::lock_scan();
+        HtmlFrontEnd.lint(&ZhLinter::new(), PAGE, filepath, config);
+        let mut values: Vec<_> = get_result()
+            .into_iter()
+            .filter(|r| r.filepath == filepath)
+            .map(|r| (r.value, r.loc.start.line, r.loc.start.column))
+            .collect();
+        values.sort_by_key(|(_, line, column)| (*line, *column));
+        values
+    }
+
+    #[test]
+    fn test_html_text_and_attributes() {
+        let config = ScanConfig::default();
+        assert_eq!(
+            lint_values("lint-test.html", &config),
+            vec![("产品介绍".to_string(), 3, 14), ("示意图".to_string(), 6, 25)]
+        );
+        assert!(HtmlFrontEnd.extract(PAGE, "lint-test.html", &config).is_empty());
+    }
+
+    #[test]
+    fn test_html_scripts_when_configured() {
+        let config = ScanConfig::default().with_static_content(StaticContentConfig {
+            scripts: true,
+            ..Default::default()
+        });
+        let occurrences = HtmlFrontEnd.extract(PAGE, "scripts-test.html", &config);
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!((occurrences[0].line, occurrences[0].column), (9, 29));
+        assert!(lint_values("scripts-test.html", &config)
+            .iter()
+            .any(|(value, line, _)| value.contains("标题") && *line == 9));
+    }
+
+    #[test]
+    fn test_html_reports_parse_errors() {
+        let content = "<p>Hi</p>\n<script>\n  i18n.t('a', { ns: 'x' );\n</script>\n";
+        let config = ScanConfig::default().with_static_content(StaticContentConfig {
+            scripts: true,
+            ..Default::default()
+        });
+        let (occurrences, errors) = HtmlFrontEnd.extract_with_errors(content, "a.html", &config);
+        assert_eq!(occurrences.len(), 1);
+        let spans: Vec<_> = errors.iter().map(|e| (e.span.start_line, e.span.start_column)).collect();
+        assert_eq!(spans, vec![(3, 15)]);
+        assert!(HtmlFrontEnd.extract_with_errors(content, "a.html", &ScanConfig::default()).1.is_empty());
+    }
+}
@@ -1,12 +1,24 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use super::config_check::{validate_config, ConfigDiagnostic};
+use super::config::{FuncConfig, ResourceConfig, ScanConfig, StaticContentConfig, TransConfig};
+use super::duplicate_key::DuplicateKeyStrategy;
+use super::front_end::FrontEndKind;
+use super::hash_key::HashCollisionStrategy;
+use super::js_object::{locate_js_module, locate_js_value, parse_js_module, KeyLocations};
+use super::key_strategy::KeyStrategyConfig;
+use super::plural::PluralSuffixStyle;
 
 /// JavaScript 配置文件结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JsConfig {
     pub input: Vec<String>,
+    /// 输出目录，未配置 `resource` 时词条写入 `${output}/{{lng}}.json`；两者都未配置时使用 `ScanConfig` 的默认路径
+    #[serde(default)]
     pub output: String,
     #[serde(default)]
     pub debug: bool,
@@ -18,6 +30,52 @@ pub struct JsConfig {
     pub lngs: Vec<String>,
     #[serde(rename = "defaultLng")]
     pub default_lng: String,
+    #[serde(default)]
+    #[serde(rename = "duplicateKeys")]
+    pub duplicate_keys: DuplicateKeyStrategy,
+    #[serde(default)]
+    #[serde(rename = "hashCollisions")]
+    pub hash_collisions: HashCollisionStrategy,
+    #[serde(default)]
+    #[serde(rename = "keyStrategy")]
+    pub key_strategy: KeyStrategyConfig,
+    #[serde(default)]
+    pub ns: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde(rename = "defaultNs")]
+    pub default_ns: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "pluralStyle")]
+    pub plural_style: PluralSuffixStyle,
+    #[serde(default)]
+    #[serde(rename = "nsSeparator")]
+    pub ns_separator: Option<String>,
+    #[serde(default)]
+    #[serde(rename = "frontEnds")]
+    pub front_ends: HashMap<String, FrontEndKind>,
+    #[serde(default)]
+    #[serde(rename = "staticContent")]
+    pub static_content: StaticContentConfig,
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    #[serde(default)]
+    #[serde(rename = "ignoreFiles")]
+    pub ignore_files: Option<Vec<String>>,
+    /// 继承的配置文件，相对于当前配置文件所在目录
+    #[serde(default, deserialize_with = "string_or_list", skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    /// monorepo 中各包所在目录的模式，如 `packages/*`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspaces: Vec<String>,
+    /// 子命令专用的配置，如 `command: { lint: { lngs: ['zh'] } }`，运行该子命令时覆盖文件中的配置
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub command: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    pub resource: Option<ResourceConfig>,
+    #[serde(default)]
+    pub func: Option<FuncConfig>,
+    #[serde(default)]
+    pub trans: Option<TransConfig>,
 }
 
 impl Default for JsConfig {
@@ -30,68 +88,244 @@ impl Default for JsConfig {
             remove_unused_keys: false,
             lngs: vec!["zh".to_string(), "en".to_string()],
             default_lng: "zh".to_string(),
+            duplicate_keys: DuplicateKeyStrategy::default(),
+            hash_collisions: HashCollisionStrategy::default(),
+            key_strategy: KeyStrategyConfig::default(),
+            ns: None,
+            default_ns: None,
+            plural_style: PluralSuffixStyle::default(),
+            ns_separator: None,
+            front_ends: HashMap::new(),
+            static_content: StaticContentConfig::default(),
+            exclude: None,
+            ignore_files: None,
+            extends: Vec::new(),
+            workspaces: Vec::new(),
+            command: HashMap::new(),
+            resource: None,
+            func: None,
+            trans: None,
         }
     }
 }
 
+/// 加载后的配置，附带未识别的配置项
+#[derive(Debug, Clone)]
+pub struct LoadedJsConfig {
+    pub config: JsConfig,
+    /// 未识别的配置项路径，如 `trans.compnent`
+    pub unknown_keys: Vec<String>,
+    /// 各配置项在文件中的位置，TOML 配置不记录
+    pub key_locations: KeyLocations,
+}
+
+impl LoadedJsConfig {
+    /// 未识别配置项的提示信息
+    pub fn warnings(&self) -> Vec<String> {
+        self.unknown_keys
+            .iter()
+            .map(|key| format!("Unknown config key \"{}\" is ignored", key))
+            .collect()
+    }
+
+    /// 检查配置：未识别的配置项与 `validate_config` 的结果，带配置项所在的行列号
+    pub fn check(&self) -> Vec<ConfigDiagnostic> {
+        self.unknown_keys
+            .iter()
+            .map(|key| ConfigDiagnostic::warning(key, "unknown config key, it is ignored".to_string()))
+            .chain(validate_config(&self.config.to_scan_config()))
+            .map(|diagnostic| diagnostic.locate(&self.key_locations))
+            .collect()
+    }
+}
+
 impl JsConfig {
-    /// 从 JavaScript 配置文件加载
-    pub fn from_js_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        Self::from_js_string(&content)
+    /// 从配置文件加载，按扩展名选择格式：`.json`、`.jsonc`/`.json5`、`.toml`，其余按 JavaScript 模块解析
+    pub fn from_js_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::load(path)?.config)
+    }
+
+    /// 加载配置文件并收集未识别的配置项
+    ///
+    /// `extends` 中的配置文件（相对于当前配置文件所在目录）先按顺序合并，再以当前文件覆盖：
+    /// 对象逐项合并，数组与其他值整体替换。
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<LoadedJsConfig> {
+        let path = path.as_ref();
+        let (value, key_locations) = read_config_value(path).map_err(Error::Config)?;
+        let value = resolve_extends(path, value).map_err(Error::Config)?;
+        let loaded = Self::from_value(value).map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+        Ok(LoadedJsConfig { key_locations, ..loaded })
     }
 
     /// 从 JavaScript 字符串解析配置
-    pub fn from_js_string(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        // 简单的 JavaScript 配置解析
-        // 移除 module.exports = 和注释
-        let cleaned = content
-            .lines()
-            .filter(|line| !line.trim().starts_with("//"))
-            .collect::<Vec<_>>()
-            .join("\n");
-        
-        // 提取 JSON 部分
-        let start = cleaned.find('{').ok_or("Invalid config format")?;
-        let end = cleaned.rfind('}').ok_or("Invalid config format")?;
-        let json_str = &cleaned[start..=end];
-        
-        // 处理 JavaScript 对象语法到 JSON
-        let json_str = json_str
-            .replace("'", "\"")  // 单引号转双引号
-            .replace(",\n}", "\n}")  // 移除尾随逗号
-            .replace(",\n  }", "\n  }")
-            // 为 JavaScript 对象键添加引号
-            .replace("input:", "\"input\":")
-            .replace("output:", "\"output\":")
-            .replace("debug:", "\"debug\":")
-            .replace("sort:", "\"sort\":")
-            .replace("removeUnusedKeys:", "\"removeUnusedKeys\":")
-            .replace("lngs:", "\"lngs\":")
-            .replace("defaultLng:", "\"defaultLng\":");
-        
-        let config: JsConfig = serde_json::from_str(&json_str)?;
-        Ok(config)
-    }
-
-    /// 转换为扫描配置
-    pub fn to_scan_config(&self) -> crate::scan::config::ScanConfig {
-        crate::scan::config::ScanConfig {
+    pub fn from_js_string(content: &str) -> Result<Self> {
+        Ok(Self::from_value(parse_js_module(content).map_err(Error::Config)?)?.config)
+    }
+
+    /// 从 JSON 值反序列化，记录未识别的配置项
+    pub fn from_value(value: serde_json::Value) -> Result<LoadedJsConfig> {
+        let mut unknown_keys = Vec::new();
+        let config = serde_ignored::deserialize(value, |path| unknown_keys.push(key_path(&path)))
+            .map_err(|e| Error::Config(e.to_string()))?;
+        Ok(LoadedJsConfig {
+            config,
+            unknown_keys,
+            key_locations: KeyLocations::new(),
+        })
+    }
+
+    /// 转换为扫描配置，未配置的项使用 `ScanConfig` 的默认值
+    pub fn to_scan_config(&self) -> ScanConfig {
+        let defaults = ScanConfig::default();
+        ScanConfig {
             input: self.input.clone(),
             lngs: self.lngs.clone(),
-            ns: vec!["translation".to_string()],
+            ns: self.ns.clone().unwrap_or(defaults.ns),
             default_lng: self.default_lng.clone(),
-            default_ns: "translation".to_string(),
-            resource: crate::scan::config::ResourceConfig {
-                load_path: "".to_string(),
-                save_path: format!("{}/{{{{lng}}}}.json", self.output),
+            default_ns: self.default_ns.clone().unwrap_or(defaults.default_ns),
+            resource: match (&self.resource, self.output.is_empty()) {
+                (Some(resource), _) => resource.clone(),
+                (None, true) => defaults.resource,
+                (None, false) => ResourceConfig {
+                    load_path: "".to_string(),
+                    save_path: format!("{}/{{{{lng}}}}.json", self.output),
+                },
             },
-            func: crate::scan::config::FuncConfig::default(),
-            trans: crate::scan::config::TransConfig::default(),
+            func: self.func.clone().unwrap_or(defaults.func),
+            trans: self.trans.clone().unwrap_or(defaults.trans),
+            duplicate_keys: self.duplicate_keys,
+            hash_collisions: self.hash_collisions,
+            key_strategy: self.key_strategy.clone(),
+            ns_separator: self.ns_separator.clone().unwrap_or(defaults.ns_separator),
+            plural_style: self.plural_style,
+            front_ends: self.front_ends.clone(),
+            static_content: self.static_content.clone(),
+            exclude: self.exclude.clone().unwrap_or(defaults.exclude),
+            ignore_files: self.ignore_files.clone().unwrap_or(defaults.ignore_files),
         }
     }
 }
 
+/// 读取配置文件，按扩展名选择格式
+pub(super) fn read_config_value(path: &Path) -> Result<(serde_json::Value, KeyLocations), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        // JSON 是合法的 JS 值，位置信息由 JS 解析器给出
+        "json" => serde_json::from_str(&content)
+            .map(|value| (value, locate_js_value(&content).map(|(_, l)| l).unwrap_or_default()))
+            .map_err(|e| e.to_string()),
+        "jsonc" | "json5" => locate_js_value(&content),
+        "toml" => toml::from_str::<serde_json::Value>(&content)
+            .map(|value| (value, KeyLocations::new()))
+            .map_err(|e| e.to_string()),
+        _ => locate_js_module(&content),
+    }
+    .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// 按继承顺序（先父后子）列出 `extends` 链上的配置文件及其内容，`chain` 为正在加载的文件，用于发现循环引用
+pub(super) fn extends_layers(
+    path: &Path,
+    value: serde_json::Value,
+    chain: &mut Vec<PathBuf>,
+    layers: &mut Vec<(PathBuf, serde_json::Value)>,
+) -> Result<(), String> {
+    let parents: Vec<String> = match value.get("extends") {
+        None => Vec::new(),
+        Some(serde_json::Value::String(parent)) => vec![parent.clone()],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("{}: extends must be a path or a list of paths", path.display()))?,
+        Some(_) => return Err(format!("{}: extends must be a path or a list of paths", path.display())),
+    };
+
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if chain.contains(&canonical) {
+        let cycle: Vec<String> = chain.iter().chain([&canonical]).map(|p| p.display().to_string()).collect();
+        return Err(format!("circular extends: {}", cycle.join(" -> ")));
+    }
+    chain.push(canonical);
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    for parent in parents {
+        let parent_path = dir.join(parent);
+        let (parent_value, _) = read_config_value(&parent_path)?;
+        extends_layers(&parent_path, parent_value, chain, layers)?;
+    }
+    chain.pop();
+
+    layers.push((path.to_path_buf(), value));
+    Ok(())
+}
+
+/// 合并 `extends` 链上的配置
+fn resolve_extends(path: &Path, value: serde_json::Value) -> Result<serde_json::Value, String> {
+    let mut layers = Vec::new();
+    extends_layers(path, value, &mut Vec::new(), &mut layers)?;
+    let mut merged = serde_json::Value::Object(serde_json::Map::new());
+    for (_, layer) in layers {
+        merge_values(&mut merged, layer);
+    }
+    Ok(merged)
+}
+
+/// 以 `overlay` 覆盖 `base`：对象逐项合并，其他值整体替换
+///
+/// `type` 不同的对象（如 `keyStrategy`）是不同的变体，同样整体替换。
+pub(super) fn merge_values(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) if existing.is_object() && value.is_object() && same_variant(existing, &value) => {
+                        merge_values(existing, value)
+                    }
+                    _ => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// `overlay` 未指定 `type`，或与 `base` 的 `type` 相同
+fn same_variant(base: &serde_json::Value, overlay: &serde_json::Value) -> bool {
+    overlay.get("type").is_none_or(|variant| base.get("type") == Some(variant))
+}
+
+/// `extends` 可以是单个路径或路径列表
+fn string_or_list<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StringOrList {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match StringOrList::deserialize(deserializer)? {
+        StringOrList::One(value) => vec![value],
+        StringOrList::Many(values) => values,
+    })
+}
+
+/// 配置项路径，如 `trans.component`、`input[0]`
+fn key_path(path: &serde_ignored::Path) -> String {
+    use serde_ignored::Path;
+    match path {
+        Path::Root => String::new(),
+        Path::Seq { parent, index } => format!("{}[{}]", key_path(parent), index),
+        Path::Map { parent, key } => match key_path(parent) {
+            prefix if prefix.is_empty() => key.clone(),
+            prefix => format!("{}.{}", prefix, key),
+        },
+        Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => key_path(parent),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,8 +347,153 @@ module.exports = {
         let config = JsConfig::from_js_string(js_content).unwrap();
         assert_eq!(config.input.len(), 2);
         assert_eq!(config.output, "./i18n");
-        assert_eq!(config.debug, true);
+        assert!(config.debug);
         assert_eq!(config.default_lng, "zh");
         assert_eq!(config.lngs, vec!["zh", "en"]);
+        assert_eq!(config.duplicate_keys, DuplicateKeyStrategy::Error);
+    }
+
+    #[test]
+    fn test_parse_duplicate_keys_strategy() {
+        let js_content = r#"
+module.exports = {
+  input: ['./src/**/*.tsx'],
+  output: './i18n',
+  lngs: ['zh', 'en'],
+  defaultLng: 'zh',
+  duplicateKeys: 'last',
+  hashCollisions: 'disambiguate',
+};
+        "#;
+
+        let config = JsConfig::from_js_string(js_content).unwrap();
+        assert_eq!(config.duplicate_keys, DuplicateKeyStrategy::Last);
+        assert_eq!(config.to_scan_config().duplicate_keys, DuplicateKeyStrategy::Last);
+        assert_eq!(config.to_scan_config().hash_collisions, HashCollisionStrategy::Disambiguate);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_nested_config() {
+        let js_content = r#"
+module.exports = {
+  input: ['./src/**/*.{js,jsx,ts,tsx}'],
+  output: './i18n',
+  lngs: ['zh', 'en'],
+  defaultLng: 'zh',
+  defaultNs: 'common',
+  resource: {
+    savePath: 'i18n/translation/{{lng}}.js',
+  },
+  func: {
+    list: ['__', 'i18n.t'],
+  },
+  trans: {
+    component: 'I18n',
+  },
+  keyStrategy: { type: 'hash64', length: 12 },
+  frontEnds: { html: 'angular' },
+};
+        "#;
+
+        let config = JsConfig::from_js_string(js_content).unwrap().to_scan_config();
+        assert_eq!(config.default_ns, "common");
+        assert_eq!(config.save_path("en", "common"), "i18n/translation/en.js");
+        assert_eq!(config.func.list, vec!["__", "i18n.t"]);
+        assert_eq!(config.trans.component, "I18n");
+        assert_eq!(config.trans.i18n_key, "i18nKey");
+        assert_eq!(
+            config.key_strategy,
+            KeyStrategyConfig::Hash64 {
+                prefix: "k_".to_string(),
+                length: 12
+            }
+        );
+        assert_eq!(config.front_end_for("index.html"), Some(FrontEndKind::Angular));
+    }
+
+    #[test]
+    fn test_load_config_formats() {
+        let dir = std::env::temp_dir().join(format!("js-config-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let js = dir.join("i18n.config.js");
+        fs::write(
+            &js,
+            r#"
+const src = './src';
+/* 共享的语言配置 */
+const lngs = ['zh', 'en'];
+module.exports = {
+  input: [`${src}/**/*.{js,ts}`, "${src}/it's-literal.js"],
+  output: './i18n', // 输出目录
+  lngs,
+  defaultLng: lngs[0] === undefined ? 'zh' : 'zh',
+};
+"#,
+        )
+        .unwrap();
+        let error = JsConfig::load(&js).unwrap_err().to_string();
+        assert!(error.ends_with("line 9, column 19: expected `,` or `}`"), "{}", error);
+
+        fs::write(
+            &js,
+            "const src = './src';\nmodule.exports = {\n  input: [`${src}/**/*.js`, \"it's.js\"],\n  output: './i18n',\n  lngs: ['zh', 'en'],\n  defaultLng: 'zh',\n  trans: { compnent: 'I18n' },\n  typo: true,\n};\n",
+        )
+        .unwrap();
+        let loaded = JsConfig::load(&js).unwrap();
+        assert_eq!(loaded.config.input, vec!["./src/**/*.js", "it's.js"]);
+        assert_eq!(loaded.unknown_keys, vec!["trans.compnent", "typo"]);
+        assert_eq!(loaded.warnings()[1], "Unknown config key \"typo\" is ignored");
+        let located: Vec<_> = loaded.check().into_iter().map(|d| (d.path, d.line, d.column)).collect();
+        assert_eq!(
+            located,
+            vec![
+                ("trans.compnent".to_string(), Some(7), Some(12)),
+                ("typo".to_string(), Some(8), Some(3)),
+            ]
+        );
+
+        let jsonc = dir.join("i18n.config.jsonc");
+        fs::write(&jsonc, "{\n  // 注释\n  \"input\": [\"src/**/*.ts\"],\n  \"output\": \"i18n\",\n  \"lngs\": [\"zh\"],\n  \"defaultLng\": \"zh\",\n}\n").unwrap();
+        assert_eq!(JsConfig::from_js_file(&jsonc).unwrap().input, vec!["src/**/*.ts"]);
+
+        let toml = dir.join("i18n.config.toml");
+        fs::write(&toml, "input = [\"src/**/*.ts\"]\noutput = \"i18n\"\nlngs = [\"zh\", \"en\"]\ndefaultLng = \"zh\"\n\n[resource]\nsavePath = \"i18n/{{lng}}.json\"\n").unwrap();
+        let config = JsConfig::from_js_file(&toml).unwrap().to_scan_config();
+        assert_eq!(config.save_path("en", "translation"), "i18n/en.json");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extends() {
+        let dir = std::env::temp_dir().join(format!("js-config-extends-{}", std::process::id()));
+        fs::create_dir_all(dir.join("packages/admin")).unwrap();
+        fs::write(
+            dir.join("i18n.config.js"),
+            "module.exports = {\n  input: ['src/**/*.js'],\n  output: 'i18n',\n  lngs: ['zh', 'en'],\n  defaultLng: 'zh',\n  func: { list: ['t'], hooks: ['useT'] },\n};\n",
+        )
+        .unwrap();
+        fs::write(dir.join("packages/base.json"), "{ \"extends\": \"../i18n.config.js\", \"lngs\": [\"zh\", \"en\", \"ja\"] }").unwrap();
+        let child = dir.join("packages/admin/i18n.config.js");
+        fs::write(
+            &child,
+            "module.exports = {\n  extends: ['../base.json'],\n  input: ['../../packages/admin-component/src/**/*.js'],\n  func: { list: ['__'] },\n};\n",
+        )
+        .unwrap();
+
+        let config = JsConfig::from_js_file(&child).unwrap();
+        assert_eq!(config.extends, vec!["../base.json"]);
+        assert_eq!(config.input, vec!["../../packages/admin-component/src/**/*.js"]);
+        assert_eq!(config.lngs, vec!["zh", "en", "ja"]);
+        let config = config.to_scan_config();
+        assert_eq!(config.func.list, vec!["__"]);
+        assert_eq!(config.func.hooks, vec!["useT"]);
+
+        fs::write(dir.join("packages/base.json"), "{ \"extends\": \"admin/i18n.config.js\" }").unwrap();
+        let error = JsConfig::load(&child).unwrap_err().to_string();
+        assert!(error.starts_with("circular extends: "), "{}", error);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
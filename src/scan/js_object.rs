@@ -0,0 +1,518 @@
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+
+/// 解析 JS 配置模块，返回 `module.exports` 或 `export default` 导出的值
+///
+/// 支持注释、尾随逗号、未加引号的 key、单引号与模板字符串、顶层 `const`/`let`/`var` 常量
+/// 及其引用、展开（`...base`）与简写属性。不执行代码：`require`、函数调用等会报错。
+pub fn parse_js_module(source: &str) -> Result<Value, String> {
+    locate_js_module(source).map(|(value, _)| value)
+}
+
+/// 配置项路径（如 `func.list[0]`）到其 key 在源码中的行列号，列号从 1 开始
+pub type KeyLocations = HashMap<String, (u32, u32)>;
+
+/// 同 `parse_js_module`，同时返回导出对象中各配置项的位置
+pub fn locate_js_module(source: &str) -> Result<(Value, KeyLocations), String> {
+    let mut parser = JsParser::new(source);
+    let mut exported = None;
+    loop {
+        parser.skip_trivia();
+        if parser.peek().is_none() {
+            break;
+        }
+        if parser.eat(';') {
+            continue;
+        }
+        if let Some(quote @ ('\'' | '"')) = parser.peek() {
+            // 指令，如 'use strict'
+            parser.string(quote)?;
+            continue;
+        }
+        let start = parser.pos;
+        let word = parser.identifier().ok_or_else(|| parser.error("expected a statement"))?;
+        match word.as_str() {
+            "const" | "let" | "var" => {
+                let name = parser.expect_identifier()?;
+                parser.expect('=')?;
+                let value = parser.value()?;
+                parser.constants.insert(name, value);
+            }
+            "module" => {
+                parser.expect('.')?;
+                if parser.expect_identifier()? != "exports" {
+                    return Err(parser.error_at(start, "expected `module.exports`"));
+                }
+                parser.expect('=')?;
+                exported = Some(parser.exported_value()?);
+            }
+            "export" => {
+                if parser.expect_identifier()? != "default" {
+                    return Err(parser.error_at(start, "only `export default` is supported"));
+                }
+                exported = Some(parser.exported_value()?);
+            }
+            "import" | "require" => {
+                return Err(parser.error_at(start, "imports are not supported in config files, inline the values instead"));
+            }
+            _ => return Err(parser.error_at(start, &format!("unsupported statement `{}`", word))),
+        }
+    }
+    let exported = exported.ok_or_else(|| "config file has no `module.exports` or `export default`".to_string())?;
+    Ok((exported, parser.locations))
+}
+
+/// 解析单个 JS 值，如 JSON、JSONC 或对象字面量
+pub fn parse_js_value(source: &str) -> Result<Value, String> {
+    locate_js_value(source).map(|(value, _)| value)
+}
+
+/// 同 `parse_js_value`，同时返回各配置项的位置
+pub fn locate_js_value(source: &str) -> Result<(Value, KeyLocations), String> {
+    let mut parser = JsParser::new(source);
+    let value = parser.exported_value()?;
+    parser.skip_trivia();
+    match parser.peek() {
+        None => Ok((value, parser.locations)),
+        Some(_) => Err(parser.error("unexpected content after the value")),
+    }
+}
+
+struct JsParser {
+    chars: Vec<char>,
+    pos: usize,
+    constants: HashMap<String, Value>,
+    /// 解析导出值时记录 key 的位置，常量中的 key 不记录
+    recording: bool,
+    path: Vec<String>,
+    locations: KeyLocations,
+}
+
+impl JsParser {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            constants: HashMap::new(),
+            recording: false,
+            path: Vec::new(),
+            locations: KeyLocations::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, ch: char) -> bool {
+        self.skip_trivia();
+        if self.peek() == Some(ch) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, ch: char) -> Result<(), String> {
+        if self.eat(ch) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", ch)))
+        }
+    }
+
+    fn error(&self, message: &str) -> String {
+        self.error_at(self.pos, message)
+    }
+
+    /// 带行列号的错误信息
+    fn error_at(&self, pos: usize, message: &str) -> String {
+        let (line, column) = self.line_column(pos);
+        format!("line {}, column {}: {}", line, column, message)
+    }
+
+    fn line_column(&self, pos: usize) -> (u32, u32) {
+        let before = &self.chars[..pos.min(self.chars.len())];
+        let line = before.iter().filter(|c| **c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        (line as u32, column as u32)
+    }
+
+    fn exported_value(&mut self) -> Result<Value, String> {
+        self.recording = true;
+        let value = self.value();
+        self.recording = false;
+        value
+    }
+
+    /// 在 `segment` 下解析值，并记录其位置
+    fn value_at(&mut self, segment: String, start: usize) -> Result<Value, String> {
+        if !self.recording {
+            return self.value();
+        }
+        self.path.push(segment);
+        self.record_location(start);
+        let value = self.value();
+        self.path.pop();
+        value
+    }
+
+    /// 记录当前路径的位置，同一路径以最先出现的为准
+    fn record_location(&mut self, start: usize) {
+        let mut key = String::new();
+        for segment in &self.path {
+            if !key.is_empty() && !segment.starts_with('[') {
+                key.push('.');
+            }
+            key.push_str(segment);
+        }
+        let location = self.line_column(start);
+        self.locations.entry(key).or_insert(location);
+    }
+
+    /// 跳过空白与注释
+    fn skip_trivia(&mut self) {
+        loop {
+            match (self.peek(), self.peek_at(1)) {
+                (Some(c), _) if c.is_whitespace() || c == '\u{feff}' => self.pos += 1,
+                (Some('/'), Some('/')) => {
+                    while self.peek().is_some_and(|c| c != '\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some('/'), Some('*')) => {
+                    self.pos += 2;
+                    while self.peek().is_some() && !(self.peek() == Some('*') && self.peek_at(1) == Some('/')) {
+                        self.pos += 1;
+                    }
+                    self.pos = (self.pos + 2).min(self.chars.len());
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        self.skip_trivia();
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        {
+            if self.pos == start && self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                break;
+            }
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| self.chars[start..self.pos].iter().collect())
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, String> {
+        self.identifier().ok_or_else(|| self.error("expected an identifier"))
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_trivia();
+        let start = self.pos;
+        let value = match self.peek() {
+            Some('{') => self.object()?,
+            Some('[') => self.array()?,
+            Some(quote @ ('\'' | '"')) => Value::String(self.string(quote)?),
+            Some('`') => Value::String(self.template()?),
+            Some(c) if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => self.number()?,
+            Some(_) => {
+                let name = self.expect_identifier()?;
+                let value = match name.as_str() {
+                    "true" => Value::Bool(true),
+                    "false" => Value::Bool(false),
+                    "null" | "undefined" => Value::Null,
+                    "require" | "import" => {
+                        return Err(self.error_at(start, "imports are not supported in config files, inline the values instead"))
+                    }
+                    _ => self
+                        .constants
+                        .get(&name)
+                        .cloned()
+                        .ok_or_else(|| self.error_at(start, &format!("unknown identifier `{}`", name)))?,
+                };
+                self.member_access(value, start)?
+            }
+            None => return Err(self.error("unexpected end of file")),
+        };
+        self.skip_trivia();
+        if self.peek() == Some('(') {
+            return Err(self.error_at(start, "function calls are not supported in config files"));
+        }
+        Ok(value)
+    }
+
+    /// 常量的属性访问，如 `paths.src`
+    fn member_access(&mut self, mut value: Value, start: usize) -> Result<Value, String> {
+        loop {
+            self.skip_trivia();
+            if self.peek() != Some('.') || self.peek_at(1) == Some('.') {
+                return Ok(value);
+            }
+            self.pos += 1;
+            let property = self.expect_identifier()?;
+            value = value
+                .get(&property)
+                .cloned()
+                .ok_or_else(|| self.error_at(start, &format!("unknown property `{}`", property)))?;
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect('{')?;
+        let mut map = Map::new();
+        loop {
+            if self.eat('}') {
+                return Ok(Value::Object(map));
+            }
+            self.skip_trivia();
+            let start = self.pos;
+            if self.peek() == Some('.') && self.peek_at(1) == Some('.') && self.peek_at(2) == Some('.') {
+                self.pos += 3;
+                match self.value()? {
+                    Value::Object(spread) => map.extend(spread),
+                    _ => return Err(self.error_at(start, "only objects can be spread into an object")),
+                }
+            } else {
+                let key = match self.peek() {
+                    Some(quote @ ('\'' | '"')) => self.string(quote)?,
+                    Some(c) if c.is_ascii_digit() => self.number()?.to_string(),
+                    Some('[') => return Err(self.error("computed keys are not supported in config files")),
+                    _ => self.expect_identifier()?,
+                };
+                let value = if self.eat(':') {
+                    self.value_at(key.clone(), start)?
+                } else {
+                    // 简写属性 `{ input }`
+                    if self.recording {
+                        self.path.push(key.clone());
+                        self.record_location(start);
+                        self.path.pop();
+                    }
+                    self.constants
+                        .get(&key)
+                        .cloned()
+                        .ok_or_else(|| self.error_at(start, &format!("expected `:` after key `{}`", key)))?
+                };
+                map.insert(key, value);
+            }
+            if !self.eat(',') {
+                if !self.eat('}') {
+                    return Err(self.error("expected `,` or `}`"));
+                }
+                return Ok(Value::Object(map));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            if self.eat(']') {
+                return Ok(Value::Array(items));
+            }
+            self.skip_trivia();
+            let start = self.pos;
+            if self.peek() == Some('.') && self.peek_at(1) == Some('.') && self.peek_at(2) == Some('.') {
+                self.pos += 3;
+                match self.value()? {
+                    Value::Array(spread) => items.extend(spread),
+                    _ => return Err(self.error_at(start, "only arrays can be spread into an array")),
+                }
+            } else {
+                let value = self.value_at(format!("[{}]", items.len()), start)?;
+                items.push(value);
+            }
+            if !self.eat(',') {
+                if !self.eat(']') {
+                    return Err(self.error("expected `,` or `]`"));
+                }
+                return Ok(Value::Array(items));
+            }
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None | Some('\n') => return Err(self.error_at(start, "unterminated string")),
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') => out.push_str(&self.escape()?),
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    /// 模板字符串，`${name}` 只能引用常量
+    fn template(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut out = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error_at(start, "unterminated template string")),
+                Some('`') => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                Some('\\') => out.push_str(&self.escape()?),
+                Some('$') if self.peek_at(1) == Some('{') => {
+                    self.pos += 2;
+                    let value = self.value()?;
+                    self.expect('}')?;
+                    match value {
+                        Value::String(s) => out.push_str(&s),
+                        Value::Null => out.push_str("null"),
+                        other => out.push_str(&other.to_string()),
+                    }
+                }
+                Some(c) => {
+                    out.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.pos += 1;
+        let ch = self.peek().ok_or_else(|| self.error_at(start, "unterminated escape"))?;
+        self.pos += 1;
+        let hex = |parser: &mut Self, len: usize| -> Result<char, String> {
+            let digits: String = parser.chars[parser.pos..(parser.pos + len).min(parser.chars.len())].iter().collect();
+            parser.pos += len;
+            u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .ok_or_else(|| parser.error_at(start, "invalid escape sequence"))
+        };
+        Ok(match ch {
+            'n' => "\n".to_string(),
+            't' => "\t".to_string(),
+            'r' => "\r".to_string(),
+            'b' => "\u{8}".to_string(),
+            'f' => "\u{c}".to_string(),
+            'v' => "\u{b}".to_string(),
+            '0' => "\0".to_string(),
+            '\n' => String::new(),
+            'x' => hex(self, 2)?.to_string(),
+            'u' if self.peek() == Some('{') => {
+                self.pos += 1;
+                let len = self.chars[self.pos..].iter().position(|c| *c == '}').unwrap_or(0);
+                let ch = hex(self, len)?;
+                self.pos += 1;
+                ch.to_string()
+            }
+            'u' => hex(self, 4)?.to_string(),
+            other => other.to_string(),
+        })
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.pos;
+        let negative = self.peek() == Some('-');
+        if matches!(self.peek(), Some('-' | '+')) {
+            self.pos += 1;
+        }
+        if self.peek() == Some('0') && matches!(self.peek_at(1), Some('x' | 'X')) {
+            self.pos += 2;
+            let digits_start = self.pos;
+            while self.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                self.pos += 1;
+            }
+            let digits: String = self.chars[digits_start..self.pos].iter().collect();
+            let value = i64::from_str_radix(&digits, 16).map_err(|_| self.error_at(start, "invalid number"))?;
+            return Ok(Value::from(if negative { -value } else { value }));
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '_') || (matches!(c, '+' | '-') && matches!(self.chars[self.pos - 1], 'e' | 'E')))
+        {
+            self.pos += 1;
+        }
+        let text: String = self.chars[start..self.pos].iter().filter(|c| **c != '_' && **c != '+').collect();
+        if let Ok(value) = text.parse::<i64>() {
+            return Ok(Value::from(value));
+        }
+        text.parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| self.error_at(start, "invalid number"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_js_module() {
+        let source = r#"
+// 共享的源码目录
+const src = './src';
+const base = { lngs: ['zh', 'en'], /* 默认语言 */ defaultLng: 'zh' };
+const ns = ["translation"];
+
+module.exports = {
+  ...base,
+  input: [`${src}/**/*.{js,ts}`, '!**/*.spec.js',],
+  ns,
+  note: 'it\'s "fine"',
+  "quoted-key": 0x10,
+  nested: { depth: -1.5e2, flag: true, none: undefined },
+};
+"#;
+        assert_eq!(
+            parse_js_module(source).unwrap(),
+            json!({
+                "lngs": ["zh", "en"],
+                "defaultLng": "zh",
+                "input": ["./src/**/*.{js,ts}", "!**/*.spec.js"],
+                "ns": ["translation"],
+                "note": "it's \"fine\"",
+                "quoted-key": 16,
+                "nested": { "depth": -150.0, "flag": true, "none": null }
+            })
+        );
+        assert_eq!(parse_js_module("export default { a: 1 };").unwrap(), json!({ "a": 1 }));
+
+        let (_, locations) = locate_js_module(source).unwrap();
+        assert_eq!(locations["input"], (9, 3));
+        assert_eq!(locations["input[1]"], (9, 34));
+        assert_eq!(locations["ns"], (10, 3));
+        assert_eq!(locations["nested.flag"], (13, 28));
+        assert!(!locations.contains_key("lngs"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_js_module("module.exports = {\n  input: path.join('src'),\n};").unwrap_err();
+        assert_eq!(error, "line 2, column 10: unknown identifier `path`");
+        let error = parse_js_module("const p = require('path');").unwrap_err();
+        assert!(error.contains("imports are not supported"));
+        assert!(parse_js_module("const a = 1;").is_err());
+        assert_eq!(parse_js_value("{ // jsonc\n \"a\": [1, 2,], }").unwrap(), json!({ "a": [1, 2] }));
+    }
+}
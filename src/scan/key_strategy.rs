@@ -0,0 +1,387 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// 由句子生成词条 key 的策略
+pub trait KeyStrategy: Send + Sync {
+    /// 为句子生成 key（不含 context 后缀）
+    fn generate(&self, sentence: &str) -> String;
+
+    /// 发生哈希冲突时为句子生成第 `salt` 个候选 key
+    fn salted(&self, sentence: &str, salt: u32) -> String {
+        self.generate(&format!("{}#{}", sentence, salt))
+    }
+}
+
+/// 与 `demo/i18n/scan/hash-key.js` 完全一致的算法：
+/// `prefix + ('0000' + hashString(value.replace(/\s+/g, '')).toString(36)).slice(-7)`
+#[derive(Debug, Clone)]
+pub struct LegacyHashKey {
+    pub prefix: String,
+}
+
+impl Default for LegacyHashKey {
+    fn default() -> Self {
+        Self {
+            prefix: "k_".to_string(),
+        }
+    }
+}
+
+impl KeyStrategy for LegacyHashKey {
+    fn generate(&self, sentence: &str) -> String {
+        let cleaned: String = sentence.chars().filter(|c| !is_js_whitespace(*c)).collect();
+        let padded = format!("0000{}", radix_36(hash_string(&cleaned) as u64));
+        let start = padded.len().saturating_sub(7);
+        format!("{}{}", self.prefix, &padded[start..])
+    }
+}
+
+/// 64 位 FNV-1a 哈希，key 长度可配置，适合词条量大的新项目
+#[derive(Debug, Clone)]
+pub struct Hash64Key {
+    pub prefix: String,
+    /// 36 进制哈希部分的长度，取值 1..=13
+    pub length: usize,
+}
+
+impl Default for Hash64Key {
+    fn default() -> Self {
+        Self {
+            prefix: "k_".to_string(),
+            length: 11,
+        }
+    }
+}
+
+impl KeyStrategy for Hash64Key {
+    fn generate(&self, sentence: &str) -> String {
+        let cleaned: String = sentence.chars().filter(|c| !is_js_whitespace(*c)).collect();
+        let hash = format!("{:0>13}", radix_36(fnv1a_64(&cleaned)));
+        let length = self.length.clamp(1, 13);
+        format!("{}{}", self.prefix, &hash[13 - length..])
+    }
+}
+
+/// 可读的 slug key，如 `Save changes` → `save_changes`
+///
+/// 句子中没有 ASCII 字母或数字时（如纯中文），退化为 `Hash64Key`。
+#[derive(Debug, Clone)]
+pub struct SlugKey {
+    pub prefix: String,
+    pub max_length: usize,
+}
+
+impl Default for SlugKey {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            max_length: 32,
+        }
+    }
+}
+
+impl KeyStrategy for SlugKey {
+    fn generate(&self, sentence: &str) -> String {
+        let words: Vec<String> = sentence
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.to_ascii_lowercase())
+            .collect();
+
+        if words.is_empty() {
+            return Hash64Key {
+                prefix: self.prefix.clone(),
+                ..Default::default()
+            }
+            .generate(sentence);
+        }
+
+        let mut slug = words.join("_");
+        slug.truncate(self.max_length.max(1));
+        let slug = slug.trim_end_matches('_');
+        format!("{}{}", self.prefix, slug)
+    }
+
+    fn salted(&self, sentence: &str, salt: u32) -> String {
+        // 追加 4 位哈希后缀，避免 `_1` 之类的后缀被当作复数形式
+        let suffix = Hash64Key {
+            prefix: String::new(),
+            length: 4,
+        }
+        .generate(&format!("{}#{}", sentence, salt));
+        format!("{}_{}", self.generate(sentence), suffix)
+    }
+}
+
+/// 可在配置中选择的 key 生成策略
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum KeyStrategyConfig {
+    Legacy {
+        #[serde(default = "default_prefix")]
+        prefix: String,
+    },
+    Hash64 {
+        #[serde(default = "default_prefix")]
+        prefix: String,
+        #[serde(default = "default_hash64_length")]
+        length: usize,
+    },
+    Slug {
+        #[serde(default)]
+        prefix: String,
+        #[serde(default = "default_slug_max_length", rename = "maxLength", alias = "max_length")]
+        max_length: usize,
+    },
+}
+
+fn default_prefix() -> String {
+    "k_".to_string()
+}
+
+fn default_hash64_length() -> usize {
+    Hash64Key::default().length
+}
+
+fn default_slug_max_length() -> usize {
+    SlugKey::default().max_length
+}
+
+impl Default for KeyStrategyConfig {
+    fn default() -> Self {
+        KeyStrategyConfig::Legacy {
+            prefix: default_prefix(),
+        }
+    }
+}
+
+impl KeyStrategyConfig {
+    /// 根据配置创建策略实例
+    pub fn build(&self) -> Box<dyn KeyStrategy> {
+        match self {
+            KeyStrategyConfig::Legacy { prefix } => Box::new(LegacyHashKey {
+                prefix: prefix.clone(),
+            }),
+            KeyStrategyConfig::Hash64 { prefix, length } => Box::new(Hash64Key {
+                prefix: prefix.clone(),
+                length: *length,
+            }),
+            KeyStrategyConfig::Slug { prefix, max_length } => Box::new(SlugKey {
+                prefix: prefix.clone(),
+                max_length: *max_length,
+            }),
+        }
+    }
+
+    /// 识别该策略生成的 key 上的 context 与复数后缀
+    pub fn pattern(&self) -> KeyPattern {
+        let (prefix, hash) = match self {
+            // `('0000' + hash).slice(-7)`，哈希部分为 5 到 7 位
+            KeyStrategyConfig::Legacy { prefix } => (prefix, Some("[0-9a-z]{5,7}".to_string())),
+            KeyStrategyConfig::Hash64 { prefix, length } => {
+                (prefix, Some(format!("[0-9a-z]{{{}}}", length.clamp(&1, &13))))
+            }
+            // slug 本身含 `_`，无法从文本中区分 context
+            KeyStrategyConfig::Slug { prefix, .. } => (prefix, None),
+        };
+        let prefix = regex::escape(prefix);
+        KeyPattern {
+            plural: Regex::new(&format!(r"^({}.+)_(\d|plural|zero|one|two|few|many|other)$", prefix)).unwrap(),
+            context: hash.map(|hash| Regex::new(&format!("^({}{})_(.+)$", prefix, hash)).unwrap()),
+        }
+    }
+}
+
+/// 资源文件中的 key 结构：`key[_context][_复数后缀]`，由 [`KeyStrategyConfig::pattern`] 创建
+#[derive(Debug, Clone)]
+pub struct KeyPattern {
+    plural: Regex,
+    context: Option<Regex>,
+}
+
+impl Default for KeyPattern {
+    fn default() -> Self {
+        KeyStrategyConfig::default().pattern()
+    }
+}
+
+impl KeyPattern {
+    /// 去掉复数后缀，支持 i18next v3（`_0`、`_plural`）与 v4（`_one`、`_other` 等）
+    pub fn strip_plural<'a>(&self, key: &'a str) -> &'a str {
+        self.plural
+            .captures(key)
+            .and_then(|captures| captures.get(1))
+            .map_or(key, |m| m.as_str())
+    }
+
+    /// 拆分带 context 的 key，如 `k_0012abc_verb` → `("k_0012abc", "verb")`；slug key 总是返回 `None`
+    pub fn split_context<'a>(&self, key: &'a str) -> Option<(&'a str, &'a str)> {
+        let captures = self.context.as_ref()?.captures(key)?;
+        Some((captures.get(1)?.as_str(), captures.get(2)?.as_str()))
+    }
+}
+
+/// 与 JS 正则 `\s` 相同的空白字符集合
+fn is_js_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' ' | '\u{a0}' | '\u{1680}'
+            | '\u{2000}'..='\u{200a}'
+            | '\u{2028}' | '\u{2029}' | '\u{202f}' | '\u{205f}' | '\u{3000}' | '\u{feff}'
+    )
+}
+
+/// 模拟 hash-string 库的行为：按 UTF-16 码元（即 JS 的 `charCodeAt`）计算 31 进制哈希
+fn hash_string(s: &str) -> u32 {
+    let mut hash: u32 = 0;
+    for unit in s.encode_utf16() {
+        hash = hash.wrapping_mul(31).wrapping_add(unit as u32);
+    }
+    hash
+}
+
+fn fnv1a_64(s: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in s.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// 将数字转换为 36 进制字符串
+fn radix_36(mut num: u64) -> String {
+    if num == 0 {
+        return "0".to_string();
+    }
+
+    let chars = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let mut result = Vec::new();
+    while num > 0 {
+        result.push(chars[(num % 36) as usize]);
+        num /= 36;
+    }
+    result.reverse();
+    String::from_utf8(result).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radix_36() {
+        assert_eq!(radix_36(0), "0");
+        assert_eq!(radix_36(35), "z");
+        assert_eq!(radix_36(36), "10");
+    }
+
+    #[test]
+    fn test_hash_string() {
+        let hash1 = hash_string("test");
+        let hash2 = hash_string("test");
+        let hash3 = hash_string("different");
+
+        assert_eq!(hash1, hash2);
+        assert_ne!(hash1, hash3);
+    }
+
+
+    #[test]
+    fn test_legacy_matches_js_algorithm() {
+        let strategy = LegacyHashKey::default();
+        // hashString("test") = 3556498 = "2487m"（36 进制）
+        assert_eq!(strategy.generate("test"), "k_002487m");
+        assert_eq!(strategy.generate("te st"), "k_002487m");
+        // hashString("") = 0，JS 只补 4 个 0
+        assert_eq!(strategy.generate(""), "k_00000");
+        // 中文按 UTF-16 码元计算：hashString("中") = 0x4e2d
+        assert_eq!(strategy.generate("中"), format!("k_0000{}", radix_36(0x4e2d)));
+    }
+
+    #[test]
+    fn test_legacy_custom_prefix() {
+        let strategy = LegacyHashKey {
+            prefix: "admin_".to_string(),
+        };
+        assert_eq!(strategy.generate("test"), "admin_002487m");
+    }
+
+    #[test]
+    fn test_hash64_length() {
+        let short = Hash64Key {
+            prefix: "k_".to_string(),
+            length: 8,
+        };
+        let long = Hash64Key {
+            prefix: "k_".to_string(),
+            length: 13,
+        };
+        assert_eq!(short.generate("保存").len(), 10);
+        assert_eq!(long.generate("保存").len(), 15);
+        assert!(long.generate("保存").ends_with(&short.generate("保存")[2..]));
+        assert_ne!(short.generate("Aa"), short.generate("BB"));
+    }
+
+    #[test]
+    fn test_slug() {
+        let strategy = SlugKey::default();
+        assert_eq!(strategy.generate("Save changes!"), "save_changes");
+        let fallback = Hash64Key {
+            prefix: String::new(),
+            ..Default::default()
+        };
+        assert_eq!(strategy.generate("保存"), fallback.generate("保存"));
+        assert_ne!(strategy.salted("Save", 1), strategy.generate("Save"));
+    }
+
+    #[test]
+    fn test_key_pattern() {
+        let legacy = KeyStrategyConfig::default().pattern();
+        assert_eq!(legacy.strip_plural("k_0012abc_one"), "k_0012abc");
+        assert_eq!(legacy.strip_plural("k_0012abc_verb_plural"), "k_0012abc_verb");
+        assert_eq!(legacy.strip_plural("normal_key_one"), "normal_key_one");
+        assert_eq!(legacy.split_context("k_0012abc_verb"), Some(("k_0012abc", "verb")));
+        assert_eq!(legacy.split_context("k_0012abc"), None);
+
+        let hash64 = KeyStrategyConfig::Hash64 {
+            prefix: "app.".to_string(),
+            length: 8,
+        }
+        .pattern();
+        assert_eq!(hash64.strip_plural("app.0012abcd_other"), "app.0012abcd");
+        assert_eq!(hash64.split_context("app.0012abcd_verb_one"), Some(("app.0012abcd", "verb_one")));
+        assert_eq!(hash64.split_context("appx0012abcd_verb"), None);
+
+        let slug = KeyStrategyConfig::Slug {
+            prefix: String::new(),
+            max_length: 32,
+        }
+        .pattern();
+        assert_eq!(slug.strip_plural("save_changes_other"), "save_changes");
+        assert_eq!(slug.split_context("save_changes"), None);
+    }
+
+    #[test]
+    fn test_config_deserialize() {
+        let config: KeyStrategyConfig = serde_json::from_str(r#"{ "type": "hash64", "length": 12 }"#).unwrap();
+        assert_eq!(
+            config,
+            KeyStrategyConfig::Hash64 {
+                prefix: "k_".to_string(),
+                length: 12
+            }
+        );
+        assert_eq!(config.build().generate("保存").len(), 14);
+        assert_eq!(KeyStrategyConfig::default().build().generate("test"), "k_002487m");
+
+        let slug = KeyStrategyConfig::Slug {
+            prefix: String::new(),
+            max_length: 8,
+        };
+        for json in [r#"{ "type": "slug", "maxLength": 8 }"#, r#"{ "type": "slug", "max_length": 8 }"#] {
+            assert_eq!(serde_json::from_str::<KeyStrategyConfig>(json).unwrap(), slug);
+        }
+        assert_eq!(serde_json::to_value(&slug).unwrap()["maxLength"], 8);
+    }
+}
@@ -0,0 +1,459 @@
+use regex::Regex;
+use std::ops::Range;
+
+use super::config::ScanConfig;
+use super::front_end::{extract_func_calls, FrontEnd};
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+use super::parse_trans_from_string_by_babel::{parse_trans_with_errors, TransParseOptions};
+use super::span::SourceError;
+use super::vue::mask_with;
+use super::zh_linter::ZhLinter;
+
+/// Markdown 与 MDX 文档前端
+///
+/// 检查正文与内嵌 HTML 属性中的中文，代码块、行内代码、注释、链接地址与 `<script>`、`<style>` 默认跳过。
+/// MDX 的 `import`/`export` 与 `{ }` 表达式走 JS 检查流程，文档中的 `t()` 调用与 Trans 组件会被提取；
+/// `staticContent.scripts` 开启时 `<script>` 的内容同样走 JS 提取与检查流程。
+pub struct MarkdownFrontEnd {
+    pub mdx: bool,
+}
+
+impl FrontEnd for MarkdownFrontEnd {
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence> {
+        self.extract_with_errors(content, filepath, config).0
+    }
+
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        let layout = analyze(content, self.mdx, false);
+        let mut occurrences = Vec::new();
+        let mut errors = Vec::new();
+        if config.static_content.scripts {
+            let scripts = mask_ranges(content, &layout.scripts, true);
+            let (script_occurrences, _, script_errors) = extract_func_calls(&scripts, filepath, &config.func, &[]);
+            occurrences.extend(script_occurrences);
+            errors.extend(script_errors);
+        }
+        if !self.mdx {
+            return (occurrences, errors);
+        }
+
+        let source = mask_ranges(content, &[layout.code, layout.scripts].concat(), false);
+        let (func_occurrences, _, func_errors) = extract_func_calls(&source, filepath, &config.func, &[]);
+        occurrences.extend(func_occurrences);
+        errors.extend(func_errors);
+        let options = TransParseOptions {
+            component: config.trans.component.clone(),
+            i18n_key: config.trans.i18n_key.clone(),
+            ..Default::default()
+        };
+        let (trans_occurrences, trans_errors) = parse_trans_with_errors(&source, &options);
+        occurrences.extend(trans_occurrences);
+        errors.extend(trans_errors);
+        (occurrences, errors)
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, config: &ScanConfig) {
+        let layout = analyze(content, self.mdx, config.static_content.code_blocks);
+        linter.verify_text_ranges(content, filepath, &layout.text);
+
+        let skipped = [layout.code.as_slice(), layout.scripts.as_slice()].concat();
+        linter.verify_attributes(&mask_ranges(content, &skipped, false), filepath);
+
+        if self.mdx {
+            linter.verify(&mask_ranges(content, &layout.islands, true), filepath);
+        }
+        if config.static_content.scripts {
+            linter.verify(&mask_ranges(content, &layout.scripts, true), filepath);
+        }
+    }
+}
+
+/// 文档结构，范围均为原文件中的字节范围
+#[derive(Debug, Default)]
+struct Layout {
+    /// 需要检查的正文片段
+    text: Vec<Range<usize>>,
+    /// 代码块、行内代码、注释与 `<style>` 的内容
+    code: Vec<Range<usize>>,
+    /// `<script>` 的内容
+    scripts: Vec<Range<usize>>,
+    /// MDX 的 import/export 与 `{ }` 表达式
+    islands: Vec<Range<usize>>,
+}
+
+/// 划分正文、代码与 MDX 表达式；正文按行切分，并在标签、表格分隔符和链接地址处断开
+fn analyze(content: &str, mdx: bool, code_blocks: bool) -> Layout {
+    let block_marker = Regex::new(r"^[ \t]*(?:(?:#{1,6}|>|[-*+]|\d+[.)])[ \t]+)*").unwrap();
+    let mut layout = Layout::default();
+    let mut fence: Option<(char, usize)> = None;
+    let mut in_esm = false;
+    let mut piece: Option<usize> = None;
+    let mut line_start = true;
+    let mut i = 0;
+
+    macro_rules! close_piece {
+        ($end:expr) => {
+            if let Some(start) = piece.take() {
+                layout.text.push(start..$end);
+            }
+        };
+    }
+
+    while i < content.len() {
+        if line_start {
+            line_start = false;
+            let line_end = content[i..].find('\n').map_or(content.len(), |e| i + e);
+            let line = &content[i..line_end];
+
+            let skipped_line = if let Some((ch, len)) = fence {
+                if closes_fence(line, ch, len) {
+                    fence = None;
+                    layout.code.push(i..line_end);
+                } else if code_blocks {
+                    layout.text.push(i..line_end);
+                } else {
+                    layout.code.push(i..line_end);
+                }
+                true
+            } else if let Some(open) = opens_fence(line) {
+                fence = Some(open);
+                layout.code.push(i..line_end);
+                true
+            } else if mdx && (in_esm || line.starts_with("import ") || line.starts_with("export ")) {
+                // ESM 块持续到空行
+                in_esm = !line.trim().is_empty();
+                layout.islands.push(i..line_end);
+                true
+            } else {
+                i += block_marker.find(line).map_or(0, |m| m.end());
+                false
+            };
+            if skipped_line {
+                i = line_end;
+            }
+            continue;
+        }
+
+        let rest = &content[i..];
+        let ch = rest.chars().next().unwrap();
+        match ch {
+            '\n' => {
+                close_piece!(i);
+                i += 1;
+                line_start = true;
+            }
+            '\\' => {
+                piece.get_or_insert(i);
+                i += 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+            }
+            '|' => {
+                close_piece!(i);
+                i += 1;
+            }
+            '<' if rest.starts_with("<!--") => {
+                close_piece!(i);
+                let end = rest.find("-->").map_or(content.len(), |e| i + e + 3);
+                layout.code.push(i..end);
+                i = end;
+            }
+            '<' if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') => {
+                close_piece!(i);
+                let (len, expressions) = tag_len(rest);
+                if mdx {
+                    layout.islands.extend(expressions.into_iter().map(|r| i + r.start..i + r.end));
+                }
+                i += len;
+                // `<script>`、`<style>` 的内容不是正文，一直跳过到结束标签
+                if let Some(name) = raw_text_element(rest).filter(|_| !rest[..len].ends_with("/>")) {
+                    let end = find_ignore_case(&content[i..], &format!("</{}", name)).map_or(content.len(), |e| i + e);
+                    if name == "script" {
+                        layout.scripts.push(i..end);
+                    } else {
+                        layout.code.push(i..end);
+                    }
+                    i = end;
+                }
+            }
+            '`' => {
+                let ticks = rest.len() - rest.trim_start_matches('`').len();
+                match closing_ticks(&rest[ticks..], ticks) {
+                    Some(end) if !code_blocks => {
+                        close_piece!(i);
+                        layout.code.push(i..i + ticks + end);
+                        i += ticks + end;
+                    }
+                    Some(end) => {
+                        piece.get_or_insert(i);
+                        i += ticks + end;
+                    }
+                    None => {
+                        piece.get_or_insert(i);
+                        i += ticks;
+                    }
+                }
+            }
+            ']' if rest.starts_with("](") => {
+                close_piece!(i + 1);
+                i += 2 + link_destination_len(&rest[2..]);
+            }
+            '{' if mdx => {
+                close_piece!(i);
+                let len = balanced_len(rest, '{', '}');
+                layout.islands.push(i..i + len);
+                i += len;
+            }
+            _ => {
+                piece.get_or_insert(i);
+                i += ch.len_utf8();
+            }
+        }
+    }
+    close_piece!(content.len());
+
+    layout
+}
+
+/// 开始代码块的行（最多 3 个空格缩进的 ``` 或 ~~~），返回围栏字符与长度
+fn opens_fence(line: &str) -> Option<(char, usize)> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.len() - trimmed.trim_start_matches(ch).len();
+    (len >= 3).then_some((ch, len))
+}
+
+fn closes_fence(line: &str, ch: char, len: usize) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= len && trimmed.chars().all(|c| c == ch)
+}
+
+/// 行内代码结束位置：与开头数量相同的反引号之后
+fn closing_ticks(s: &str, ticks: usize) -> Option<usize> {
+    let mut i = 0;
+    while let Some(found) = s[i..].find('`') {
+        let start = i + found;
+        let run = s[start..].len() - s[start..].trim_start_matches('`').len();
+        if run == ticks {
+            return Some(start + run);
+        }
+        i = start + run;
+    }
+    None
+}
+
+/// 标签长度（含 `>`），跳过引号与 `{ }` 中的内容，同时返回其中 `{ }` 表达式的相对范围
+fn tag_len(s: &str) -> (usize, Vec<Range<usize>>) {
+    let mut expressions = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+    while i < s.len() {
+        let ch = s[i..].chars().next().unwrap();
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '{' => {
+                let len = balanced_len(&s[i..], '{', '}');
+                expressions.push(i..i + len);
+                i += len;
+                continue;
+            }
+            None if ch == '>' => return (i + 1, expressions),
+            None => {}
+        }
+        i += ch.len_utf8();
+    }
+    (s.len(), expressions)
+}
+
+/// 以 `<script` 或 `<style` 开头的开始标签，返回小写的标签名
+fn raw_text_element(tag: &str) -> Option<&'static str> {
+    ["script", "style"].into_iter().find(|name| {
+        tag.get(1..name.len() + 1).is_some_and(|s| s.eq_ignore_ascii_case(name))
+            && tag[name.len() + 1..].starts_with(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+    })
+}
+
+/// 忽略 ASCII 大小写查找子串
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// 从开括号到匹配的闭括号的长度（含两端），跳过字符串中的括号
+fn balanced_len(s: &str, open: char, close: char) -> usize {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, ch) in s.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        if ch == '"' || ch == '\'' || ch == '`' {
+            quote = Some(ch);
+        } else if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth = depth.saturating_sub(1);
+            if depth == 0 {
+                return i + ch.len_utf8();
+            }
+        }
+    }
+    s.len()
+}
+
+/// 链接地址 `(url "title")` 在 `(` 之后的长度，包括 `)`
+fn link_destination_len(s: &str) -> usize {
+    let mut depth = 1usize;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            '\n' => return i,
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+/// `inside` 为 true 时只保留范围内的字符，否则把范围内的字符替换为空格
+fn mask_ranges(content: &str, ranges: &[Range<usize>], inside: bool) -> String {
+    let mut covered = vec![false; content.len()];
+    for range in ranges {
+        covered[range.clone()].fill(true);
+    }
+    mask_with(content, |i| covered[i] == inside)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::config::StaticContentConfig;
+    use crate::scan::zh_linter::get_result;
+
+    fn lint_values(front_end: &MarkdownFrontEnd, content: &str, filepath: &str, config: &ScanConfig) -> Vec<(String, u32, u32)> {
+        let _guard = crate::scan::lock_scan();
+        front_end.lint(&ZhLinter::new(), content, filepath, config);
+        let mut values: Vec<_> = get_result()
+            .into_iter()
+            .filter(|r| r.filepath == filepath)
+            .map(|r| (r.value, r.loc.start.line, r.loc.start.column))
+            .collect();
+        values.sort_by_key(|(_, line, column)| (*line, *column));
+        values
+    }
+
+    const DOC: &str = r#"# 快速开始
+
+- 安装 `npm i 依赖` 后运行
+| 名称 | Name |
+[文档](https://example.com/中文 "链接") <img alt="截图" src="a.png">
+<!-- 注释 -->
+```js
+const a = '代码';
+```
+"#;
+
+    #[test]
+    fn test_markdown_text_nodes() {
+        let front_end = MarkdownFrontEnd { mdx: false };
+        let config = ScanConfig::default();
+        let values = lint_values(&front_end, DOC, "lint-test.md", &config);
+        let expected = [
+            ("快速开始", 1, 3),
+            ("安装", 3, 3),
+            ("后运行", 3, 17),
+            ("名称", 4, 3),
+            ("[文档]", 5, 1),
+            ("截图", 5, 45),
+        ];
+        let expected: Vec<_> = expected.iter().map(|(v, l, c)| (v.to_string(), *l, *c)).collect();
+        assert_eq!(values, expected);
+        assert!(front_end.extract(DOC, "lint-test.md", &config).is_empty());
+    }
+
+    #[test]
+    fn test_markdown_code_blocks_when_configured() {
+        let front_end = MarkdownFrontEnd { mdx: false };
+        let config = ScanConfig::default().with_static_content(StaticContentConfig {
+            code_blocks: true,
+            ..Default::default()
+        });
+        let values = lint_values(&front_end, DOC, "code-test.md", &config);
+        assert!(values.iter().any(|(value, line, _)| value == "安装 `npm i 依赖` 后运行" && *line == 3));
+        assert!(values.iter().any(|(value, line, _)| value == "const a = '代码';" && *line == 8));
+    }
+
+    const SCRIPT_DOC: &str = "# 标题\n\n<script>\nvar x = '脚本';\ndocument.title = i18n.t('doc.title');\n</script>\n<STYLE>\n.a::after { content: \"样式\"; }\n</STYLE>\n\n正文\n";
+
+    #[test]
+    fn test_markdown_scripts_skipped_by_default() {
+        let config = ScanConfig::default();
+        for (mdx, filepath) in [(false, "scripts-off.md"), (true, "scripts-off.mdx")] {
+            let front_end = MarkdownFrontEnd { mdx };
+            let values = lint_values(&front_end, SCRIPT_DOC, filepath, &config);
+            let values: Vec<_> = values.iter().map(|(v, _, _)| v.as_str()).collect();
+            assert_eq!(values, vec!["标题", "正文"]);
+            assert!(front_end.extract(SCRIPT_DOC, filepath, &config).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_markdown_scripts_when_configured() {
+        let config = ScanConfig::default().with_static_content(StaticContentConfig {
+            scripts: true,
+            ..Default::default()
+        });
+        let front_end = MarkdownFrontEnd { mdx: false };
+        let values = lint_values(&front_end, SCRIPT_DOC, "scripts-on.md", &config);
+        assert!(values.iter().any(|(value, line, _)| value.contains("脚本") && *line == 4));
+        assert!(!values.iter().any(|(value, _, _)| value.contains("样式")));
+
+        let occurrences = front_end.extract(SCRIPT_DOC, "scripts-on.md", &config);
+        let keys: Vec<_> = occurrences.iter().map(|o| (o.key.as_str(), o.line)).collect();
+        assert_eq!(keys, vec![("doc.title", 5)]);
+    }
+
+    #[test]
+    fn test_mdx_islands() {
+        let doc = "import { t } from 'i18next';\n\n# {t('title')} 标题\n\n<Note label={t('note')} title=\"提示\">正文</Note>\n\n```\nt('ignored')\n```\n";
+        let front_end = MarkdownFrontEnd { mdx: true };
+        let config = ScanConfig::default();
+        let keys: Vec<_> = front_end
+            .extract(doc, "doc.mdx", &config)
+            .into_iter()
+            .map(|o| (o.key, o.line, o.column))
+            .collect();
+        assert_eq!(keys, vec![("title".to_string(), 3, 6), ("note".to_string(), 5, 16)]);
+
+        let values = lint_values(&front_end, doc, "lint-test.mdx", &config);
+        let values: Vec<_> = values.iter().map(|(v, _, _)| v.as_str()).collect();
+        assert_eq!(values, vec!["标题", "提示", "正文"]);
+    }
+
+    #[test]
+    fn test_markdown_reports_parse_errors() {
+        let config = ScanConfig::default().with_static_content(StaticContentConfig {
+            scripts: true,
+            ..Default::default()
+        });
+        let md = "# 标题\n\n<script>\ni18n.t('a', { ns: 'x' );\n</script>\n";
+        let (_, errors) = MarkdownFrontEnd { mdx: false }.extract_with_errors(md, "a.md", &config);
+        let spans: Vec<_> = errors.iter().map(|e| (e.span.start_line, e.span.start_column)).collect();
+        assert_eq!(spans, vec![(4, 13)]);
+
+        let mdx = "# 标题\n\n{t('b', { ns: 'y' )}\n\n<Trans>你好 {user.name}</Trans>\n";
+        let (_, errors) = MarkdownFrontEnd { mdx: true }.extract_with_errors(mdx, "a.mdx", &ScanConfig::default());
+        let spans: Vec<_> = errors.iter().map(|e| (e.span.start_line, e.span.start_column)).collect();
+        assert_eq!(spans, vec![(3, 9), (5, 11)]);
+    }
+}
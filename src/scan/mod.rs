@@ -1,33 +1,149 @@
 //! Scanning module for i18n keys and translations
 
+pub mod angular;
+pub mod baseline;
+pub mod bindings;
 pub mod config;
+pub mod config_check;
+pub mod config_layers;
+pub mod context;
+pub mod diagnostic;
+pub mod diff;
+pub mod duplicate_key;
+pub mod front_end;
+pub mod globs;
 pub mod js_config;
+pub mod js_object;
 pub mod hash_key;
+pub mod html;
+pub mod key_strategy;
+pub mod markdown;
+pub mod namespace;
 pub mod nodes_to_string;
 pub mod parse_func_from_string_by_babel;
+pub mod plural;
+pub mod progress;
+pub mod reporter;
+pub mod parse_trans_from_string_by_babel;
 pub mod zh_linter;
 pub mod slp;
+pub mod span;
+pub mod svelte;
+pub mod vue;
+pub mod workspace;
+pub mod writer;
 
 // Re-export submodules
-pub use config::*;
-pub use hash_key::*;
-pub use nodes_to_string::*;
-pub use parse_func_from_string_by_babel::*;
-pub use zh_linter::*;
-pub use slp::*;
-
+// `Location` 与 `ParserOptions` 在 `nodes_to_string`、`parse_func_from_string_by_babel` 与 `zh_linter` 中同名，需通过模块路径使用
+pub use angular::{AngularFrontEnd, parse_translate_pipes};
+pub use baseline::{BASELINE_VERSION, Baseline, BaselineEntry, BaselineMatch, fingerprint};
+pub use bindings::{TranslationBinding, find_bindings, apply_bindings, binding_funcs};
+pub use config::{
+    BabelOptions, BabelPlugin, ResourceConfig, FuncConfig, TransConfig, StaticContentConfig,
+    DEFAULT_EXTENSIONS, extension_matches, ScanConfig, default_exclude, default_ignore_files,
+    get_default_config, config_builder,
+};
+pub use config_check::{ConfigDiagnostic, validate_config, config_schema};
+pub use config_layers::{ENV_PREFIX, ConfigSource, LayeredConfig, default_config_value};
+pub use context::{CONTEXT_SEPARATOR, resolve_context, strip_context};
+pub use diagnostic::{DiagnosticSink, CollectingSink};
+pub use diff::{
+    ScanDiff, DefaultValueChange, Finding, Severity, findings, diff_results, load_result, scan_tree,
+    diff_trees,
+};
+pub use duplicate_key::{
+    DuplicateKeyStrategy, KeyLocation, KeyConflict, MergedKeys, merge_occurrences,
+};
+pub use front_end::{
+    FrontEnd, FrontEndKind, DEFAULT_FRONT_ENDS, suffix_matches, longest_suffix_match,
+    ScriptFrontEnd,
+};
+pub use globs::{expand_braces, Glob, resolve_files};
+pub use js_object::{
+    parse_js_module, KeyLocations, locate_js_module, parse_js_value, locate_js_value,
+};
+pub use hash_key::{
+    HashCollisionStrategy, KeySource, HashCollision, KeyIndex, hash_key, hash_key_simple,
+    clear_key_map, get_key_map_size,
+};
+pub use html::HtmlFrontEnd;
+pub use key_strategy::{
+    KeyStrategy, LegacyHashKey, Hash64Key, SlugKey, KeyStrategyConfig, KeyPattern,
+};
+pub use markdown::MarkdownFrontEnd;
+pub use namespace::{split_namespace, qualify_key, unqualify_key, resolve_namespace};
+pub use nodes_to_string::{
+    AstNode, ObjectProperty, ObjectKey, TemplateElement, TemplateElementValue, nodes_to_string,
+    unsupported_expressions, nodes_to_string_simple,
+};
+pub use parse_func_from_string_by_babel::{
+    CustomHandler, PropsFilter, ParseOptions, BabylonOptions, FuncParseConfig, ParserOpts,
+    KeyOccurrence, Parser, Property, PropertyValue, func_pattern, match_balanced_parentheses,
+    create_parser, create_parser_with_options,
+};
+pub use plural::{
+    PluralCategory, PluralSuffixStyle, plural_categories, plural_suffixes, is_singular_suffix,
+};
+pub use progress::{ScanProgress, ProgressCallback, CancellationToken};
+pub use reporter::{
+    TOOL_NAME, Reporter, ReporterKind, TextReporter, JsonReporter, StylishReporter, SarifReporter,
+    CheckstyleReporter, JunitReporter, GithubReporter,
+};
+pub use parse_trans_from_string_by_babel::{
+    TransParseOptions, parse_trans_from_string_by_babel, parse_trans_with_errors,
+};
+pub use zh_linter::{
+    Position, LintResult, EslintConfig, EcmaFeatures, MessageId, EslintMessage, ZhLinter,
+    get_result, get_hard_code_suggestions, get_no_string_concatenations, clear_results,
+    create_linter, verify_code,
+};
+pub use slp::{
+    SourceRow, SisulizerProjectOptions, SisulizerProject, ProjectStats, create_sisulizer_project,
+    create_default_sisulizer_project,
+};
+pub use span::{Span, SourceError, code_frame};
+pub use svelte::{SVELTE_STORES, SvelteFrontEnd};
+pub use vue::{
+    SfcBlockKind, SfcBlock, split_sfc, mask_sfc, blank_sfc, VueFrontEnd, extract_sfc, extract_sfc_with_errors, lint_sfc,
+    parse_v_t_directives,
+};
+pub use workspace::{
+    CONFIG_FILE_NAMES, WorkspacePackage, discover_packages, rebase_config, PackageReport,
+    LanguageTotal, WorkspaceReport, scan_workspace,
+};
+pub use writer::{
+    ResourcePlan, plan_resources, write_resources, sync_resources, PrunedResource, prune_resources,
+    ResourceCoverage, resource_coverage, read_resource,
+};
+
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fs;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::{Error, Result};
 
-pub use config::ScanConfig;
-pub use hash_key::{hash_key, hash_key_simple};
-pub use zh_linter::{verify_code, get_result, get_hard_code_suggestions, get_no_string_concatenations};
 
 /// Scan result structure
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ScanResult {
     pub keys: Vec<String>,
     pub translations: HashMap<String, String>,
+    /// Extracted key -> key generated from its sentence (the default value, or the key itself)
+    #[serde(default)]
+    pub generated_keys: HashMap<String, String>,
+    /// Namespace -> keys in that namespace, written as they appear in `keys`
+    #[serde(default)]
+    pub namespaces: HashMap<String, Vec<String>>,
+    /// Key -> context, for keys extracted with a `context` option (the key carries a `_context` suffix)
+    #[serde(default)]
+    pub contexts: HashMap<String, String>,
+    /// Keys used with `count`, expanded into plural forms per language when writing resources
+    #[serde(default)]
+    pub plural_keys: Vec<String>,
+    /// Key -> `defaultValue_plural`
+    #[serde(default)]
+    pub plural_translations: HashMap<String, String>,
     pub errors: Vec<ScanError>,
     pub warnings: Vec<ScanWarning>,
     pub stats: ScanStats,
@@ -41,6 +157,9 @@ pub struct ScanError {
     pub column: u32,
     pub message: String,
     pub error_type: ErrorType,
+    /// 出错的源码范围，只有起始位置（如文件读取失败、key 冲突）时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
 /// Scan warning
@@ -51,6 +170,8 @@ pub struct ScanWarning {
     pub column: u32,
     pub message: String,
     pub warning_type: WarningType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
 }
 
 /// Error types
@@ -61,6 +182,14 @@ pub enum ErrorType {
     DuplicateKey,
     MissingTranslation,
     HardCodedText,
+    HashCollision,
+}
+
+impl ErrorType {
+    /// 是否为未翻译文本等检查结果，而不是提取词条时的错误
+    pub fn is_lint(&self) -> bool {
+        matches!(self, ErrorType::HardCodedText)
+    }
 }
 
 /// Warning types
@@ -69,6 +198,7 @@ pub enum WarningType {
     UnusedKey,
     StringConcatenation,
     HardCodedDomain,
+    UndeclaredNamespace,
 }
 
 /// Scan statistics
@@ -85,6 +215,26 @@ pub struct ScanStats {
 pub struct Scanner {
     config: ScanConfig,
     linter: zh_linter::ZhLinter,
+    /// 通过 `register_front_end` 注册的前端，优先于配置中的前端
+    front_ends: Vec<(String, Arc<dyn FrontEnd>)>,
+    /// 每次扫描结束后接收全部问题
+    sink: Option<Arc<dyn DiagnosticSink>>,
+    progress: Option<ProgressCallback>,
+    cancellation: Option<CancellationToken>,
+}
+
+/// 检查器的结果保存在全局状态中，同一时间只能进行一次扫描
+static SCAN_LOCK: Mutex<()> = Mutex::new(());
+
+/// 持有期间独占检查器的全局结果，直接调用 `FrontEnd::lint` 并读取结果的测试也需要持有
+pub(crate) fn lock_scan() -> MutexGuard<'static, ()> {
+    SCAN_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// 待扫描的源码，`content` 为空时读取 `filepath`
+struct Source<'a> {
+    filepath: &'a str,
+    content: Option<&'a str>,
 }
 
 impl Scanner {
@@ -93,6 +243,10 @@ impl Scanner {
         Self {
             config: ScanConfig::default(),
             linter: zh_linter::ZhLinter::new(),
+            front_ends: Vec::new(),
+            sink: None,
+            progress: None,
+            cancellation: None,
         }
     }
 
@@ -101,6 +255,43 @@ impl Scanner {
         Self {
             config,
             linter: zh_linter::ZhLinter::new(),
+            front_ends: Vec::new(),
+            sink: None,
+            progress: None,
+            cancellation: None,
+        }
+    }
+
+    /// 为文件名后缀注册自定义语言前端，如 `register_front_end("astro", Box::new(AstroFrontEnd))`
+    pub fn register_front_end(&mut self, suffix: &str, front_end: Box<dyn FrontEnd>) {
+        self.front_ends
+            .push((suffix.trim_start_matches('.').to_string(), Arc::from(front_end)));
+    }
+
+    /// 设置接收扫描问题的 sink，问题同时仍保留在 `ScanResult` 中
+    pub fn set_diagnostic_sink(&mut self, sink: Arc<dyn DiagnosticSink>) {
+        self.sink = Some(sink);
+    }
+
+    /// 设置进度回调，每扫描完一个文件调用一次
+    pub fn set_progress_callback(&mut self, progress: ProgressCallback) {
+        self.progress = Some(progress);
+    }
+
+    /// 设置取消标记，取消后 `scan_files` 返回 `Error::Cancelled`
+    pub fn set_cancellation_token(&mut self, cancellation: CancellationToken) {
+        self.cancellation = Some(cancellation);
+    }
+
+    /// 文件使用的语言前端，没有匹配的前端时不扫描该文件
+    fn front_end_for(&self, filepath: &str) -> Option<Arc<dyn FrontEnd>> {
+        let registered = self
+            .front_ends
+            .iter()
+            .map(|(suffix, front_end)| (suffix.as_str(), front_end));
+        match longest_suffix_match(registered, filepath) {
+            Some(front_end) => Some(front_end.clone()),
+            None => self.config.front_end_for(filepath).map(|kind| Arc::from(kind.build())),
         }
     }
 
@@ -110,23 +301,87 @@ impl Scanner {
     }
 
     /// Scan multiple files
-    pub fn scan_files(&mut self, files: &[String]) -> Result<ScanResult, Box<dyn std::error::Error>> {
+    pub fn scan_files(&mut self, files: &[String]) -> Result<ScanResult> {
+        let sources: Vec<Source> = files
+            .iter()
+            .map(|filepath| Source {
+                filepath,
+                content: None,
+            })
+            .collect();
+        self.scan(&sources)
+    }
+
+    /// 扫描内存中的源码（如打包工具转换后的模块、编辑器中未保存的内容），不读取文件
+    ///
+    /// `path_hint` 只用于选择语言前端和报告位置；没有匹配的前端时结果为空。
+    pub fn scan_source(&mut self, path_hint: &str, content: &str) -> Result<ScanResult> {
+        self.scan(&[Source {
+            filepath: path_hint,
+            content: Some(content),
+        }])
+    }
+
+    /// 一次扫描多份内存中的源码，每项为 `(path_hint, content)`
+    pub fn scan_sources(&mut self, sources: &[(String, String)]) -> Result<ScanResult> {
+        let sources: Vec<Source> = sources
+            .iter()
+            .map(|(path_hint, content)| Source {
+                filepath: path_hint,
+                content: Some(content),
+            })
+            .collect();
+        self.scan(&sources)
+    }
+
+    /// 检查内存中的源码，得到与 `lint` 子命令相同的问题，参数同 `scan_source`
+    pub fn lint_source(&mut self, filepath: &str, content: &str) -> Result<Vec<Finding>> {
+        let result = self.scan_source(filepath, content)?;
+        Ok(diff::findings(&result))
+    }
+
+    fn scan(&mut self, sources: &[Source]) -> Result<ScanResult> {
         let start_time = std::time::Instant::now();
-        let mut keys = Vec::new();
-        let mut translations = HashMap::new();
+        let mut locations = Vec::new();
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
 
+        let _guard = lock_scan();
         // Clear previous results
         zh_linter::clear_results();
 
-        for filepath in files {
-            match self.scan_single_file(filepath) {
-                Ok(file_result) => {
-                    keys.extend(file_result.keys);
-                    translations.extend(file_result.translations);
-                    errors.extend(file_result.errors);
-                    warnings.extend(file_result.warnings);
+        let targets: Vec<(&Source, Arc<dyn FrontEnd>)> = sources
+            .iter()
+            .filter_map(|source| Some((source, self.front_end_for(source.filepath)?)))
+            .collect();
+        let files_scanned = targets.len();
+        for (index, (source, front_end)) in targets.into_iter().enumerate() {
+            if self.cancellation.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                return Err(Error::Cancelled);
+            }
+            let filepath = source.filepath.to_string();
+            let scanned = self.scan_single_file(source, front_end.as_ref());
+            if let Some(progress) = &self.progress {
+                progress(&ScanProgress {
+                    scanned: index + 1,
+                    total: files_scanned,
+                    filepath: filepath.clone(),
+                });
+            }
+            match scanned {
+                Ok((occurrences, source_errors)) => {
+                    locations.extend(occurrences.into_iter().map(|occurrence| KeyLocation {
+                        filepath: filepath.clone(),
+                        occurrence,
+                    }));
+                    errors.extend(source_errors.into_iter().map(|error| ScanError {
+                        filepath: filepath.clone(),
+                        line: error.span.start_line,
+                        column: error.span.start_column,
+                        message: error.message,
+                        error_type: ErrorType::ParseError,
+                        span: Some(error.span),
+                    }));
                 }
                 Err(e) => {
                     errors.push(ScanError {
@@ -135,11 +390,81 @@ impl Scanner {
                         column: 1,
                         message: e.to_string(),
                         error_type: ErrorType::ParseError,
+                        span: None,
                     });
                 }
             }
         }
 
+        // Merge keys across files and report conflicting default values
+        let merged = duplicate_key::merge_occurrences(&locations, self.config.duplicate_keys);
+        for conflict in &merged.conflicts {
+            let first = &conflict.locations[0];
+            errors.push(ScanError {
+                filepath: first.filepath.clone(),
+                line: first.occurrence.line,
+                column: first.occurrence.column,
+                message: conflict.message(),
+                error_type: ErrorType::DuplicateKey,
+                span: None,
+            });
+        }
+
+        // Generate keys from sentences and report true hash collisions (per namespace)
+        let mut first_locations: HashMap<&str, &KeyLocation> = HashMap::new();
+        for location in &locations {
+            first_locations.entry(location.occurrence.key.as_str()).or_insert(location);
+        }
+        let mut key_indexes: HashMap<String, KeyIndex> = HashMap::new();
+        let mut generated_keys = HashMap::new();
+        let mut namespaces: HashMap<String, Vec<String>> = HashMap::new();
+        let mut contexts = HashMap::new();
+        for key in &merged.keys {
+            let first = first_locations[key.as_str()];
+            let ns = first.occurrence.options.ns.as_deref().unwrap_or(&self.config.default_ns);
+            let context = first.occurrence.options.context.as_deref();
+            let sentence = match merged.translations.get(key) {
+                Some(translation) => translation.as_str(),
+                None => context::strip_context(namespace::unqualify_key(&self.config, ns, key), context),
+            };
+            let position = format!("{}:{}:{}", first.filepath, first.occurrence.line, first.occurrence.column);
+            let key_index = key_indexes.entry(ns.to_string()).or_insert_with(|| {
+                KeyIndex::new(self.config.hash_collisions).with_key_strategy(self.config.key_strategy.build())
+            });
+            let (generated_key, collision) = key_index.insert(sentence, context, Some(&position));
+            if let Some(collision) = collision {
+                errors.push(ScanError {
+                    filepath: first.filepath.clone(),
+                    line: first.occurrence.line,
+                    column: first.occurrence.column,
+                    message: collision.message(),
+                    error_type: ErrorType::HashCollision,
+                    span: None,
+                });
+            }
+            generated_keys.insert(key.clone(), generated_key);
+            if let Some(context) = context {
+                contexts.insert(key.clone(), context.to_string());
+            }
+
+            if !self.config.is_declared_namespace(ns) {
+                warnings.push(ScanWarning {
+                    filepath: first.filepath.clone(),
+                    line: first.occurrence.line,
+                    column: first.occurrence.column,
+                    message: format!(
+                        "Key \"{}\" uses undeclared namespace \"{}\" (declared: {})",
+                        key,
+                        ns,
+                        self.config.ns.join(", ")
+                    ),
+                    warning_type: WarningType::UndeclaredNamespace,
+                    span: None,
+                });
+            }
+            namespaces.entry(ns.to_string()).or_default().push(key.clone());
+        }
+
         // Collect linter results
         let lint_results = get_result();
         for result in lint_results {
@@ -149,6 +474,7 @@ impl Scanner {
                 column: result.loc.start.column,
                 message: format!("Hard-coded Chinese text found: {}", result.value),
                 error_type: ErrorType::HardCodedText,
+                span: Some(Span::from(&result.loc)),
             });
         }
 
@@ -160,6 +486,7 @@ impl Scanner {
                 column: suggestion.loc.start.column,
                 message: format!("Hard-coded domain found: {}", suggestion.value),
                 warning_type: WarningType::HardCodedDomain,
+                span: Some(Span::from(&suggestion.loc)),
             });
         }
 
@@ -171,72 +498,67 @@ impl Scanner {
                 column: concat.loc.start.column,
                 message: format!("String concatenation found: {}", concat.value),
                 warning_type: WarningType::StringConcatenation,
+                span: Some(Span::from(&concat.loc)),
             });
         }
 
         let processing_time = start_time.elapsed().as_millis() as u64;
 
-        let unique_keys: Vec<String> = keys.into_iter().collect::<std::collections::HashSet<_>>().into_iter().collect();
-        let keys_count = unique_keys.len();
+        let keys_count = merged.keys.len();
         let errors_count = errors.len();
         let warnings_count = warnings.len();
 
-        Ok(ScanResult {
-            keys: unique_keys,
-            translations,
+        let result = ScanResult {
+            keys: merged.keys,
+            translations: merged.translations,
+            generated_keys,
+            namespaces,
+            contexts,
+            plural_keys: merged.plural_keys,
+            plural_translations: merged.plural_translations,
             errors,
             warnings,
             stats: ScanStats {
-                files_scanned: files.len(),
+                files_scanned,
                 keys_found: keys_count,
                 errors_count,
                 warnings_count,
                 processing_time_ms: processing_time,
             },
-        })
-    }
-
-    /// Scan a single file
-    fn scan_single_file(&mut self, filepath: &str) -> Result<ScanResult, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(filepath)?;
-        
-        // Run linter
-        self.linter.verify(&content, filepath);
-
-        // Parse functions from content
-        let mut parser = parse_func_from_string_by_babel::Parser::new();
-        parser.parse_func_from_string_by_babel(
-            &content,
-            parse_func_from_string_by_babel::ParserOpts::default(),
-            None,
-            None,
-        );
-
-        // Extract keys and translations
-        let mut keys = Vec::new();
-        let mut translations = HashMap::new();
-
-        for (key, options) in &parser.translations {
-            keys.push(key.clone());
-            if let Some(default_value) = &options.default_value {
-                translations.insert(key.clone(), default_value.clone());
+        };
+        if let Some(sink) = &self.sink {
+            for finding in diff::findings(&result) {
+                sink.report(&finding);
             }
         }
+        Ok(result)
+    }
 
-        let keys_count = keys.len();
-        Ok(ScanResult {
-            keys,
-            translations,
-            errors: Vec::new(),
-            warnings: Vec::new(),
-            stats: ScanStats {
-                files_scanned: 1,
-                keys_found: keys_count,
-                errors_count: 0,
-                warnings_count: 0,
-                processing_time_ms: 0,
-            },
-        })
+    /// Scan a single file, returning every translation call in source order
+    fn scan_single_file(
+        &mut self,
+        source: &Source,
+        front_end: &dyn FrontEnd,
+    ) -> Result<(Vec<KeyOccurrence>, Vec<SourceError>)> {
+        let content = match source.content {
+            Some(content) => Cow::Borrowed(content),
+            None => Cow::Owned(fs::read_to_string(source.filepath).map_err(|e| Error::file(source.filepath, e))?),
+        };
+
+        front_end.lint(&self.linter, &content, source.filepath, &self.config);
+        let (occurrences, errors) = front_end.extract_with_errors(&content, source.filepath, &self.config);
+
+        Ok((self.finish_occurrences(occurrences), errors))
+    }
+
+    /// 按位置排序，并解析命名空间与 context
+    fn finish_occurrences(&self, mut occurrences: Vec<KeyOccurrence>) -> Vec<KeyOccurrence> {
+        occurrences.sort_by_key(|occurrence| (occurrence.line, occurrence.column));
+        for occurrence in &mut occurrences {
+            namespace::resolve_namespace(&self.config, occurrence);
+            context::resolve_context(occurrence);
+        }
+        occurrences
     }
 
     /// Get current configuration
@@ -280,4 +602,296 @@ mod tests {
         assert_eq!(result.stats.files_scanned, 0);
         assert_eq!(result.keys.len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_scan_reports_conflicting_default_values() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.tsx");
+        let b = temp_dir.path().join("b.tsx");
+        fs::write(&a, "t('save', 'Save');").unwrap();
+        fs::write(&b, "\nt('save', 'Store');").unwrap();
+        let files = vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+
+        let mut scanner = Scanner::new();
+        let result = scanner.scan_files(&files).unwrap();
+        let duplicates: Vec<_> = result
+            .errors
+            .iter()
+            .filter(|e| matches!(e.error_type, ErrorType::DuplicateKey))
+            .collect();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].filepath, files[0]);
+        assert!(duplicates[0].message.contains(&format!("{}:2:3", files[1])));
+        assert_eq!(result.translations.get("save"), Some(&"Save".to_string()));
+
+        let mut scanner = Scanner::with_config(ScanConfig::default().with_duplicate_keys(DuplicateKeyStrategy::Last));
+        let result = scanner.scan_files(&files).unwrap();
+        assert!(!result.errors.iter().any(|e| matches!(e.error_type, ErrorType::DuplicateKey)));
+        assert_eq!(result.translations.get("save"), Some(&"Store".to_string()));
+    }
+
+    #[test]
+    fn test_scan_reports_parse_errors_with_span() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.tsx");
+        fs::write(&a, "t('save', { ns: 'common' );\n<Trans>你好 {user.name}</Trans>;").unwrap();
+        let files = vec![a.to_string_lossy().to_string()];
+
+        let result = Scanner::new().scan_files(&files).unwrap();
+        let parse_errors: Vec<_> = result
+            .errors
+            .iter()
+            .filter(|e| matches!(e.error_type, ErrorType::ParseError))
+            .map(|e| (e.line, e.column, e.span))
+            .collect();
+        assert_eq!(
+            parse_errors,
+            vec![(1, 11, Some(Span::new(1, 11, 1, 12))), (2, 11, Some(Span::new(2, 11, 2, 22)))]
+        );
+        assert!(result.keys.contains(&"save".to_string()));
+    }
+
+    #[test]
+    fn test_lint_source_in_memory() {
+        let mut scanner = Scanner::new();
+        let content = "t('save');\nalert('保存失败');\n";
+        let findings = scanner.lint_source("/no/such/dir/a.js", content).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "HardCodedText");
+        assert_eq!((findings[0].filepath.as_str(), findings[0].line), ("/no/such/dir/a.js", 2));
+
+        // 没有匹配的前端时不检查
+        assert!(scanner.lint_source("a.css", content).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_scan_sources_in_memory() {
+        let mut scanner = Scanner::new();
+        let result = scanner
+            .scan_source("/no/such/dir/Button.vue", "<template><p>{{ $t('save') }}</p></template>")
+            .unwrap();
+        assert_eq!(result.stats.files_scanned, 1);
+        assert_eq!(result.keys, vec!["save"]);
+
+        let sources = vec![
+            ("src/a.js".to_string(), "t('cancel');\nalert('保存失败');\n".to_string()),
+            ("src/style.css".to_string(), "a { color: red; }".to_string()),
+            ("src/b.ts".to_string(), "t('ok');".to_string()),
+        ];
+        let result = scanner.scan_sources(&sources).unwrap();
+        assert_eq!(result.stats.files_scanned, 2);
+        assert_eq!(result.keys, vec!["cancel", "ok"]);
+        let findings = diff::findings(&result);
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].filepath.as_str(), findings[0].line), ("src/a.js", 2));
+    }
+
+    #[test]
+    fn test_scan_reports_progress() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.js");
+        let b = temp_dir.path().join("b.js");
+        fs::write(&a, "t('a');").unwrap();
+        fs::write(&b, "t('b');").unwrap();
+        let files = vec![
+            a.to_string_lossy().to_string(),
+            temp_dir.path().join("style.css").to_string_lossy().to_string(),
+            b.to_string_lossy().to_string(),
+        ];
+
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let mut scanner = Scanner::new();
+        let sink = reported.clone();
+        scanner.set_progress_callback(Arc::new(move |progress: &ScanProgress| {
+            sink.lock().unwrap().push((progress.scanned, progress.total, progress.filepath.clone()));
+        }));
+        scanner.scan_files(&files).unwrap();
+
+        assert_eq!(
+            *reported.lock().unwrap(),
+            vec![(1, 2, files[0].clone()), (2, 2, files[2].clone())]
+        );
+    }
+
+    #[test]
+    fn test_scan_cancelled() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.js");
+        let b = temp_dir.path().join("b.js");
+        fs::write(&a, "t('a');").unwrap();
+        fs::write(&b, "t('b');").unwrap();
+        let files = vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+
+        // 扫描完第一个文件后取消
+        let token = CancellationToken::new();
+        let mut scanner = Scanner::new();
+        let cancel = token.clone();
+        scanner.set_progress_callback(Arc::new(move |_: &ScanProgress| cancel.cancel()));
+        scanner.set_cancellation_token(token);
+        assert!(matches!(scanner.scan_files(&files), Err(Error::Cancelled)));
+    }
+
+    #[test]
+    fn test_scan_reports_to_diagnostic_sink() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.tsx");
+        fs::write(&a, "t('save', { ns: 'common' );").unwrap();
+        let files = vec![a.to_string_lossy().to_string()];
+
+        let sink = Arc::new(CollectingSink::new());
+        let mut scanner = Scanner::new();
+        scanner.set_diagnostic_sink(sink.clone());
+        let result = scanner.scan_files(&files).unwrap();
+
+        let findings = sink.take();
+        assert_eq!(findings.len(), result.errors.len() + result.warnings.len());
+        assert!(findings.iter().any(|f| f.kind == "ParseError" && f.span == Some(Span::new(1, 11, 1, 12))));
+    }
+
+    #[test]
+    fn test_scan_reports_hash_collisions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let a = temp_dir.path().join("a.tsx");
+        let b = temp_dir.path().join("b.tsx");
+        // "Aa" 与 "BB" 的哈希值相同
+        fs::write(&a, "t('Aa');").unwrap();
+        fs::write(&b, "t('BB');").unwrap();
+        let files = vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()];
+
+        let mut scanner = Scanner::new();
+        let result = scanner.scan_files(&files).unwrap();
+        let collisions: Vec<_> = result
+            .errors
+            .iter()
+            .filter(|e| matches!(e.error_type, ErrorType::HashCollision))
+            .collect();
+        assert_eq!(collisions.len(), 1);
+        assert_eq!(collisions[0].filepath, files[1]);
+        assert!(collisions[0].message.contains(&format!("{}:1:3", files[0])));
+        assert_eq!(result.generated_keys["Aa"], result.generated_keys["BB"]);
+
+        let config = ScanConfig::default().with_hash_collisions(HashCollisionStrategy::Disambiguate);
+        let mut scanner = Scanner::with_config(config);
+        let result = scanner.scan_files(&files).unwrap();
+        assert_ne!(result.generated_keys["Aa"], result.generated_keys["BB"]);
+    }
+
+    #[test]
+    fn test_scan_honors_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.tsx");
+        let readme = temp_dir.path().join("notes.txt");
+        fs::write(&app, "__('保存');\nt('忽略');\n<I18n i18nKey=\"title\">标题</I18n>").unwrap();
+        fs::write(&readme, "__('文档');").unwrap();
+        let files = vec![app.to_string_lossy().to_string(), readme.to_string_lossy().to_string()];
+
+        let config = ScanConfig::default()
+            .with_func(FuncConfig {
+                list: vec!["__".to_string()],
+                ..Default::default()
+            })
+            .with_trans(TransConfig {
+                component: "I18n".to_string(),
+                ..Default::default()
+            });
+        let mut scanner = Scanner::with_config(config);
+        let result = scanner.scan_files(&files).unwrap();
+
+        assert_eq!(result.keys, vec!["保存", "title"]);
+        assert_eq!(result.translations.get("title"), Some(&"标题".to_string()));
+        assert_eq!(result.stats.files_scanned, 1);
+    }
+
+    #[test]
+    fn test_scan_groups_keys_by_namespace() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.tsx");
+        fs::write(&app, "t('common:save');\nt('save', { ns: 'errors' });\nt('保存');\nt('Error: failed');").unwrap();
+        let files = vec![app.to_string_lossy().to_string()];
+
+        let config = ScanConfig::default().with_namespaces(vec!["translation".to_string(), "common".to_string()]);
+        let mut scanner = Scanner::with_config(config);
+        let result = scanner.scan_files(&files).unwrap();
+
+        assert_eq!(result.keys, vec!["common:save", "errors:save", "保存", "Error: failed"]);
+        assert_eq!(result.namespaces["common"], vec!["common:save"]);
+        assert_eq!(result.namespaces["translation"], vec!["保存", "Error: failed"]);
+        // 不同命名空间中的同名 key 由相同的句子生成 key
+        assert_eq!(result.generated_keys["common:save"], result.generated_keys["errors:save"]);
+
+        let undeclared: Vec<_> = result
+            .warnings
+            .iter()
+            .filter(|w| matches!(w.warning_type, WarningType::UndeclaredNamespace))
+            .collect();
+        assert_eq!(undeclared.len(), 1);
+        assert_eq!(undeclared[0].line, 2);
+        assert!(undeclared[0].message.contains("\"errors\""));
+    }
+
+    #[test]
+    fn test_scan_collects_plural_keys() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.tsx");
+        fs::write(
+            &app,
+            "t('共{{count}}项', { count });\nt('file', { count: n, defaultValue: '{{count}} file', defaultValue_plural: '{{count}} files' });\nt('保存');",
+        )
+        .unwrap();
+
+        let mut scanner = Scanner::new();
+        let result = scanner.scan_files(&[app.to_string_lossy().to_string()]).unwrap();
+        assert_eq!(result.plural_keys, vec!["共{{count}}项", "file"]);
+        assert_eq!(result.plural_translations.get("file"), Some(&"{{count}} files".to_string()));
+    }
+
+    #[test]
+    fn test_scan_keeps_context_variants_apart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.tsx");
+        fs::write(&app, "t('打开', { context: 'verb' });\nt('打开', { context: 'status' });\nt('打开');").unwrap();
+
+        let mut scanner = Scanner::new();
+        let result = scanner.scan_files(&[app.to_string_lossy().to_string()]).unwrap();
+
+        assert_eq!(result.keys, vec!["打开_verb", "打开_status", "打开"]);
+        assert!(!result.errors.iter().any(|e| matches!(e.error_type, ErrorType::DuplicateKey)));
+        assert_eq!(result.contexts.get("打开_verb"), Some(&"verb".to_string()));
+        assert_eq!(result.generated_keys["打开_verb"], format!("{}_verb", result.generated_keys["打开"]));
+        assert_eq!(result.generated_keys["打开_status"], format!("{}_status", result.generated_keys["打开"]));
+    }
+
+    #[test]
+    fn test_scan_resolves_hook_namespace() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.tsx");
+        fs::write(
+            &app,
+            "export function App() {\n  const { t } = useTranslation('common');\n  return t('save');\n}\n",
+        )
+        .unwrap();
+
+        let config = ScanConfig::default().with_namespaces(vec!["translation".to_string(), "common".to_string()]);
+        let mut scanner = Scanner::with_config(config);
+        let result = scanner.scan_files(&[app.to_string_lossy().to_string()]).unwrap();
+        assert_eq!(result.keys, vec!["common:save"]);
+    }
+
+    #[test]
+    fn test_scan_vue_single_file_component() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("App.vue");
+        fs::write(
+            &app,
+            "<template>\n  <h1>{{ $t('title') }}</h1>\n  <p>未翻译</p>\n</template>\n<script setup>\nconst { t } = useI18n();\nt('script');\n</script>\n",
+        )
+        .unwrap();
+        let filepath = app.to_string_lossy().to_string();
+
+        let mut scanner = Scanner::new();
+        let result = scanner.scan_files(std::slice::from_ref(&filepath)).unwrap();
+        assert_eq!(result.keys, vec!["title", "script"]);
+        let hard_coded = result.errors.iter().find(|e| e.filepath == filepath).unwrap();
+        assert_eq!((hard_coded.line, hard_coded.column), (3, 6));
+    }
+}
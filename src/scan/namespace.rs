@@ -0,0 +1,114 @@
+use super::config::ScanConfig;
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+
+/// 拆分 key 中的命名空间前缀，如 `common:save` → `(Some("common"), "save")`
+///
+/// 只有前缀是由字母、数字、`_`、`-`、`.` 组成的名称，且分隔符后紧跟非空白内容时才拆分，
+/// 避免把 `t('Error: failed')` 之类的句子误当作命名空间。
+pub fn split_namespace<'a>(key: &'a str, ns_separator: &str) -> (Option<&'a str>, &'a str) {
+    if ns_separator.is_empty() {
+        return (None, key);
+    }
+    let (ns, rest) = match key.split_once(ns_separator) {
+        Some(parts) => parts,
+        None => return (None, key),
+    };
+    let is_name = !ns.is_empty()
+        && ns
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+    if !is_name || rest.is_empty() || rest.starts_with(char::is_whitespace) {
+        return (None, key);
+    }
+    (Some(ns), rest)
+}
+
+/// 带命名空间的 key 在扫描结果中的写法：默认命名空间不加前缀，其余为 `ns:key`
+pub fn qualify_key(config: &ScanConfig, ns: &str, key: &str) -> String {
+    if ns == config.default_ns {
+        key.to_string()
+    } else {
+        format!("{}{}{}", ns, config.ns_separator, key)
+    }
+}
+
+/// 去掉 `qualify_key` 添加的命名空间前缀
+pub fn unqualify_key<'a>(config: &ScanConfig, ns: &str, key: &'a str) -> &'a str {
+    if ns == config.default_ns {
+        return key;
+    }
+    key.strip_prefix(ns)
+        .and_then(|rest| rest.strip_prefix(config.ns_separator.as_str()))
+        .unwrap_or(key)
+}
+
+/// 解析一次调用的命名空间，并把 key 改写为扫描结果中的写法
+///
+/// 与 i18next 一致，优先级为：key 中的 `ns:` 前缀 > `{ ns }` 选项 > `default_ns`。
+/// 调用中的 `nsSeparator` 选项会覆盖配置中的分隔符，为空时不解析前缀。
+pub fn resolve_namespace(config: &ScanConfig, occurrence: &mut KeyOccurrence) {
+    let separator = occurrence
+        .options
+        .ns_separator
+        .clone()
+        .unwrap_or_else(|| config.ns_separator.clone());
+    let (prefix, key) = split_namespace(&occurrence.key, &separator);
+    let ns = prefix
+        .map(|p| p.to_string())
+        .or_else(|| occurrence.options.ns.clone().filter(|ns| !ns.is_empty()))
+        .unwrap_or_else(|| config.default_ns.clone());
+
+    occurrence.key = qualify_key(config, &ns, key);
+    occurrence.options.ns = Some(ns);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::parse_func_from_string_by_babel::ParseOptions;
+
+    fn occurrence(key: &str, ns: Option<&str>) -> KeyOccurrence {
+        KeyOccurrence {
+            key: key.to_string(),
+            options: ParseOptions {
+                ns: ns.map(|s| s.to_string()),
+                ..Default::default()
+            },
+            line: 1,
+            column: 1,
+        }
+    }
+
+    #[test]
+    fn test_split_namespace() {
+        assert_eq!(split_namespace("common:save", ":"), (Some("common"), "save"));
+        assert_eq!(split_namespace("Error: failed", ":"), (None, "Error: failed"));
+        assert_eq!(split_namespace("提示:保存", ":"), (None, "提示:保存"));
+        assert_eq!(split_namespace("common:save", ""), (None, "common:save"));
+        assert_eq!(split_namespace("common::save", "::"), (Some("common"), "save"));
+    }
+
+    #[test]
+    fn test_resolve_namespace() {
+        let config = ScanConfig::default();
+
+        let mut prefixed = occurrence("common:save", None);
+        resolve_namespace(&config, &mut prefixed);
+        assert_eq!(prefixed.key, "common:save");
+        assert_eq!(prefixed.options.ns.as_deref(), Some("common"));
+
+        let mut option = occurrence("save", Some("errors"));
+        resolve_namespace(&config, &mut option);
+        assert_eq!(option.key, "errors:save");
+
+        let mut both = occurrence("common:save", Some("errors"));
+        resolve_namespace(&config, &mut both);
+        assert_eq!(both.key, "common:save");
+
+        let mut default = occurrence("translation:save", None);
+        resolve_namespace(&config, &mut default);
+        assert_eq!(default.key, "save");
+        assert_eq!(unqualify_key(&config, "errors", "errors:save"), "save");
+        assert_eq!(unqualify_key(&config, "translation", "errors:save"), "errors:save");
+    }
+}
@@ -1,34 +1,48 @@
-use colored::*;
 use regex::Regex;
 
-/// 错误回调函数类型
-pub type OnErrorCallback = Box<dyn Fn() + Send + Sync>;
+use super::diagnostic::{error_at, DiagnosticSink};
+use super::span::Span;
+
+/// 无法转换的 JSX 表达式的提示
+const UNSUPPORTED_EXPRESSION: &str =
+    "Unsupported JSX expression. Only static values or {{interpolation}} blocks are supported.";
 
 /// AST 节点类型枚举
+///
+/// `span` 为节点在源码中的范围，手动构造时为默认值，可用 `with_span` 设置。
 #[derive(Debug, Clone)]
 pub enum AstNode {
     JSXText {
         value: String,
+        span: Span,
     },
     StringLiteral {
         value: String,
+        span: Span,
     },
     JSXExpressionContainer {
         expression: Box<AstNode>,
+        span: Span,
     },
     NumericLiteral {
         value: f64,
+        span: Span,
     },
     ObjectExpression {
         properties: Vec<ObjectProperty>,
+        span: Span,
     },
     TemplateLiteral {
         quasis: Vec<TemplateElement>,
+        span: Span,
     },
     JSXElement {
         children: Vec<AstNode>,
+        span: Span,
+    },
+    Other {
+        span: Span,
     },
-    Other,
 }
 
 /// 对象属性
@@ -64,10 +78,46 @@ pub struct Location {
 }
 
 impl AstNode {
-    /// 获取节点的位置信息
+    /// 获取节点在源码中的范围
+    pub fn span(&self) -> Span {
+        match self {
+            AstNode::JSXText { span, .. }
+            | AstNode::StringLiteral { span, .. }
+            | AstNode::JSXExpressionContainer { span, .. }
+            | AstNode::NumericLiteral { span, .. }
+            | AstNode::ObjectExpression { span, .. }
+            | AstNode::TemplateLiteral { span, .. }
+            | AstNode::JSXElement { span, .. }
+            | AstNode::Other { span } => *span,
+        }
+    }
+
+    /// 设置节点在源码中的范围
+    pub fn with_span(mut self, value: Span) -> Self {
+        match &mut self {
+            AstNode::JSXText { span, .. }
+            | AstNode::StringLiteral { span, .. }
+            | AstNode::JSXExpressionContainer { span, .. }
+            | AstNode::NumericLiteral { span, .. }
+            | AstNode::ObjectExpression { span, .. }
+            | AstNode::TemplateLiteral { span, .. }
+            | AstNode::JSXElement { span, .. }
+            | AstNode::Other { span } => *span = value,
+        }
+        self
+    }
+
+    /// 获取节点的起始位置，未设置范围时为 1:1
     pub fn get_location(&self) -> Location {
-        // 默认位置，实际使用时应该从解析器获取真实位置
-        Location { line: 1, column: 1 }
+        let span = self.span();
+        if span.is_unknown() {
+            Location { line: 1, column: 1 }
+        } else {
+            Location {
+                line: span.start_line,
+                column: span.start_column,
+            }
+        }
     }
 
     /// 检查是否为 JSXText 节点
@@ -103,7 +153,7 @@ impl AstNode {
     /// 获取节点的值（如果是文本或字符串字面量）
     pub fn get_value(&self) -> Option<&String> {
         match self {
-            AstNode::JSXText { value } | AstNode::StringLiteral { value } => Some(value),
+            AstNode::JSXText { value, .. } | AstNode::StringLiteral { value, .. } => Some(value),
             _ => None,
         }
     }
@@ -111,7 +161,7 @@ impl AstNode {
     /// 获取表达式（如果是 JSXExpressionContainer）
     pub fn get_expression(&self) -> Option<&AstNode> {
         match self {
-            AstNode::JSXExpressionContainer { expression } => Some(expression),
+            AstNode::JSXExpressionContainer { expression, .. } => Some(expression),
             _ => None,
         }
     }
@@ -119,7 +169,7 @@ impl AstNode {
     /// 获取子节点（如果是 JSXElement）
     pub fn get_children(&self) -> Option<&Vec<AstNode>> {
         match self {
-            AstNode::JSXElement { children } => Some(children),
+            AstNode::JSXElement { children, .. } => Some(children),
             _ => None,
         }
     }
@@ -127,7 +177,7 @@ impl AstNode {
     /// 获取对象属性（如果是 ObjectExpression）
     pub fn get_properties(&self) -> Option<&Vec<ObjectProperty>> {
         match self {
-            AstNode::ObjectExpression { properties } => Some(properties),
+            AstNode::ObjectExpression { properties, .. } => Some(properties),
             _ => None,
         }
     }
@@ -135,22 +185,20 @@ impl AstNode {
     /// 获取模板字面量的 quasis（如果是 TemplateLiteral）
     pub fn get_quasis(&self) -> Option<&Vec<TemplateElement>> {
         match self {
-            AstNode::TemplateLiteral { quasis } => Some(quasis),
+            AstNode::TemplateLiteral { quasis, .. } => Some(quasis),
             _ => None,
         }
     }
 }
 
-/// 将 AST 节点数组转换为字符串
+/// 将 AST 节点数组转换为字符串，无法转换的表达式向 `sink` 报告 `ParseError`
 pub fn nodes_to_string(
     nodes: &[AstNode],
     filepath: &str,
-    on_error: Option<&OnErrorCallback>,
+    sink: Option<&dyn DiagnosticSink>,
 ) -> String {
     let mut memo = String::new();
-    let mut node_index = 0;
-
-    for node in nodes {
+    for (node_index, node) in nodes.iter().enumerate() {
         if node.is_jsx_text() || node.is_string_literal() {
             if let Some(value) = node.get_value() {
                 let processed_value = process_text_value(value);
@@ -179,26 +227,34 @@ pub fn nodes_to_string(
                             memo.push_str(&quasi.value.raw);
                         }
                     }
-                } else {
-                    if let Some(error_callback) = on_error {
-                        let location = node.get_location();
-                        let error_fn = create_error_handler(filepath, location.line, location.column);
-                        error_callback();
-                        error_fn();
-                    }
+                } else if let Some(sink) = sink {
+                    sink.report(&error_at("ParseError", filepath, node.span(), UNSUPPORTED_EXPRESSION));
                 }
             }
         } else if let Some(children) = node.get_children() {
-            let child_string = nodes_to_string(children, filepath, on_error);
+            let child_string = nodes_to_string(children, filepath, sink);
             memo.push_str(&format!("<{}>{}</{}>", node_index, child_string, node_index));
         }
-
-        node_index += 1;
     }
 
     memo
 }
 
+/// 找出 nodes_to_string 无法转换的表达式（如 `{user.name}`），返回其所在的表达式容器
+pub fn unsupported_expressions(nodes: &[AstNode]) -> Vec<&AstNode> {
+    let mut found = Vec::new();
+    for node in nodes {
+        if let Some(expression) = node.get_expression() {
+            if matches!(expression, AstNode::Other { .. }) {
+                found.push(node);
+            }
+        } else if let Some(children) = node.get_children() {
+            found.extend(unsupported_expressions(children));
+        }
+    }
+    found
+}
+
 /// 处理文本值，移除换行符和多余空格
 fn process_text_value(value: &str) -> String {
     // remove leading spaces containing a leading newline character
@@ -216,67 +272,64 @@ fn process_text_value(value: &str) -> String {
     result.trim().to_string()
 }
 
-/// 创建错误处理函数
-fn create_error_handler(filepath: &str, line: u32, column: u32) -> Box<dyn Fn()> {
-    let filepath = filepath.to_string();
-    Box::new(move || {
-        eprintln!();
-        eprintln!("{}", format!("{}:{}:{}", filepath, line, column).yellow());
-        eprintln!(
-            "{}",
-            "Unsupported JSX expression. Only static values or {{interpolation}} blocks are supported.".red()
-        );
-    })
-}
-
 /// 简化版本的 nodes_to_string，不需要错误回调
 pub fn nodes_to_string_simple(nodes: &[AstNode], filepath: &str) -> String {
     nodes_to_string(nodes, filepath, None)
 }
 
-/// 创建默认错误回调
-pub fn create_default_error_callback(filepath: &str, line: u32, column: u32) -> OnErrorCallback {
-    let filepath = filepath.to_string();
-    Box::new(move || {
-        eprintln!();
-        eprintln!("{}", format!("{}:{}:{}", filepath, line, column).yellow());
-        eprintln!(
-            "{}",
-            "Unsupported JSX expression. Only static values or {{interpolation}} blocks are supported.".red()
-        );
-    })
-}
-
 // 便捷构造函数
 impl AstNode {
     pub fn jsx_text(value: String) -> Self {
-        AstNode::JSXText { value }
+        AstNode::JSXText {
+            value,
+            span: Span::default(),
+        }
     }
 
     pub fn string_literal(value: String) -> Self {
-        AstNode::StringLiteral { value }
+        AstNode::StringLiteral {
+            value,
+            span: Span::default(),
+        }
     }
 
     pub fn jsx_expression_container(expression: AstNode) -> Self {
         AstNode::JSXExpressionContainer {
             expression: Box::new(expression),
+            span: Span::default(),
         }
     }
 
     pub fn numeric_literal(value: f64) -> Self {
-        AstNode::NumericLiteral { value }
+        AstNode::NumericLiteral {
+            value,
+            span: Span::default(),
+        }
     }
 
     pub fn object_expression(properties: Vec<ObjectProperty>) -> Self {
-        AstNode::ObjectExpression { properties }
+        AstNode::ObjectExpression {
+            properties,
+            span: Span::default(),
+        }
     }
 
     pub fn template_literal(quasis: Vec<TemplateElement>) -> Self {
-        AstNode::TemplateLiteral { quasis }
+        AstNode::TemplateLiteral {
+            quasis,
+            span: Span::default(),
+        }
     }
 
     pub fn jsx_element(children: Vec<AstNode>) -> Self {
-        AstNode::JSXElement { children }
+        AstNode::JSXElement {
+            children,
+            span: Span::default(),
+        }
+    }
+
+    pub fn other() -> Self {
+        AstNode::Other { span: Span::default() }
     }
 }
 
@@ -300,6 +353,7 @@ impl TemplateElement {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scan::diagnostic::CollectingSink;
 
     #[test]
     fn test_jsx_text_processing() {
@@ -365,6 +419,38 @@ mod tests {
         assert_eq!(result, "Hello{{name}}World");
     }
 
+    #[test]
+    fn test_unsupported_expressions() {
+        let span = Span::new(2, 5, 2, 16);
+        let nodes = vec![
+            AstNode::jsx_text("Hello ".to_string()),
+            AstNode::jsx_element(vec![AstNode::jsx_expression_container(AstNode::other()).with_span(span)]),
+            AstNode::jsx_expression_container(AstNode::numeric_literal(1.0)),
+        ];
+        let found = unsupported_expressions(&nodes);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].span(), span);
+        assert_eq!((found[0].get_location().line, found[0].get_location().column), (2, 5));
+        assert_eq!(nodes[0].get_location().line, 1);
+    }
+
+    #[test]
+    fn test_unsupported_expression_reported_to_sink() {
+        let span = Span::new(3, 7, 3, 18);
+        let nodes = vec![
+            AstNode::jsx_text("Hello ".to_string()),
+            AstNode::jsx_expression_container(AstNode::other()).with_span(span),
+        ];
+        let sink = CollectingSink::new();
+        assert_eq!(nodes_to_string(&nodes, "test.tsx", Some(&sink)), "Hello");
+
+        let findings = sink.take();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "ParseError");
+        assert_eq!(findings[0].filepath, "test.tsx");
+        assert_eq!(findings[0].span, Some(span));
+    }
+
     #[test]
     fn test_process_text_value() {
         assert_eq!(process_text_value("  \n  Hello World  \n  "), "Hello World");
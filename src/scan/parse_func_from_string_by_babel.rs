@@ -1,10 +1,9 @@
-use colored::*;
 use regex::Regex;
 
 use std::collections::HashMap;
 
-/// 错误回调函数类型
-pub type OnErrorCallback = Box<dyn Fn() + Send + Sync>;
+use super::diagnostic::{error_at, DiagnosticSink};
+use super::span::{SourceError, Span};
 
 /// 自定义处理函数类型
 pub type CustomHandler = Box<dyn Fn(&str, &ParseOptions) + Send + Sync>;
@@ -13,7 +12,7 @@ pub type CustomHandler = Box<dyn Fn(&str, &ParseOptions) + Send + Sync>;
 pub type PropsFilter = Box<dyn Fn(&str) -> String + Send + Sync>;
 
 /// 解析选项
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ParseOptions {
     pub default_value: Option<String>,
     pub default_value_plural: Option<String>,
@@ -24,20 +23,6 @@ pub struct ParseOptions {
     pub ns_separator: Option<String>,
 }
 
-impl Default for ParseOptions {
-    fn default() -> Self {
-        Self {
-            default_value: None,
-            default_value_plural: None,
-            count: None,
-            context: None,
-            ns: None,
-            key_separator: None,
-            ns_separator: None,
-        }
-    }
-}
-
 /// Babel 解析器选项
 #[derive(Debug, Clone)]
 pub struct BabylonOptions {
@@ -75,6 +60,7 @@ impl Default for FuncParseConfig {
 }
 
 /// 解析器选项
+#[derive(Default)]
 pub struct ParserOpts {
     pub list: Option<Vec<String>>,
     pub props_filter: Option<PropsFilter>,
@@ -82,34 +68,34 @@ pub struct ParserOpts {
     pub babylon_options: Option<BabylonOptions>,
 }
 
-impl Default for ParserOpts {
-    fn default() -> Self {
-        Self {
-            list: None,
-            props_filter: None,
-            filepath: None,
-            babylon_options: None,
-        }
-    }
+/// 翻译键在源码中的一次出现
+#[derive(Debug, Clone)]
+pub struct KeyOccurrence {
+    pub key: String,
+    pub options: ParseOptions,
+    pub line: u32,
+    pub column: u32,
 }
 
 /// 解析器结构体
 pub struct Parser {
     pub options: ParserOptions,
     pub translations: HashMap<String, ParseOptions>,
+    /// 按出现顺序记录的所有调用，`translations` 中同名 key 只保留最后一次
+    pub occurrences: Vec<KeyOccurrence>,
+    /// 无法解析的选项对象
+    pub errors: Vec<SourceError>,
 }
 
 /// 解析器选项
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ParserOptions {
     pub func: FuncParseConfig,
 }
 
-impl Default for ParserOptions {
+impl Default for Parser {
     fn default() -> Self {
-        Self {
-            func: FuncParseConfig::default(),
-        }
+        Self::new()
     }
 }
 
@@ -118,6 +104,8 @@ impl Parser {
         Self {
             options: ParserOptions::default(),
             translations: HashMap::new(),
+            occurrences: Vec::new(),
+            errors: Vec::new(),
         }
     }
 
@@ -125,6 +113,8 @@ impl Parser {
         Self {
             options,
             translations: HashMap::new(),
+            occurrences: Vec::new(),
+            errors: Vec::new(),
         }
     }
 
@@ -167,7 +157,7 @@ impl Parser {
         content: &str,
         opts: ParserOpts,
         custom_handler: Option<CustomHandler>,
-        on_error: Option<OnErrorCallback>,
+        sink: Option<&dyn DiagnosticSink>,
     ) -> &mut Self {
         let funcs = opts.list.unwrap_or_else(|| self.options.func.list.clone());
 
@@ -177,23 +167,29 @@ impl Parser {
 
         let match_funcs = funcs
             .iter()
-            .map(|func| format!("(?:{})", regex::escape(func)))
+            .map(|func| format!("(?:{})", func_pattern(func)))
             .collect::<Vec<_>>()
             .join("|");
 
         let match_special_characters = r"[\r\n\s]*";
-        let string_group = format!(
-            "{}({}|{}|{}){}",
-            match_special_characters,
-            r"`(?:[^`\\]|\\(?:.|$))*`",      // backtick
-            r#""(?:[^"\\]|\\(?:.|$))*""#,   // double quotes
-            r"'(?:[^'\\]|\\(?:.|$))*'",     // single quote
-            match_special_characters
-        );
+        // 使用命名分组，正则形式的函数名中的分组不影响 key 与默认值的位置
+        let string_group = |name: &str| {
+            format!(
+                "{}(?P<{}>{}|{}|{}){}",
+                match_special_characters,
+                name,
+                r"`(?:[^`\\]|\\(?:.|$))*`",      // backtick
+                r#""(?:[^"\\]|\\(?:.|$))*""#,   // double quotes
+                r"'(?:[^'\\]|\\(?:.|$))*'",     // single quote
+                match_special_characters
+            )
+        };
 
         let pattern = format!(
             r"(?:(?:^\s*)|[^a-zA-Z0-9_])(?:{})\({}(?:[,]{})?[,)]",
-            match_funcs, string_group, string_group
+            match_funcs,
+            string_group("key"),
+            string_group("default_value")
         );
 
         let re = Regex::new(&pattern).unwrap();
@@ -202,13 +198,14 @@ impl Parser {
             let mut options = ParseOptions::default();
             let full = captures.get(0).unwrap().as_str();
 
-            let key_match = captures.get(1).map(|m| m.as_str()).unwrap_or("");
+            let key_match = captures.name("key").map(|m| m.as_str()).unwrap_or("");
+            let key_start = captures.name("key").map_or(0, |m| m.start());
             let key = match self.fix_string_after_reg_exp(key_match, true) {
                 Some(k) => k,
                 None => continue,
             };
 
-            if let Some(default_value_match) = captures.get(2) {
+            if let Some(default_value_match) = captures.name("default_value") {
                 if let Some(default_value) = self.fix_string_after_reg_exp(default_value_match.as_str(), false) {
                     options.default_value = Some(default_value);
                 } else {
@@ -218,30 +215,42 @@ impl Parser {
 
             let ends_with_comma = full.ends_with(',');
             if ends_with_comma {
-                let babylon_options = opts.babylon_options.as_ref().map(|o| o.clone()).unwrap_or_else(|| self.options.func.babylon.clone());
+                let babylon_options = opts.babylon_options.clone().unwrap_or_else(|| self.options.func.babylon.clone());
                 let filepath = opts.filepath.as_deref().unwrap_or("");
 
-                // 找到匹配位置后的代码
+                // 找到匹配位置后的选项对象，不是对象字面量（如变量）时无法静态解析
                 let match_end = captures.get(0).unwrap().end();
-                let remaining_content = &content[match_end..];
-                let mut code = match_balanced_parentheses(remaining_content);
+                let code_start = match_end + (content.len() - match_end - content[match_end..].trim_start().len());
+                let remaining_content = &content[code_start..];
+                let mut code = if remaining_content.starts_with('{') {
+                    match_balanced_parentheses(remaining_content)
+                } else {
+                    String::new()
+                };
 
-                // 应用属性过滤器
+                // 应用属性过滤器，过滤后的代码与源码不再对应，位置都指向调用处
+                let filtered = opts.props_filter.is_some();
                 if let Some(ref filter) = opts.props_filter {
                     code = filter(&code);
                 }
+                let locate = |start: usize, end: usize| {
+                    if filtered {
+                        Span::from_offsets(content, key_start, key_start)
+                    } else {
+                        Span::from_offsets(content, code_start + start, code_start + end)
+                    }
+                };
 
                 if !code.trim().is_empty() {
-                    match parse_babel_code(&format!("({})", code), &babylon_options) {
+                    match parse_babel_code(&code, &locate, &babylon_options) {
                         Ok(props) => {
                             self.process_parsed_properties(&mut options, &props);
                         }
                         Err(err) => {
-                            if let Some(ref error_callback) = on_error {
-                                let error_handler = create_parse_error_handler(filepath, &code, &err);
-                                error_callback();
-                                error_handler();
+                            if let Some(sink) = sink {
+                                sink.report(&error_at("ParseError", filepath, err.span, &err.message));
                             }
+                            self.errors.push(err);
                         }
                     }
                 }
@@ -252,6 +261,13 @@ impl Parser {
                 continue;
             }
 
+            let (line, column) = offset_to_line_column(content, key_start);
+            self.occurrences.push(KeyOccurrence {
+                key: key.clone(),
+                options: options.clone(),
+                line,
+                column,
+            });
             self.set(&key, options);
         }
 
@@ -271,6 +287,10 @@ impl Parser {
         ];
 
         for prop in props {
+            // 只有 count 关心属性是否存在，其余选项需要静态的字符串值
+            if matches!(prop.value, PropertyValue::Unknown) && prop.key != "count" {
+                continue;
+            }
             if supported_options.contains(&prop.key.as_str()) {
                 let value = match &prop.value {
                     PropertyValue::Literal(s) => s.clone(),
@@ -298,6 +318,8 @@ impl Parser {
 pub struct Property {
     pub key: String,
     pub value: PropertyValue,
+    /// 属性（键和值）在源码中的范围
+    pub span: Span,
 }
 
 /// 属性值枚举
@@ -308,15 +330,52 @@ pub enum PropertyValue {
     Unknown,
 }
 
-/// 匹配平衡的括号
+/// 将字节偏移转换为从 1 开始的行列号（列按字符计算）
+pub(crate) fn offset_to_line_column(content: &str, offset: usize) -> (u32, u32) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line as u32, column as u32)
+}
+
+/// `func.list` 中一项对应的正则：普通名称（如 `i18n.t`、`$t`）按字面匹配，
+/// 含其他正则元字符且能编译的项按正则匹配，如 `i18n\.(t|translate)`
+pub fn func_pattern(func: &str) -> String {
+    let is_regex = func.contains(['\\', '^', '(', ')', '[', ']', '{', '}', '*', '+', '?', '|']) && Regex::new(func).is_ok();
+    if is_regex {
+        func.to_string()
+    } else {
+        regex::escape(func)
+    }
+}
+
+/// 匹配平衡的括号，跳过字符串中的括号
 pub fn match_balanced_parentheses(s: &str) -> String {
     let parentheses = "[]{}()";
     let mut stack = Vec::new();
     let mut start = None;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
 
     for (i, ch) in s.char_indices() {
-        if start.is_some() && stack.is_empty() {
-            return s[start.unwrap()..i].to_string();
+        if let (Some(start), true) = (start, stack.is_empty()) {
+            return s[start..i].to_string();
+        }
+
+        if let Some(q) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        if start.is_some() && matches!(ch, '"' | '\'' | '`') {
+            quote = Some(ch);
+            continue;
         }
 
         if let Some(brace_pos) = parentheses.find(ch) {
@@ -338,40 +397,124 @@ pub fn match_balanced_parentheses(s: &str) -> String {
     s[start.unwrap_or(0)..].to_string()
 }
 
+/// 检查选项对象的括号与字符串是否闭合，返回出错位置（相对 `code` 的字节范围）与信息
+fn check_syntax(code: &str) -> Result<(), (usize, usize, String)> {
+    let mut stack: Vec<(usize, char)> = Vec::new();
+    let mut quote: Option<(usize, char)> = None;
+    let mut escaped = false;
+
+    for (i, ch) in code.char_indices() {
+        if let Some((start, q)) = quote {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == '\n' && q != '`' {
+                return Err((start, i, "Unterminated string literal".to_string()));
+            } else if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some((i, ch)),
+            '{' | '[' | '(' => stack.push((i, ch)),
+            '}' | ']' | ')' => {
+                let expected = match stack.pop() {
+                    Some((_, '{')) => '}',
+                    Some((_, '[')) => ']',
+                    Some(_) => ')',
+                    None => return Err((i, i + 1, format!("Unexpected `{}`", ch))),
+                };
+                if ch != expected {
+                    return Err((i, i + 1, format!("Unexpected `{}`, expected `{}`", ch, expected)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((start, _)) = quote {
+        return Err((start, code.len(), "Unterminated string literal".to_string()));
+    }
+    match stack.last() {
+        Some((start, open)) => Err((*start, start + 1, format!("Unclosed `{}`", open))),
+        None => Ok(()),
+    }
+}
+
 /// 解析 Babel 代码（简化版本）
-fn parse_babel_code(code: &str, _options: &BabylonOptions) -> Result<Vec<Property>, String> {
+///
+/// `locate` 把 `code` 中的字节范围转换为源码中的位置。
+fn parse_babel_code(
+    code: &str,
+    locate: &dyn Fn(usize, usize) -> Span,
+    _options: &BabylonOptions,
+) -> Result<Vec<Property>, SourceError> {
+    if let Err((start, end, message)) = check_syntax(code) {
+        return Err(SourceError {
+            message: format!("Unable to parse options: {}", message),
+            span: locate(start, end),
+        });
+    }
+
     // 这里是一个简化的解析器，实际使用时应该集成真正的 Babel 解析器
     // 目前只做基本的对象属性解析
     let mut properties = Vec::new();
 
     // 简单的正则匹配对象属性
-    let prop_regex = Regex::new(r#"(\w+)\s*:\s*(['"`])(.*?)\2"#).unwrap();
-    
+    // regex crate 不支持反向引用，因此对三种引号分别匹配
+    let prop_regex = Regex::new(r#"(\w+)\s*:\s*(?:'([^']*)'|"([^"]*)"|`([^`]*)`)"#).unwrap();
+
     for captures in prop_regex.captures_iter(code) {
+        let whole = captures.get(0).unwrap();
         let key = captures.get(1).unwrap().as_str().to_string();
-        let value = captures.get(3).unwrap().as_str().to_string();
-        
+        let value = captures
+            .get(2)
+            .or_else(|| captures.get(3))
+            .or_else(|| captures.get(4))
+            .map_or(String::new(), |m| m.as_str().to_string());
+
         properties.push(Property {
             key,
             value: PropertyValue::Literal(value),
+            span: locate(whole.start(), whole.end()),
         });
     }
 
-    Ok(properties)
-}
+    // 非字符串的属性值（如 `count: list.length`）和简写属性（如 `{ count }`）无法静态求值，
+    // 只记录属性存在
+    let expr_regex = Regex::new(r#"(\w+)\s*:\s*[^'"`\s,}]"#).unwrap();
+    let trimmed = code.trim();
+    let inner = trimmed.strip_prefix('{').and_then(|c| c.strip_suffix('}')).unwrap_or(trimmed);
+    let inner_start = inner.as_ptr() as usize - code.as_ptr() as usize;
+    let mut segment_start = inner_start;
+    let shorthand = inner.split(',').filter_map(|segment| {
+        let start = segment_start + (segment.len() - segment.trim_start().len());
+        segment_start += segment.len() + 1;
+        let segment = segment.trim();
+        (!segment.is_empty() && segment.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$'))
+            .then(|| (segment.to_string(), start, start + segment.len()))
+    });
+    let unknown_keys: Vec<(String, usize, usize)> = expr_regex
+        .captures_iter(code)
+        .map(|captures| {
+            let key = captures.get(1).unwrap();
+            (key.as_str().to_string(), key.start(), key.end())
+        })
+        .chain(shorthand)
+        .collect();
+    for (key, start, end) in unknown_keys {
+        if !properties.iter().any(|prop| prop.key == key) {
+            properties.push(Property {
+                key,
+                value: PropertyValue::Unknown,
+                span: locate(start, end),
+            });
+        }
+    }
 
-/// 创建解析错误处理函数
-fn create_parse_error_handler(filepath: &str, code: &str, error: &str) -> Box<dyn Fn()> {
-    let filepath = filepath.to_string();
-    let code = code.to_string();
-    let error = error.to_string();
-    
-    Box::new(move || {
-        eprintln!();
-        eprintln!("{}", format!("{}:1:1", filepath).yellow());
-        eprintln!("{}", format!("\nUnable to parse code \"{}\"\n", code.green()).cyan());
-        eprintln!("{}", format!("    {}", error).red());
-    })
+    Ok(properties)
 }
 
 /// 创建解析器实例
@@ -387,6 +530,7 @@ pub fn create_parser_with_options(options: ParserOptions) -> Parser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::scan::diagnostic::CollectingSink;
 
     #[test]
     fn test_match_balanced_parentheses() {
@@ -451,4 +595,92 @@ mod tests {
         let options = parser.translations.get("greeting").unwrap();
         assert_eq!(options.default_value, Some("Hello World".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_occurrences_keep_every_call_with_location() {
+        let mut parser = Parser::new();
+        let content = "t('save', '保存');\nconst a = t('save', '存储');";
+
+        parser.parse_func_from_string_by_babel(content, ParserOpts::default(), None, None);
+
+        assert_eq!(parser.occurrences.len(), 2);
+        assert_eq!(parser.occurrences[0].options.default_value.as_deref(), Some("保存"));
+        assert_eq!((parser.occurrences[0].line, parser.occurrences[0].column), (1, 3));
+        assert_eq!(parser.occurrences[1].options.default_value.as_deref(), Some("存储"));
+        assert_eq!((parser.occurrences[1].line, parser.occurrences[1].column), (2, 13));
+    }
+
+    #[test]
+    fn test_parse_options_object() {
+        let mut parser = Parser::new();
+        let content = r#"t('items', { defaultValue: "项目", context: 'male' })"#;
+
+        parser.parse_func_from_string_by_babel(content, ParserOpts::default(), None, None);
+
+        let options = parser.translations.get("items").unwrap();
+        assert_eq!(options.default_value, Some("项目".to_string()));
+        assert_eq!(options.context, Some("male".to_string()));
+    }
+
+    #[test]
+    fn test_parse_count_expression() {
+        let mut parser = Parser::new();
+        let content = "t('共{{count}}项', { count });\nt('items', { count: list.length, defaultValue: label });";
+
+        parser.parse_func_from_string_by_babel(content, ParserOpts::default(), None, None);
+
+        assert!(parser.translations.get("共{{count}}项").unwrap().count.is_some());
+        let options = parser.translations.get("items").unwrap();
+        assert!(options.count.is_some());
+        assert_eq!(options.default_value, None);
+    }
+
+    #[test]
+    fn test_property_spans() {
+        let content = "const a = 1;\nt('items', { defaultValue: '项目', count });";
+        let mut parser = Parser::new();
+        parser.parse_func_from_string_by_babel(content, ParserOpts::default(), None, None);
+        assert!(parser.errors.is_empty());
+
+        let code = match_balanced_parentheses(&content[content.find('{').unwrap()..]);
+        let code_start = content.find('{').unwrap();
+        let locate = |start: usize, end: usize| Span::from_offsets(content, code_start + start, code_start + end);
+        let props = parse_babel_code(&code, &locate, &BabylonOptions::default()).unwrap();
+        assert_eq!((props[0].key.as_str(), props[0].span), ("defaultValue", Span::new(2, 14, 2, 32)));
+        assert_eq!((props[1].key.as_str(), props[1].span), ("count", Span::new(2, 34, 2, 39)));
+    }
+
+    #[test]
+    fn test_options_syntax_errors() {
+        let mut parser = Parser::new();
+        let content = "t('a', { ns: 'x' )\nt('b', { ns: 'common\n})\nt('c', { ns: ')' })\nt('d', options)";
+        parser.parse_func_from_string_by_babel(content, ParserOpts::default(), None, None);
+
+        let errors: Vec<_> = parser.errors.iter().map(|e| (e.message.as_str(), e.span)).collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("Unable to parse options: Unclosed `{`", Span::new(1, 8, 1, 9)),
+                ("Unable to parse options: Unterminated string literal", Span::new(2, 14, 2, 21)),
+            ]
+        );
+        assert_eq!(parser.translations.get("c").unwrap().ns.as_deref(), Some(")"));
+        assert_eq!(parser.occurrences.len(), 4);
+    }
+
+    #[test]
+    fn test_options_syntax_errors_reported_to_sink() {
+        let mut parser = Parser::new();
+        let sink = CollectingSink::new();
+        let opts = ParserOpts {
+            filepath: Some("src/a.js".to_string()),
+            ..Default::default()
+        };
+        parser.parse_func_from_string_by_babel("t('a', { ns: 'x' )", opts, None, Some(&sink));
+
+        let findings = sink.take();
+        assert_eq!(findings.len(), 1);
+        assert_eq!((findings[0].filepath.as_str(), findings[0].line, findings[0].column), ("src/a.js", 1, 8));
+        assert_eq!(findings[0].message, "Unable to parse options: Unclosed `{`");
+    }
+}
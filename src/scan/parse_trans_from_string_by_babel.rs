@@ -0,0 +1,318 @@
+use regex::Regex;
+use std::collections::HashMap;
+
+use super::nodes_to_string::{nodes_to_string_simple, unsupported_expressions, AstNode, ObjectProperty, TemplateElement};
+use super::parse_func_from_string_by_babel::{KeyOccurrence, ParseOptions};
+use super::span::{SourceError, Span};
+
+/// Trans 组件解析选项
+#[derive(Debug, Clone)]
+pub struct TransParseOptions {
+    /// 组件名，默认为 `Trans`
+    pub component: String,
+    /// 指定 key 的属性名，默认为 `i18nKey`
+    pub i18n_key: String,
+    /// 指定默认值的属性名，默认为 `defaults`
+    pub defaults_key: String,
+}
+
+impl Default for TransParseOptions {
+    fn default() -> Self {
+        Self {
+            component: "Trans".to_string(),
+            i18n_key: "i18nKey".to_string(),
+            defaults_key: "defaults".to_string(),
+        }
+    }
+}
+
+/// 从源码中提取 Trans 组件
+///
+/// 未指定 `i18nKey` 时以组件内容生成的句子作为 key，与函数调用 `t('句子')` 的处理方式一致。
+pub fn parse_trans_from_string_by_babel(content: &str, options: &TransParseOptions) -> Vec<KeyOccurrence> {
+    parse_trans_with_errors(content, options).0
+}
+
+/// 同 `parse_trans_from_string_by_babel`，并返回组件内容中无法转换为句子的表达式
+pub fn parse_trans_with_errors(content: &str, options: &TransParseOptions) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+    let mut occurrences = Vec::new();
+    let mut errors = Vec::new();
+    let open_re = Regex::new(&format!(r"<{}\b", regex::escape(&options.component))).unwrap();
+
+    for mat in open_re.find_iter(content) {
+        let rest = &content[mat.end()..];
+        let (attrs_src, self_closing, open_len) = match read_tag_attributes(rest) {
+            Some(tag) => tag,
+            None => continue,
+        };
+        let attrs = parse_attributes(attrs_src);
+
+        let children = if self_closing {
+            Vec::new()
+        } else {
+            let (nodes, _) = parse_jsx_children(content, mat.end() + open_len);
+            nodes
+        };
+
+        let sentence = match attrs.get(&options.defaults_key) {
+            Some(defaults) if !defaults.is_empty() => defaults.clone(),
+            _ => {
+                errors.extend(unsupported_expressions(&children).into_iter().map(|node| SourceError {
+                    message: format!(
+                        "Unsupported JSX expression in <{}>. Only static values or {{{{interpolation}}}} blocks are supported.",
+                        options.component
+                    ),
+                    span: node.span(),
+                }));
+                nodes_to_string_simple(&children, "")
+            }
+        };
+        let trans_key = attrs
+            .get(&options.i18n_key)
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .unwrap_or_else(|| sentence.clone());
+        if trans_key.is_empty() {
+            continue;
+        }
+
+        let mut parse_options = ParseOptions {
+            default_value: Some(sentence).filter(|s| !s.is_empty()),
+            ..Default::default()
+        };
+        if let Some(count) = attrs.get("count") {
+            parse_options.count = Some(count.clone());
+        }
+        if let Some(context) = attrs.get("context") {
+            parse_options.context = Some(context.clone());
+        }
+        if let Some(ns) = attrs.get("ns") {
+            parse_options.ns = Some(ns.clone());
+        }
+
+        let (line, column) = offset_to_line_column(content, mat.start());
+        occurrences.push(KeyOccurrence {
+            key: trans_key,
+            options: parse_options,
+            line,
+            column,
+        });
+    }
+
+    (occurrences, errors)
+}
+
+/// 读取标签属性部分，返回（属性源码，是否自闭合，开始标签剩余部分的长度）
+fn read_tag_attributes(s: &str) -> Option<(&str, bool, usize)> {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, ch) in s.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            '>' if depth == 0 => {
+                let self_closing = s[..i].trim_end().ends_with('/');
+                let attrs = s[..i].trim_end().trim_end_matches('/');
+                return Some((attrs, self_closing, i + 1));
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// 解析静态属性值：`a="x"`、`a={'x'}`、`a={x}`、`a={1}`
+fn parse_attributes(src: &str) -> HashMap<String, String> {
+    let attr_re = Regex::new(r#"([\w-]+)\s*=\s*(?:"([^"]*)"|'([^']*)'|\{\s*(?:'([^']*)'|"([^"]*)"|`([^`$]*)`|([\w.]+))\s*\})"#).unwrap();
+    let mut attrs = HashMap::new();
+    for captures in attr_re.captures_iter(src) {
+        let name = captures.get(1).unwrap().as_str().to_string();
+        let value = (2..=7)
+            .find_map(|i| captures.get(i))
+            .map_or(String::new(), |m| m.as_str().to_string());
+        attrs.insert(name, value);
+    }
+    attrs
+}
+
+/// 将 `content[start..]` 中的 JSX 子节点转换为 AST 节点，遇到 `</...>` 时停止，返回节点及消耗的长度
+fn parse_jsx_children(content: &str, start: usize) -> (Vec<AstNode>, usize) {
+    let s = &content[start..];
+    let span = |from: usize, to: usize| Span::from_offsets(content, start + from, start + to);
+    let mut nodes = Vec::new();
+    let mut i = 0;
+
+    while i < s.len() {
+        let rest = &s[i..];
+        if rest.starts_with("</") {
+            let end = rest.find('>').map_or(s.len(), |e| i + e + 1);
+            return (nodes, end);
+        }
+        if let Some(tag_rest) = rest.strip_prefix('<') {
+            let name_len = tag_rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '.' || c == '_' || c == '-'))
+                .unwrap_or(tag_rest.len());
+            let after_name = &tag_rest[name_len..];
+            match read_tag_attributes(after_name) {
+                Some((_, true, open_len)) => {
+                    let end = i + 1 + name_len + open_len;
+                    nodes.push(AstNode::jsx_element(Vec::new()).with_span(span(i, end)));
+                    i = end;
+                }
+                Some((_, false, open_len)) => {
+                    let body_start = i + 1 + name_len + open_len;
+                    let (children, consumed) = parse_jsx_children(content, start + body_start);
+                    let end = body_start + consumed;
+                    nodes.push(AstNode::jsx_element(children).with_span(span(i, end)));
+                    i = end;
+                }
+                None => break,
+            }
+            continue;
+        }
+        if rest.starts_with('{') {
+            let expr_len = balanced_braces_len(rest);
+            let raw = rest.get(1..expr_len - 1).unwrap_or("");
+            let expression = raw.trim();
+            let expression_start = i + 1 + (raw.len() - raw.trim_start().len());
+            if let Some(node) = parse_expression(expression) {
+                let node = node.with_span(span(expression_start, expression_start + expression.len()));
+                nodes.push(AstNode::jsx_expression_container(node).with_span(span(i, i + expr_len)));
+            }
+            i += expr_len;
+            continue;
+        }
+        let text_len = rest.find(['<', '{']).unwrap_or(rest.len());
+        nodes.push(AstNode::jsx_text(rest[..text_len].to_string()).with_span(span(i, i + text_len)));
+        i += text_len;
+    }
+
+    (nodes, s.len())
+}
+
+/// 计算从 `{` 开始到匹配的 `}` 的长度（含两端），跳过字符串中的括号
+fn balanced_braces_len(s: &str) -> usize {
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    for (i, ch) in s.char_indices() {
+        if let Some(q) = quote {
+            if ch == q {
+                quote = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' | '`' => quote = Some(ch),
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    s.len()
+}
+
+/// 将 JSX 表达式源码转换为 nodes_to_string 可以处理的节点，空表达式和注释返回 None
+fn parse_expression(expression: &str) -> Option<AstNode> {
+    if expression.is_empty() || (expression.starts_with("/*") && expression.ends_with("*/")) {
+        return None;
+    }
+    let quoted = |q: char| expression.len() >= 2 && expression.starts_with(q) && expression.ends_with(q);
+    if quoted('\'') || quoted('"') {
+        return Some(AstNode::string_literal(expression[1..expression.len() - 1].to_string()));
+    }
+    if quoted('`') && !expression.contains("${") {
+        let raw = expression[1..expression.len() - 1].to_string();
+        return Some(AstNode::template_literal(vec![TemplateElement::new(raw)]));
+    }
+    if let Ok(value) = expression.parse::<f64>() {
+        return Some(AstNode::numeric_literal(value));
+    }
+    if expression.starts_with('{') && expression.ends_with('}') {
+        let inner = expression[1..expression.len() - 1].trim();
+        let name_len = inner
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(inner.len());
+        if name_len > 0 {
+            let property = ObjectProperty::new(inner[..name_len].to_string(), AstNode::other());
+            return Some(AstNode::object_expression(vec![property]));
+        }
+    }
+    Some(AstNode::other())
+}
+
+/// 将字节偏移转换为从 1 开始的行列号（列按字符计算）
+fn offset_to_line_column(content: &str, offset: usize) -> (u32, u32) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line as u32, column as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Vec<KeyOccurrence> {
+        parse_trans_from_string_by_babel(content, &TransParseOptions::default())
+    }
+
+    #[test]
+    fn test_trans_with_i18n_key() {
+        let occurrences = parse(r#"<Trans i18nKey="welcome">欢迎 <strong>{{ name }}</strong></Trans>"#);
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].key, "welcome");
+        assert_eq!(occurrences[0].options.default_value.as_deref(), Some("欢迎<1>{{name}}</1>"));
+    }
+
+    #[test]
+    fn test_trans_without_key_uses_sentence() {
+        let content = "const a = 1;\n  <Trans count={items.length}>\n    共 {{ count }} 项\n  </Trans>";
+        let occurrences = parse(content);
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].key, "共{{count}}项");
+        assert_eq!(occurrences[0].options.count.as_deref(), Some("items.length"));
+        assert_eq!((occurrences[0].line, occurrences[0].column), (2, 3));
+    }
+
+    #[test]
+    fn test_trans_defaults_and_self_closing() {
+        let occurrences = parse(r#"<Trans i18nKey='k' defaults="默认文本" ns="common" />"#);
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].options.default_value.as_deref(), Some("默认文本"));
+        assert_eq!(occurrences[0].options.ns.as_deref(), Some("common"));
+    }
+
+    #[test]
+    fn test_unsupported_expression_span() {
+        let content = "<div>\n  <Trans>你好，{user.name}！</Trans>\n  <Trans defaults=\"忽略\">{user.name}</Trans>\n</div>";
+        let (occurrences, errors) = parse_trans_with_errors(content, &TransParseOptions::default());
+        assert_eq!(occurrences.len(), 2);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, Span::new(2, 13, 2, 24));
+        assert!(errors[0].message.starts_with("Unsupported JSX expression in <Trans>"));
+    }
+
+    #[test]
+    fn test_custom_component() {
+        let options = TransParseOptions {
+            component: "I18n".to_string(),
+            ..Default::default()
+        };
+        let occurrences = parse_trans_from_string_by_babel("<Trans>忽略</Trans><I18n>保存</I18n>", &options);
+        assert_eq!(occurrences.len(), 1);
+        assert_eq!(occurrences[0].key, "保存");
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+/// CLDR 基数复数类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// 复数 key 的后缀格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralSuffixStyle {
+    /// i18next v3（`compatibilityJSON: 'v3'`）：`key`/`key_plural`，单一形式为 `key_0`，多形式为 `key_0`…`key_n`
+    #[default]
+    V3,
+    /// i18next v4：按 CLDR 类别命名，如 `key_one`/`key_other`
+    V4,
+}
+
+use PluralCategory::*;
+
+/// 语言的 CLDR 基数复数类别，按 CLDR 规则中的顺序排列
+///
+/// 支持 `en-US`、`zh_Hant` 等带地区的写法，未收录的语言按 `one`/`other` 处理。
+pub fn plural_categories(lng: &str) -> &'static [PluralCategory] {
+    let language = lng
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(lng)
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "zh" | "ja" | "jp" | "ko" | "vi" | "th" | "id" | "ms" | "lo" | "my" | "km" => &[Other],
+        "fr" | "es" | "it" | "pt" | "ca" => &[One, Many, Other],
+        "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "lt" => &[One, Few, Many, Other],
+        "he" | "iw" => &[One, Two, Other],
+        "ar" | "cy" => &[Zero, One, Two, Few, Many, Other],
+        "ga" => &[One, Two, Few, Many, Other],
+        "ro" | "hr" | "sr" | "bs" => &[One, Few, Other],
+        "sl" => &[One, Two, Few, Other],
+        _ => &[One, Other],
+    }
+}
+
+/// 语言需要生成的复数 key 后缀，空字符串表示使用原 key
+pub fn plural_suffixes(lng: &str, style: PluralSuffixStyle) -> Vec<String> {
+    let categories = plural_categories(lng);
+    match style {
+        PluralSuffixStyle::V4 => categories
+            .iter()
+            .map(|category| format!("_{}", category.as_str()))
+            .collect(),
+        PluralSuffixStyle::V3 => match categories.len() {
+            1 => vec!["_0".to_string()],
+            2 => vec![String::new(), "_plural".to_string()],
+            n => (0..n).map(|i| format!("_{}", i)).collect(),
+        },
+    }
+}
+
+/// 复数后缀是否对应 `one` 类别：默认语言中该形式写入 `defaultValue`，其余形式写入 `defaultValue_plural`
+pub fn is_singular_suffix(lng: &str, style: PluralSuffixStyle, suffix: &str) -> bool {
+    let categories = plural_categories(lng);
+    plural_suffixes(lng, style)
+        .iter()
+        .zip(categories)
+        .any(|(s, category)| s == suffix && *category == One)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural_categories() {
+        assert_eq!(plural_categories("zh"), &[Other]);
+        assert_eq!(plural_categories("zh-Hant"), &[Other]);
+        assert_eq!(plural_categories("en_US"), &[One, Other]);
+        assert_eq!(plural_categories("ru").len(), 4);
+        assert_eq!(plural_categories("ar").len(), 6);
+    }
+
+    #[test]
+    fn test_plural_suffixes() {
+        assert_eq!(plural_suffixes("en", PluralSuffixStyle::V4), vec!["_one", "_other"]);
+        assert_eq!(plural_suffixes("ja", PluralSuffixStyle::V4), vec!["_other"]);
+        assert_eq!(plural_suffixes("en", PluralSuffixStyle::V3), vec!["", "_plural"]);
+        assert_eq!(plural_suffixes("ja", PluralSuffixStyle::V3), vec!["_0"]);
+        assert_eq!(plural_suffixes("ru", PluralSuffixStyle::V3), vec!["_0", "_1", "_2", "_3"]);
+    }
+
+    #[test]
+    fn test_singular_suffix() {
+        assert!(is_singular_suffix("en", PluralSuffixStyle::V4, "_one"));
+        assert!(!is_singular_suffix("en", PluralSuffixStyle::V4, "_other"));
+        assert!(is_singular_suffix("en", PluralSuffixStyle::V3, ""));
+        assert!(!is_singular_suffix("zh", PluralSuffixStyle::V3, "_0"));
+    }
+}
@@ -0,0 +1,38 @@
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// 扫描进度，每扫描完一个文件报告一次
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProgress {
+    /// 已扫描的文件数
+    pub scanned: usize,
+    /// 需要扫描的文件总数（有对应语言前端的文件）
+    pub total: usize,
+    /// 刚扫描完的文件
+    pub filepath: String,
+}
+
+/// 进度回调，在扫描所在的线程中调用
+pub type ProgressCallback = Arc<dyn Fn(&ScanProgress) + Send + Sync>;
+
+/// 取消扫描的标记，克隆后在其他线程调用 `cancel`，扫描在下一个文件开始前停止
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
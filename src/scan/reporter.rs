@@ -0,0 +1,449 @@
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::diff::{Finding, Severity};
+use super::span::code_frame;
+
+/// 工具名，用于 SARIF 的 driver、Checkstyle 的 source 与 JUnit 的测试套件名
+pub const TOOL_NAME: &str = "fast-i18n-scan";
+
+/// 把扫描发现的错误与警告格式化为某种输出
+pub trait Reporter {
+    fn report(&self, findings: &[Finding]) -> String;
+}
+
+/// 内置的输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReporterKind {
+    /// 每行一条：`file:line:col  severity  kind  message`
+    Text,
+    /// `Finding` 数组
+    Json,
+    /// 按文件分组并附带代码片段，适合在终端阅读
+    Stylish,
+    /// SARIF 2.1.0，可上传到 GitHub code scanning
+    Sarif,
+    /// Checkstyle XML
+    Checkstyle,
+    /// JUnit XML，每个文件一个测试套件
+    Junit,
+    /// GitHub Actions 的 `::error file=...` 注释命令
+    Github,
+}
+
+impl ReporterKind {
+    /// 命令行可选的格式名
+    pub const NAMES: [&'static str; 7] = ["text", "json", "stylish", "sarif", "checkstyle", "junit", "github"];
+
+    pub fn from_name(name: &str) -> Option<ReporterKind> {
+        serde_json::from_value(json!(name)).ok()
+    }
+
+    /// 根据类型创建输出器，`color` 只影响 stylish 格式
+    pub fn build(&self, color: bool) -> Box<dyn Reporter> {
+        match self {
+            ReporterKind::Text => Box::new(TextReporter),
+            ReporterKind::Json => Box::new(JsonReporter),
+            ReporterKind::Stylish => Box::new(StylishReporter { color }),
+            ReporterKind::Sarif => Box::new(SarifReporter),
+            ReporterKind::Checkstyle => Box::new(CheckstyleReporter),
+            ReporterKind::Junit => Box::new(JunitReporter),
+            ReporterKind::Github => Box::new(GithubReporter),
+        }
+    }
+}
+
+fn count(findings: &[Finding], severity: Severity) -> usize {
+    findings.iter().filter(|finding| finding.severity == severity).count()
+}
+
+/// 按文件分组，文件按路径排序，组内按行列号排序
+fn group_by_file(findings: &[Finding]) -> BTreeMap<&str, Vec<&Finding>> {
+    let mut groups: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for finding in findings {
+        groups.entry(finding.filepath.as_str()).or_default().push(finding);
+    }
+    for group in groups.values_mut() {
+        group.sort_by_key(|finding| (finding.line, finding.column));
+    }
+    groups
+}
+
+pub struct TextReporter;
+
+impl Reporter for TextReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        let mut out = String::new();
+        for finding in findings {
+            out.push_str(&format!(
+                "{}:{}:{}  {}  {}  {}\n",
+                finding.filepath,
+                finding.line,
+                finding.column,
+                finding.severity.as_str(),
+                finding.kind,
+                finding.message
+            ));
+        }
+        out.push_str(&format!(
+            "{} error(s), {} warning(s)\n",
+            count(findings, Severity::Error),
+            count(findings, Severity::Warning)
+        ));
+        out
+    }
+}
+
+pub struct JsonReporter;
+
+impl Reporter for JsonReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        serde_json::to_string_pretty(findings).unwrap_or_default()
+    }
+}
+
+/// 类似 ESLint 的 stylish 格式：按文件分组，每条附带代码片段，最后输出合计
+pub struct StylishReporter {
+    pub color: bool,
+}
+
+impl StylishReporter {
+    fn paint(&self, text: &str, style: fn(&str) -> colored::ColoredString) -> String {
+        if self.color {
+            style(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl Reporter for StylishReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        if findings.is_empty() {
+            return String::new();
+        }
+        let mut out = String::new();
+        for (filepath, group) in group_by_file(findings) {
+            let source = std::fs::read_to_string(filepath).ok();
+            out.push_str(&self.paint(if filepath.is_empty() { "<unknown>" } else { filepath }, |s| s.underline()));
+            out.push('\n');
+            for finding in group {
+                let severity = match finding.severity {
+                    Severity::Error => self.paint("error", |s| s.red()),
+                    Severity::Warning => self.paint("warning", |s| s.yellow()),
+                };
+                out.push_str(&format!(
+                    "  {}  {}  {}  {}\n",
+                    self.paint(&format!("{}:{}", finding.line, finding.column), |s| s.dimmed()),
+                    severity,
+                    finding.message,
+                    self.paint(&finding.kind, |s| s.dimmed())
+                ));
+                if let Some(frame) = source.as_deref().and_then(|source| code_frame(source, &finding.span())) {
+                    for line in frame.lines() {
+                        out.push_str(&format!("    {}\n", line));
+                    }
+                }
+            }
+            out.push('\n');
+        }
+
+        let errors = count(findings, Severity::Error);
+        let warnings = count(findings, Severity::Warning);
+        let summary = format!(
+            "✖ {} problem{} ({} error{}, {} warning{})",
+            findings.len(),
+            if findings.len() == 1 { "" } else { "s" },
+            errors,
+            if errors == 1 { "" } else { "s" },
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        );
+        if errors > 0 {
+            out.push_str(&self.paint(&summary, |s| s.red().bold()));
+        } else {
+            out.push_str(&self.paint(&summary, |s| s.yellow().bold()));
+        }
+        out.push('\n');
+        out
+    }
+}
+
+/// 统一为 `/` 分隔的相对路径，供 SARIF 的 uri 使用
+fn artifact_uri(filepath: &str) -> String {
+    let path = filepath.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+pub struct SarifReporter;
+
+impl Reporter for SarifReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        let rules: BTreeSet<&str> = findings.iter().map(|finding| finding.kind.as_str()).collect();
+        let results: Vec<serde_json::Value> = findings
+            .iter()
+            .map(|finding| {
+                let mut result = json!({
+                    "ruleId": finding.kind,
+                    "level": finding.severity.as_str(),
+                    "message": { "text": finding.message },
+                });
+                if !finding.filepath.is_empty() {
+                    let mut location = json!({ "artifactLocation": { "uri": artifact_uri(&finding.filepath) } });
+                    if finding.line > 0 {
+                        let mut region = json!({ "startLine": finding.line, "startColumn": finding.column.max(1) });
+                        if let Some(span) = finding.span.filter(|span| span.end_line > 0) {
+                            region["endLine"] = json!(span.end_line);
+                            region["endColumn"] = json!(span.end_column.max(1));
+                        }
+                        location["region"] = region;
+                    }
+                    result["locations"] = json!([{ "physicalLocation": location }]);
+                }
+                result
+            })
+            .collect();
+
+        let sarif = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": TOOL_NAME,
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                    }
+                },
+                "results": results,
+            }]
+        });
+        serde_json::to_string_pretty(&sarif).unwrap_or_default()
+    }
+}
+
+/// 转义 XML 属性与文本中的特殊字符
+fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' => out.push_str("&#10;"),
+            c if (c as u32) < 0x20 && c != '\t' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub struct CheckstyleReporter;
+
+impl Reporter for CheckstyleReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<checkstyle version=\"4.3\">\n");
+        for (filepath, group) in group_by_file(findings) {
+            out.push_str(&format!("  <file name=\"{}\">\n", escape_xml(filepath)));
+            for finding in group {
+                out.push_str(&format!(
+                    "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"{}.{}\" />\n",
+                    finding.line,
+                    finding.column,
+                    finding.severity.as_str(),
+                    escape_xml(&finding.message),
+                    TOOL_NAME,
+                    escape_xml(&finding.kind)
+                ));
+            }
+            out.push_str("  </file>\n");
+        }
+        out.push_str("</checkstyle>\n");
+        out
+    }
+}
+
+/// 每个文件一个测试套件，每条错误或警告一个失败的测试用例
+pub struct JunitReporter;
+
+impl Reporter for JunitReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        out.push_str(&format!(
+            "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            TOOL_NAME,
+            findings.len(),
+            findings.len()
+        ));
+        for (filepath, group) in group_by_file(findings) {
+            let filepath = escape_xml(filepath);
+            out.push_str(&format!(
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\">\n",
+                filepath,
+                group.len(),
+                group.len()
+            ));
+            for finding in group {
+                let kind = escape_xml(&finding.kind);
+                out.push_str(&format!(
+                    "    <testcase name=\"{}.{}:{}:{}\" classname=\"{}\">\n",
+                    kind, filepath, finding.line, finding.column, filepath
+                ));
+                out.push_str(&format!(
+                    "      <failure message=\"{}\" type=\"{}\">{}:{}:{} {} {}</failure>\n",
+                    escape_xml(&finding.message),
+                    finding.severity.as_str(),
+                    filepath,
+                    finding.line,
+                    finding.column,
+                    kind,
+                    escape_xml(&finding.message)
+                ));
+                out.push_str("    </testcase>\n");
+            }
+            out.push_str("  </testsuite>\n");
+        }
+        out.push_str("</testsuites>\n");
+        out
+    }
+}
+
+/// 转义 GitHub 注释命令中的消息（`data`）或属性值（`property`）
+fn escape_github(text: &str, property: bool) -> String {
+    let text = text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A");
+    if property {
+        text.replace(':', "%3A").replace(',', "%2C")
+    } else {
+        text
+    }
+}
+
+pub struct GithubReporter;
+
+impl Reporter for GithubReporter {
+    fn report(&self, findings: &[Finding]) -> String {
+        let mut out = String::new();
+        for finding in findings {
+            let mut properties = Vec::new();
+            if !finding.filepath.is_empty() {
+                properties.push(format!("file={}", escape_github(&finding.filepath, true)));
+                if finding.line > 0 {
+                    properties.push(format!("line={}", finding.line));
+                    properties.push(format!("col={}", finding.column));
+                    if let Some(span) = finding.span.filter(|span| span.end_line > 0) {
+                        properties.push(format!("endLine={}", span.end_line));
+                        properties.push(format!("endColumn={}", span.end_column));
+                    }
+                }
+            }
+            properties.push(format!("title={}", escape_github(&finding.kind, true)));
+            out.push_str(&format!(
+                "::{} {}::{}\n",
+                finding.severity.as_str(),
+                properties.join(","),
+                escape_github(&finding.message, false)
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(severity: Severity, filepath: &str, line: u32, column: u32, message: &str) -> Finding {
+        Finding {
+            severity,
+            kind: if severity == Severity::Error { "HardCodedText" } else { "StringConcatenation" }.to_string(),
+            filepath: filepath.to_string(),
+            line,
+            column,
+            message: message.to_string(),
+            span: None,
+        }
+    }
+
+    #[test]
+    fn test_reporter_kind_from_name() {
+        for name in ReporterKind::NAMES {
+            assert!(ReporterKind::from_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(ReporterKind::from_name("junit"), Some(ReporterKind::Junit));
+        assert_eq!(ReporterKind::from_name("xml"), None);
+    }
+
+    #[test]
+    fn test_stylish_reporter() {
+        let path = std::env::temp_dir().join(format!("reporter-test-{}.js", std::process::id()));
+        std::fs::write(&path, "t('ok');\nalert('保存');\n").unwrap();
+        let filepath = path.to_str().unwrap();
+        let findings = vec![
+            finding(Severity::Warning, filepath, 2, 1, "concat"),
+            finding(Severity::Error, filepath, 2, 7, "Hard-coded text"),
+        ];
+        let out = StylishReporter { color: false }.report(&findings);
+        let frame = "      1 | t('ok');\n    > 2 | alert('保存');\n";
+        assert_eq!(
+            out,
+            format!(
+                "{}\n  2:1  warning  concat  StringConcatenation\n{}        | ^\n  2:7  error  Hard-coded text  HardCodedText\n{}        |       ^\n\n✖ 2 problems (1 error, 1 warning)\n",
+                filepath, frame, frame
+            )
+        );
+        assert_eq!(StylishReporter { color: false }.report(&[]), "");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_sarif_reporter() {
+        let findings = vec![
+            finding(Severity::Error, "./src/a.js", 3, 5, "Hard-coded text"),
+            finding(Severity::Warning, "", 0, 0, "global"),
+        ];
+        let sarif: serde_json::Value = serde_json::from_str(&SarifReporter.report(&findings)).unwrap();
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"], json!([{ "id": "HardCodedText" }, { "id": "StringConcatenation" }]));
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"],
+            json!({ "artifactLocation": { "uri": "src/a.js" }, "region": { "startLine": 3, "startColumn": 5 } })
+        );
+        assert_eq!(run["results"][1]["level"], "warning");
+        assert!(run["results"][1].get("locations").is_none());
+    }
+
+    #[test]
+    fn test_xml_reporters() {
+        let findings = vec![
+            finding(Severity::Error, "src/b.js", 1, 0, "a < b & \"c\""),
+            finding(Severity::Warning, "src/a.js", 2, 3, "concat"),
+        ];
+        let checkstyle = CheckstyleReporter.report(&findings);
+        assert!(checkstyle.contains("<file name=\"src/a.js\">\n    <error line=\"2\" column=\"3\" severity=\"warning\""));
+        assert!(checkstyle.contains("message=\"a &lt; b &amp; &quot;c&quot;\" source=\"fast-i18n-scan.HardCodedText\""));
+        assert!(checkstyle.find("src/a.js") < checkstyle.find("src/b.js"));
+
+        let junit = JunitReporter.report(&findings);
+        assert!(junit.contains("<testsuites name=\"fast-i18n-scan\" tests=\"2\" failures=\"2\">"));
+        assert!(junit.contains("<testsuite name=\"src/b.js\" tests=\"1\" failures=\"1\" errors=\"0\">"));
+        assert!(junit.contains("<failure message=\"concat\" type=\"warning\">src/a.js:2:3 StringConcatenation concat</failure>"));
+    }
+
+    #[test]
+    fn test_github_reporter() {
+        let findings = vec![
+            finding(Severity::Error, "src/a,b.js", 3, 5, "100% wrong\nsecond line"),
+            finding(Severity::Warning, "", 0, 0, "global"),
+        ];
+        assert_eq!(
+            GithubReporter.report(&findings),
+            "::error file=src/a%2Cb.js,line=3,col=5,title=HardCodedText::100%25 wrong%0Asecond line\n::warning title=StringConcatenation::global\n"
+        );
+    }
+}
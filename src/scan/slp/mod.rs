@@ -1,5 +1,6 @@
 //! SLP (Sisulizer Project) module
 
+#[allow(clippy::module_inception)]
 pub mod slp;
 
 pub use slp::*;
\ No newline at end of file
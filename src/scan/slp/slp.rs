@@ -1,14 +1,19 @@
-use colored::*;
 use regex::Regex;
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 
+use crate::error::{Error, Result};
+use crate::scan::key_strategy::{KeyPattern, KeyStrategyConfig};
+use crate::scan::ScanResult;
+
 /// 翻译行记录
 #[derive(Debug, Clone)]
 pub struct SourceRow {
     pub key: String,
+    /// 带 context 的行（如 `k_xxx_verb`）记录其 context
+    pub context: Option<String>,
     pub native_string: Option<String>,
     pub translate_map: HashMap<String, String>,
 }
@@ -17,6 +22,7 @@ impl SourceRow {
     pub fn new(key: String) -> Self {
         Self {
             key,
+            context: None,
             native_string: None,
             translate_map: HashMap::new(),
         }
@@ -28,6 +34,8 @@ impl SourceRow {
 pub struct SisulizerProjectOptions {
     pub native_lang: String,
     pub langs: Vec<String>,
+    /// 生成 key 的策略，用于识别 key 上的 context 与复数后缀
+    pub key_strategy: KeyStrategyConfig,
 }
 
 impl Default for SisulizerProjectOptions {
@@ -35,6 +43,7 @@ impl Default for SisulizerProjectOptions {
         Self {
             native_lang: "zh".to_string(),
             langs: vec!["en".to_string()],
+            key_strategy: KeyStrategyConfig::default(),
         }
     }
 }
@@ -49,6 +58,9 @@ pub struct SisulizerProject {
     row_map: HashMap<String, SourceRow>,
     /// 标记为废弃的行
     obsoleted_set: HashSet<String>,
+    /// 已知的带 context 的 key → context
+    contexts: HashMap<String, String>,
+    key_pattern: KeyPattern,
     /// 中文字符正则表达式
     chinese_regex: Regex,
 }
@@ -69,6 +81,8 @@ impl SisulizerProject {
             langs,
             row_map: HashMap::new(),
             obsoleted_set: HashSet::new(),
+            contexts: HashMap::new(),
+            key_pattern: options.key_strategy.pattern(),
             chinese_regex: Regex::new(r"[\u4e00-\u9fa5]").unwrap(),
         }
     }
@@ -106,14 +120,29 @@ impl SisulizerProject {
         }
     }
 
+    /// 添加带 context 的翻译数据，行的 Key 为 `base_key_context`
+    pub fn add_with_context(&mut self, base_key: &str, context: &str, lang: &str, translated_string: &str) {
+        let key = format!("{}_{}", base_key, context);
+        self.get_or_create_row(&key).context = Some(context.to_string());
+        self.contexts.insert(key.clone(), context.to_string());
+        self.add(&key, lang, translated_string);
+        // 句子与基础句子相同、未写入任何内容时，不保留空行
+        let row = &self.row_map[&key];
+        if row.native_string.is_none() && row.translate_map.is_empty() && lang != self.native_lang {
+            self.row_map.remove(&key);
+        }
+    }
+
     /// 获取翻译数据
     pub fn get(&self, key: &str, lang: &str) -> Option<String> {
-        let row = self.row_map.get(key)?;
+        let row = self.row_map.get(key);
         
+        // 复数形式的行可能没有本地语言句子，从同组的行中查找
         if lang == self.native_lang {
-            row.native_string.clone().or_else(|| self.try_find_native_string(key))
+            row.and_then(|row| row.native_string.clone())
+                .or_else(|| self.try_find_native_string(key))
         } else {
-            row.translate_map.get(lang).cloned()
+            row?.translate_map.get(lang).cloned()
         }
     }
 
@@ -127,47 +156,102 @@ impl SisulizerProject {
         self.obsoleted_set.insert(key.to_string());
     }
 
+    /// 合并另一个项目的词条，规则与 `add` 相同：先写入本地语言句子，再写入翻译；废弃标记取并集
+    pub fn merge(&mut self, other: &SisulizerProject) {
+        let mut keys = other.keys();
+        keys.sort();
+        for key in &keys {
+            let row = &other.row_map[key];
+            if let Some(context) = &row.context {
+                self.get_or_create_row(key).context = Some(context.clone());
+            }
+            if let Some(native_string) = &row.native_string {
+                let native_lang = self.native_lang.clone();
+                self.add(key, &native_lang, native_string);
+            }
+        }
+        for key in &keys {
+            let mut translations: Vec<_> = other.row_map[key].translate_map.iter().collect();
+            translations.sort();
+            for (lang, translated_string) in translations {
+                self.add(key, lang, translated_string);
+            }
+        }
+        self.obsoleted_set.extend(other.obsoleted_set.iter().cloned());
+        self.contexts.extend(other.contexts.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    /// 记录扫描结果中带 context 的 key，之后不再从 key 的文本中猜测 context
+    ///
+    /// 如 context 为 `one` 的 `k_0012abc_one` 不会被当作复数形式。
+    pub fn set_contexts(&mut self, result: &ScanResult) {
+        for (key, context) in &result.contexts {
+            if let Some(generated_key) = result.generated_keys.get(key) {
+                self.contexts.insert(generated_key.clone(), context.clone());
+            }
+        }
+    }
+
+    /// 得到不包含复数后缀的 key，带已知 context 的 key 保持不变
+    pub fn base_key(&self, key: &str) -> String {
+        if self.known_context(key).is_some() {
+            return key.to_string();
+        }
+        self.key_pattern.strip_plural(key).to_string()
+    }
+
+    /// 拆分带 context 的 key，如 `k_0012abc_verb` → `("k_0012abc", "verb")`
+    ///
+    /// 优先使用已知的 context，否则按 key 生成策略从文本中识别。
+    pub fn split_context_key(&self, key: &str) -> Option<(String, String)> {
+        if let Some(context) = self.known_context(key) {
+            let base_key = key.strip_suffix(context)?.strip_suffix('_')?;
+            return Some((base_key.to_string(), context.to_string()));
+        }
+        let (base_key, context) = self.key_pattern.split_context(key)?;
+        Some((base_key.to_string(), context.to_string()))
+    }
+
+    fn known_context(&self, key: &str) -> Option<&str> {
+        let context = self
+            .contexts
+            .get(key)
+            .or_else(|| self.row_map.get(key)?.context.as_ref())?;
+        key.ends_with(&format!("_{}", context)).then_some(context.as_str())
+    }
+
     /// 从指定的目录中加载数据
-    pub async fn load(&mut self, source_path: &str, lang: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn load(&mut self, source_path: &str, lang: &str) -> Result<()> {
         let file_path = Path::new(source_path).join(format!("{}.json", lang));
         
-        match fs::read_to_string(&file_path) {
-            Ok(content) => {
-                let data: Value = serde_json::from_str(&content)?;
-                
-                if let Some(obj) = data.as_object() {
-                    for (key, value) in obj {
-                        if let Some(translated_string) = value.as_str() {
-                            let native_string = self.get(key, &self.native_lang.clone());
-                            
-                            if lang == self.native_lang {
-                                let row = self.get_or_create_row(key);
-                                row.native_string = Some(translated_string.to_string());
-                            }
-                            // 认为 json 中一定是翻译后的词条，不进行 is_same_sentence 比较
-                            else if !translated_string.is_empty() && native_string.is_some() {
-                                // 检查非中文词条中是否包含中文
-                                if lang != "zh" && self.chinese_regex.is_match(translated_string) {
-                                    return Err(format!(
-                                        "{}\n  {}\n{}",
-                                        format!("\n\n{}.json 词条中发现中文：\n", lang).red(),
-                                        translated_string,
-                                        "请检查词条文件\n".red()
-                                    ).into());
-                                }
-                                let row = self.get_or_create_row(key);
-                                row.translate_map.insert(lang.to_string(), translated_string.to_string());
-                            }
+        let content = fs::read_to_string(&file_path).map_err(|e| Error::file(&file_path, e))?;
+        let data: Value = serde_json::from_str(&content)?;
+
+        if let Some(obj) = data.as_object() {
+            for (key, value) in obj {
+                if let Some(translated_string) = value.as_str() {
+                    let native_string = self.get(key, &self.native_lang.clone());
+
+                    if lang == self.native_lang {
+                        let row = self.get_or_create_row(key);
+                        row.native_string = Some(translated_string.to_string());
+                    }
+                    // 认为 json 中一定是翻译后的词条，不进行 is_same_sentence 比较
+                    else if !translated_string.is_empty() && native_string.is_some() {
+                        // 检查非中文词条中是否包含中文
+                        if lang != "zh" && self.chinese_regex.is_match(translated_string) {
+                            return Err(Error::Resource {
+                                path: file_path,
+                                message: format!("词条中发现中文，请检查词条文件：{}", translated_string),
+                            });
                         }
+                        let row = self.get_or_create_row(key);
+                        row.translate_map.insert(lang.to_string(), translated_string.to_string());
                     }
                 }
-                Ok(())
-            }
-            Err(error) => {
-                eprintln!("Error loading file {}: {}", file_path.display(), error);
-                std::process::exit(1);
             }
         }
+        Ok(())
     }
 
     /// 导出要保存的 JSON 内容
@@ -179,7 +263,7 @@ impl SisulizerProject {
         sorted_keys.sort();
         
         for key in sorted_keys {
-            let base_key = get_base_key(&key);
+            let base_key = self.base_key(&key);
             if !self.obsoleted_set.contains(&base_key) {
                 if let Some(value) = self.get(&key, target_lang) {
                     json_obj.insert(key, Value::String(value));
@@ -191,18 +275,18 @@ impl SisulizerProject {
     }
 
     /// 输出到目录结构 (新增方法)
-    pub fn output_to_directory(&self, output_dir: &str, lang: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn output_to_directory(&self, output_dir: &str, lang: Option<&str>) -> Result<()> {
         let target_lang = lang.unwrap_or(&self.native_lang);
         let output_path = Path::new(output_dir);
         
         // 创建输出目录
-        fs::create_dir_all(output_path)?;
+        fs::create_dir_all(output_path).map_err(|e| Error::file(output_path, e))?;
         
         // 创建 context 和 source 子目录
         let context_dir = output_path.join("context");
         let source_dir = output_path.join("source");
-        fs::create_dir_all(&context_dir)?;
-        fs::create_dir_all(&source_dir)?;
+        fs::create_dir_all(&context_dir).map_err(|e| Error::file(&context_dir, e))?;
+        fs::create_dir_all(&source_dir).map_err(|e| Error::file(&source_dir, e))?;
         
         // 生成 context.json
         let stats = self.get_stats();
@@ -219,7 +303,7 @@ impl SisulizerProject {
         });
         
         let context_file = context_dir.join("context.json");
-        fs::write(context_file, serde_json::to_string_pretty(&context_data)?)?;
+        fs::write(&context_file, serde_json::to_string_pretty(&context_data)?).map_err(|e| Error::file(&context_file, e))?;
         
         // 生成 source/zh.json (或其他语言) - 使用哈希键
         let mut json_obj = serde_json::Map::new();
@@ -227,7 +311,7 @@ impl SisulizerProject {
         sorted_keys.sort();
         
         for key in sorted_keys {
-            let base_key = get_base_key(&key);
+            let base_key = self.base_key(&key);
             if !self.obsoleted_set.contains(&base_key) {
                 if let Some(value) = self.get(&key, target_lang) {
                     // 生成哈希键
@@ -238,18 +322,27 @@ impl SisulizerProject {
         }
         
         let source_file = source_dir.join(format!("{}.json", target_lang));
-        fs::write(source_file, serde_json::to_string_pretty(&json_obj)?)?;
+        fs::write(&source_file, serde_json::to_string_pretty(&json_obj)?).map_err(|e| Error::file(&source_file, e))?;
         
         Ok(())
     }
 
     /// 翻译的词条 Key 可能是带上下文或者带复数形式的
     fn try_find_native_string(&self, key: &str) -> Option<String> {
-        let base_key = get_base_key(key);
-        let mut possible_keys = vec![base_key.clone()];
-        
-        // 中文复数形式
-        possible_keys.push(format!("{}_{}", base_key, "0"));
+        let base_key = self.base_key(key);
+        let mut base_keys = vec![base_key.clone()];
+        // 带 context 的行回退到不带 context 的基础句子
+        if let Some((context_base_key, _)) = self.split_context_key(&base_key) {
+            base_keys.push(context_base_key);
+        }
+
+        let mut possible_keys = Vec::new();
+        for base_key in base_keys {
+            possible_keys.push(base_key.clone());
+            // 中文复数形式（v3 为 `_0`，v4 为 `_other`）
+            possible_keys.push(format!("{}_{}", base_key, "0"));
+            possible_keys.push(format!("{}_{}", base_key, "other"));
+        }
         
         for possible_key in possible_keys {
             if let Some(row) = self.row_map.get(&possible_key) {
@@ -295,17 +388,35 @@ impl SisulizerProject {
         self.obsoleted_set.clear();
     }
 
-    /// 获取统计信息
+    /// 获取与指定 Key 属于同一复数组的所有 Key（含自身），按字母顺序排列
+    pub fn plural_group(&self, key: &str) -> Vec<String> {
+        let base_key = self.base_key(key);
+        let mut keys: Vec<String> = self
+            .row_map
+            .keys()
+            .filter(|k| self.base_key(k) == base_key)
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// 获取统计信息，同一复数组的多个 Key 按一个句子计算
     pub fn get_stats(&self) -> ProjectStats {
-        let total_keys = self.row_map.len();
-        let obsoleted_keys = self.obsoleted_set.len();
+        let mut groups: HashMap<String, Vec<&SourceRow>> = HashMap::new();
+        for (key, row) in &self.row_map {
+            groups.entry(self.base_key(key)).or_default().push(row);
+        }
+
+        let total_keys = groups.len();
+        let obsoleted_keys = groups.keys().filter(|base| self.obsoleted_set.contains(*base)).count();
         let active_keys = total_keys - obsoleted_keys;
         
         let mut lang_stats = HashMap::new();
         for lang in &self.langs {
-            let translated_count = self.row_map
+            let translated_count = groups
                 .values()
-                .filter(|row| row.translate_map.contains_key(lang))
+                .filter(|rows| rows.iter().any(|row| row.translate_map.contains_key(lang)))
                 .count();
             lang_stats.insert(lang.clone(), translated_count);
         }
@@ -328,17 +439,6 @@ pub struct ProjectStats {
     pub lang_stats: HashMap<String, usize>,
 }
 
-/// 得到不包含单复数形式的 Key
-pub fn get_base_key(key: &str) -> String {
-    let regex = Regex::new(r"^(k_.+)_(\d|plural)$").unwrap();
-    
-    if let Some(captures) = regex.captures(key) {
-        captures.get(1).map_or(key.to_string(), |m| m.as_str().to_string())
-    } else {
-        key.to_string()
-    }
-}
-
 /// 创建 Sisulizer 项目
 pub fn create_sisulizer_project(options: Option<SisulizerProjectOptions>) -> SisulizerProject {
     SisulizerProject::new(options)
@@ -353,16 +453,61 @@ pub fn create_default_sisulizer_project() -> SisulizerProject {
 mod tests {
     use super::*;
     use std::fs;
-    use std::path::Path;
     use tempfile::TempDir;
 
     #[test]
-    fn test_get_base_key() {
-        assert_eq!(get_base_key("k_hello_world"), "k_hello_world");
-        assert_eq!(get_base_key("k_hello_world_0"), "k_hello_world");
-        assert_eq!(get_base_key("k_hello_world_plural"), "k_hello_world");
-        assert_eq!(get_base_key("k_hello_world_1"), "k_hello_world");
-        assert_eq!(get_base_key("normal_key"), "normal_key");
+    fn test_base_key() {
+        let project = SisulizerProject::new(None);
+        assert_eq!(project.base_key("k_hello_world"), "k_hello_world");
+        assert_eq!(project.base_key("k_hello_world_0"), "k_hello_world");
+        assert_eq!(project.base_key("k_hello_world_plural"), "k_hello_world");
+        assert_eq!(project.base_key("k_hello_world_1"), "k_hello_world");
+        assert_eq!(project.base_key("k_hello_world_one"), "k_hello_world");
+        assert_eq!(project.base_key("k_hello_world_other"), "k_hello_world");
+        assert_eq!(project.base_key("normal_key"), "normal_key");
+    }
+
+    #[test]
+    fn test_key_strategy_prefix() {
+        let mut project = SisulizerProject::new(Some(SisulizerProjectOptions {
+            key_strategy: KeyStrategyConfig::Hash64 {
+                prefix: "app.".to_string(),
+                length: 8,
+            },
+            ..Default::default()
+        }));
+        project.add("app.0012abcd", "zh", "打开");
+        project.add_with_context("app.0012abcd", "verb", "en", "Open");
+        project.add("app.0034efgh_other", "zh", "{{count}} 项");
+
+        assert_eq!(project.split_context_key("app.0012abcd_menu"), Some(("app.0012abcd".to_string(), "menu".to_string())));
+        assert_eq!(project.get("app.0012abcd_verb", "zh"), Some("打开".to_string()));
+        assert_eq!(project.get("app.0034efgh_one", "zh"), Some("{{count}} 项".to_string()));
+        assert_eq!(project.base_key("k_0012abc_one"), "k_0012abc_one");
+    }
+
+    #[test]
+    fn test_contexts_named_like_plural_forms() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("app.js");
+        fs::write(&app, "t('文件', { context: 'one' });\nt('文件', { context: 'other' });\n").unwrap();
+        let result = crate::scan::Scanner::new()
+            .scan_files(&[app.to_string_lossy().to_string()])
+            .unwrap();
+
+        let mut project = SisulizerProject::new(None);
+        project.set_contexts(&result);
+        for (key, generated_key) in &result.generated_keys {
+            project.add(generated_key, "zh", crate::scan::context::strip_context(key, result.contexts.get(key).map(|c| c.as_str())));
+        }
+
+        let keys = project.keys();
+        assert_eq!(keys.len(), 2);
+        for key in &keys {
+            assert_eq!(&project.base_key(key), key);
+            assert_eq!(project.plural_group(key), vec![key.clone()]);
+        }
+        assert_eq!(project.get_stats().total_keys, 2);
     }
 
     #[test]
@@ -427,8 +572,8 @@ mod tests {
         assert!(parsed.get("farewell").is_none()); // 应该被过滤掉
     }
 
-    #[tokio::test]
-    async fn test_load() {
+    #[test]
+    fn test_load() {
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
         
@@ -442,16 +587,16 @@ mod tests {
         let mut project = SisulizerProject::new(None);
         
         // 加载中文
-        project.load(temp_path.to_str().unwrap(), "zh").await.unwrap();
+        project.load(temp_path.to_str().unwrap(), "zh").unwrap();
         assert_eq!(project.get("greeting", "zh"), Some("你好".to_string()));
         
         // 加载英文
-        project.load(temp_path.to_str().unwrap(), "en").await.unwrap();
+        project.load(temp_path.to_str().unwrap(), "en").unwrap();
         assert_eq!(project.get("greeting", "en"), Some("Hello".to_string()));
     }
 
-    #[tokio::test]
-    async fn test_load_with_chinese_in_english() {
+    #[test]
+    fn test_load_with_chinese_in_english() {
         let temp_dir = TempDir::new().unwrap();
         let temp_path = temp_dir.path();
         
@@ -463,11 +608,42 @@ mod tests {
         fs::write(temp_path.join("zh.json"), zh_content).unwrap();
         
         let mut project = SisulizerProject::new(None);
-        project.load(temp_path.to_str().unwrap(), "zh").await.unwrap();
+        project.load(temp_path.to_str().unwrap(), "zh").unwrap();
         
         // 加载包含中文的英文文件应该出错
-        let result = project.load(temp_path.to_str().unwrap(), "en").await;
-        assert!(result.is_err());
+        let result = project.load(temp_path.to_str().unwrap(), "en");
+        assert!(matches!(result, Err(Error::Resource { .. })));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut project = SisulizerProject::new(None);
+
+        // 文件不存在时返回错误，而不是退出进程
+        let result = project.load(temp_dir.path().to_str().unwrap(), "ja");
+        assert!(matches!(result, Err(Error::File { .. })));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut project = SisulizerProject::new(None);
+        project.add("k_a", "zh", "保存");
+        project.add("k_a", "en", "Save");
+
+        let mut other = SisulizerProject::new(None);
+        other.add("k_a", "zh", "保存");
+        other.add("k_a", "en", "Save changes");
+        other.add("k_b", "zh", "取消");
+        other.add("k_b", "en", "Cancel");
+        other.add("k_c", "en", "Orphan");
+        other.obsolete("k_d");
+
+        project.merge(&other);
+        assert_eq!(project.get("k_a", "en").as_deref(), Some("Save changes"));
+        assert_eq!(project.get("k_b", "en").as_deref(), Some("Cancel"));
+        assert!(!project.has_key("k_c"));
+        assert!(project.get_obsoleted_keys().contains("k_d"));
     }
 
     #[test]
@@ -484,4 +660,33 @@ mod tests {
         assert_eq!(stats.obsoleted_keys, 1);
         assert_eq!(stats.lang_stats.get("en"), Some(&1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_plural_rows() {
+        let mut project = SisulizerProject::new(None);
+        project.add("k_items_0", "zh", "共{{count}}项");
+        project.add("k_items", "en", "{{count}} item");
+        project.add("k_items_plural", "en", "{{count}} items");
+        project.add("k_other", "zh", "其他");
+
+        assert_eq!(project.get("k_items_plural", "zh"), Some("共{{count}}项".to_string()));
+        assert_eq!(project.plural_group("k_items"), vec!["k_items", "k_items_0", "k_items_plural"]);
+
+        let stats = project.get_stats();
+        assert_eq!(stats.total_keys, 2);
+        assert_eq!(stats.lang_stats.get("en"), Some(&1));
+    }
+
+    #[test]
+    fn test_context_rows() {
+        let mut project = SisulizerProject::new(None);
+        project.add("k_0012abc", "zh", "打开");
+        project.add_with_context("k_0012abc", "verb", "en", "Open");
+        project.add_with_context("k_0012abc", "status", "en", "Opened");
+
+        assert_eq!(project.split_context_key("k_0012abc_verb"), Some(("k_0012abc".to_string(), "verb".to_string())));
+        assert_eq!(project.get("k_0012abc_verb", "zh"), Some("打开".to_string()));
+        assert_eq!(project.get("k_0012abc_status", "en"), Some("Opened".to_string()));
+        assert_eq!(project.row_map["k_0012abc_verb"].context.as_deref(), Some("verb"));
+    }
+}
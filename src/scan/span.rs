@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+use super::diff::display_width;
+use super::parse_func_from_string_by_babel::offset_to_line_column;
+use super::zh_linter::Location;
+
+/// 源码中的一段范围，行列号从 1 开始，列按字符计算，结束位置不包含在内
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Span {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl Span {
+    pub fn new(start_line: u32, start_column: u32, end_line: u32, end_column: u32) -> Span {
+        Span {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
+
+    /// 只有起始位置的范围
+    pub fn point(line: u32, column: u32) -> Span {
+        Span::new(line, column, line, column)
+    }
+
+    /// 由字节偏移 `start..end` 计算范围
+    pub fn from_offsets(content: &str, start: usize, end: usize) -> Span {
+        let (start_line, start_column) = offset_to_line_column(content, start);
+        let (end_line, end_column) = offset_to_line_column(content, end.max(start));
+        Span::new(start_line, start_column, end_line, end_column)
+    }
+
+    /// 是否尚未设置位置（如手动构造的节点）
+    pub fn is_unknown(&self) -> bool {
+        self.start_line == 0
+    }
+}
+
+impl From<&Location> for Span {
+    fn from(loc: &Location) -> Span {
+        Span::new(loc.start.line, loc.start.column, loc.end.line, loc.end.column)
+    }
+}
+
+/// 源码中无法解析的片段
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceError {
+    pub message: String,
+    pub span: Span,
+}
+
+/// 显示范围所在的行及其前后各一行，并用 `^` 标出范围
+///
+/// 范围跨多行时每行分别标出；行号超出范围时返回 None。
+pub fn code_frame(source: &str, span: &Span) -> Option<String> {
+    let lines: Vec<&str> = source.lines().collect();
+    let first_index = (span.start_line as usize).checked_sub(1).filter(|index| *index < lines.len())?;
+    let last_index = (span.end_line.max(span.start_line) as usize - 1).min(lines.len() - 1);
+    let first = first_index.saturating_sub(1);
+    let last = (last_index + 1).min(lines.len() - 1);
+    let width = (last + 1).to_string().len();
+
+    let mut out = String::new();
+    for (i, text) in lines.iter().enumerate().take(last + 1).skip(first) {
+        let marked = i >= first_index && i <= last_index;
+        let marker = if marked { '>' } else { ' ' };
+        out.push_str(format!("{} {:>width$} | {}", marker, i + 1, text).trim_end());
+        out.push('\n');
+        if !marked {
+            continue;
+        }
+
+        let chars: Vec<char> = text.chars().collect();
+        let start = if i == first_index {
+            span.start_column.saturating_sub(1) as usize
+        } else {
+            chars.iter().take_while(|c| c.is_whitespace()).count()
+        }
+        .min(chars.len());
+        let end = if i == last_index && span.end_line >= span.start_line {
+            span.end_column.saturating_sub(1) as usize
+        } else {
+            chars.len()
+        }
+        .clamp(start, chars.len());
+
+        // 制表符原样保留、全角字符占两列，使 `^` 与源码对齐
+        let padding: String = chars[..start]
+            .iter()
+            .map(|c| if *c == '\t' { "\t".to_string() } else { " ".repeat(display_width(&c.to_string())) })
+            .collect();
+        let underline = display_width(&chars[start..end].iter().collect::<String>()).max(1);
+        out.push_str(&format!("  {:>width$} | {}{}\n", "", padding, "^".repeat(underline)));
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_from_offsets() {
+        let content = "const a = 1;\nt('保存', { ns: 'x' });\n";
+        let start = content.find("{ ns").unwrap();
+        let span = Span::from_offsets(content, start, start + "{ ns: 'x' }".len());
+        assert_eq!(span, Span::new(2, 9, 2, 20));
+    }
+
+    #[test]
+    fn test_code_frame() {
+        let source = "const a = 1;\nconst b = '保存';\n\tfoo();\n";
+        assert_eq!(
+            code_frame(source, &Span::new(2, 11, 2, 15)).unwrap(),
+            "  1 | const a = 1;\n> 2 | const b = '保存';\n    |           ^^^^^^\n  3 | \tfoo();\n"
+        );
+        assert_eq!(code_frame(source, &Span::point(3, 2)).unwrap(), "  2 | const b = '保存';\n> 3 | \tfoo();\n    | \t^\n");
+        assert_eq!(code_frame(source, &Span::point(0, 0)), None);
+        assert_eq!(code_frame(source, &Span::point(9, 1)), None);
+    }
+
+    #[test]
+    fn test_code_frame_multiline() {
+        let source = "t('k', {\n  ns: 'x',\n";
+        assert_eq!(
+            code_frame(source, &Span::new(1, 8, 2, 11)).unwrap(),
+            "> 1 | t('k', {\n    |        ^\n> 2 |   ns: 'x',\n    |   ^^^^^^^^\n"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use super::config::{FuncConfig, ScanConfig};
+use super::front_end::{extract_func_calls, FrontEnd};
+use super::parse_func_from_string_by_babel::KeyOccurrence;
+use super::span::SourceError;
+use super::vue::{blank_sfc, component_bindings, mask_sfc, split_sfc, SfcBlockKind};
+use super::zh_linter::ZhLinter;
+
+/// svelte-i18n 以 store 形式提供的翻译函数，扫描 Svelte 文件时自动加入函数列表
+pub const SVELTE_STORES: [&str; 3] = ["$_", "$t", "$format"];
+
+/// Svelte 组件前端
+///
+/// `<script>`（包括 `context="module"`）走 JS 提取流程；`<script>` 与 `<style>` 之外的标记中提取
+/// `{$_('key')}`、`{$t('key')}` 等调用，并检查 `{ }` 表达式之外未翻译的中文文本。
+pub struct SvelteFrontEnd;
+
+impl FrontEnd for SvelteFrontEnd {
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence> {
+        self.extract_with_errors(content, filepath, config).0
+    }
+
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        let func = with_stores(&config.func);
+        let (script, markup) = split_component(content);
+
+        let (mut occurrences, script_bindings, mut errors) = extract_func_calls(&script, filepath, &func, &[]);
+        let markup_bindings = component_bindings(script_bindings, script.len());
+        let (markup_occurrences, _, markup_errors) = extract_func_calls(&markup, filepath, &func, &markup_bindings);
+        occurrences.extend(markup_occurrences);
+        errors.extend(markup_errors);
+
+        (occurrences, errors)
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, _config: &ScanConfig) {
+        let (script, markup) = split_component(content);
+        linter.verify(&script, filepath);
+        linter.verify_markup(&markup, filepath, Some(("{", "}")));
+    }
+}
+
+/// 拆分为与原文件行列对应的（脚本，标记）两部分
+fn split_component(content: &str) -> (String, String) {
+    let blocks = split_sfc(content);
+    let script = mask_sfc(content, &blocks, SfcBlockKind::Script);
+    let markup = blank_sfc(content, &blocks, &[SfcBlockKind::Script, SfcBlockKind::Style]);
+    (script, markup)
+}
+
+fn with_stores(func: &FuncConfig) -> FuncConfig {
+    let mut func = func.clone();
+    for store in SVELTE_STORES {
+        if !func.list.iter().any(|f| f == store) {
+            func.list.push(store.to_string());
+        }
+    }
+    func
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan::zh_linter::get_result;
+
+    const COMPONENT: &str = r#"<script>
+  import { _ } from 'svelte-i18n';
+  const title = $_('page.title');
+</script>
+
+<h1>{$_('page.heading', { values: { name } })}</h1>
+<p>{$t("page.body")} 未翻译 {#if ok}<span>{ { a: 1 }.a }</span>{/if}</p>
+<style>
+  h1 { color: red; }
+</style>
+"#;
+
+    #[test]
+    fn test_svelte_extract() {
+        let occurrences = SvelteFrontEnd.extract(COMPONENT, "Page.svelte", &ScanConfig::default());
+        let found: Vec<_> = occurrences.iter().map(|o| (o.key.as_str(), o.line, o.column)).collect();
+        assert_eq!(
+            found,
+            vec![("page.title", 3, 20), ("page.heading", 6, 9), ("page.body", 7, 8)]
+        );
+    }
+
+    #[test]
+    fn test_svelte_lint_markup() {
+        let _guard = crate::scan::lock_scan();
+        SvelteFrontEnd.lint(&ZhLinter::new(), COMPONENT, "lint-test.svelte", &ScanConfig::default());
+        let results: Vec<_> = get_result()
+            .into_iter()
+            .filter(|r| r.filepath == "lint-test.svelte")
+            .collect();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].value, "未翻译");
+        assert_eq!((results[0].loc.start.line, results[0].loc.start.column), (7, 22));
+    }
+
+    #[test]
+    fn test_svelte_reports_parse_errors() {
+        let content = "<script>\n  const a = $_('a', { values: 1 );\n</script>\n<p>{$t('b', { values: 2 )}</p>\n";
+        let (occurrences, errors) = SvelteFrontEnd.extract_with_errors(content, "Page.svelte", &ScanConfig::default());
+        assert_eq!(occurrences.len(), 2);
+        let spans: Vec<_> = errors.iter().map(|e| (e.span.start_line, e.span.start_column)).collect();
+        assert_eq!(spans, vec![(2, 21), (4, 13)]);
+    }
+}
@@ -0,0 +1,293 @@
+use regex::Regex;
+
+use super::bindings::TranslationBinding;
+use super::config::{FuncConfig, ScanConfig};
+use super::front_end::{extract_func_calls, FrontEnd};
+use super::parse_func_from_string_by_babel::{offset_to_line_column, KeyOccurrence, ParseOptions};
+use super::span::SourceError;
+use super::zh_linter::ZhLinter;
+
+/// 单文件组件中的顶层块类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SfcBlockKind {
+    Template,
+    Script,
+    Style,
+}
+
+/// 单文件组件中的顶层块，`start..end` 为块内容（不含标签）在原文件中的字节范围
+#[derive(Debug, Clone)]
+pub struct SfcBlock {
+    pub kind: SfcBlockKind,
+    /// `<script setup>`
+    pub setup: bool,
+    /// `lang` 属性，如 `ts`、`pug`
+    pub lang: Option<String>,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// 拆分单文件组件的顶层 `<template>`、`<script>`、`<style>` 块
+///
+/// 模板中嵌套的 `<template>` 按深度匹配，顶层注释与自定义块（如 `<i18n>`）被忽略。
+pub fn split_sfc(content: &str) -> Vec<SfcBlock> {
+    let open = Regex::new(r"<!--|<(template|script|style)\b").unwrap();
+    let setup = Regex::new(r"(?:^|\s)setup(?:\s|=|$)").unwrap();
+    let lang = Regex::new(r#"\blang\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(captures) = open.captures_at(content, pos) {
+        let whole = captures.get(0).unwrap();
+        let name = match captures.get(1) {
+            Some(name) => name.as_str(),
+            None => {
+                pos = find_from(content, whole.end(), "-->").map_or(content.len(), |i| i + 3);
+                continue;
+            }
+        };
+        let open_end = match tag_end(&content[whole.end()..]) {
+            Some(i) => whole.end() + i,
+            None => break,
+        };
+        let attrs = &content[whole.end()..open_end];
+        let start = open_end + 1;
+        if attrs.trim_end().ends_with('/') {
+            pos = start;
+            continue;
+        }
+
+        let end = if name == "template" {
+            element_close(content, start, "template")
+        } else {
+            find_from(content, start, &format!("</{}", name)).unwrap_or(content.len())
+        };
+        let kind = match name {
+            "template" => SfcBlockKind::Template,
+            "script" => SfcBlockKind::Script,
+            _ => SfcBlockKind::Style,
+        };
+        blocks.push(SfcBlock {
+            kind,
+            setup: kind == SfcBlockKind::Script && setup.is_match(attrs),
+            lang: lang
+                .captures(attrs)
+                .and_then(|c| c.get(1).or_else(|| c.get(2)))
+                .map(|m| m.as_str().to_string()),
+            start,
+            end,
+        });
+        pos = find_from(content, end, ">").map_or(content.len(), |i| i + 1);
+    }
+
+    blocks
+}
+
+/// 保留指定类型的块，其余字符替换为空格、换行保持不变
+///
+/// 结果与原文件的行列号一一对应，可直接交给按行列报告位置的提取器与 Linter。
+pub fn mask_sfc(content: &str, blocks: &[SfcBlock], kind: SfcBlockKind) -> String {
+    mask_with(content, |i| blocks.iter().any(|b| b.kind == kind && (b.start..b.end).contains(&i)))
+}
+
+/// 与 `mask_sfc` 相反：把指定类型的块替换为空格，保留其余内容（如 Svelte 的标记部分）
+pub fn blank_sfc(content: &str, blocks: &[SfcBlock], kinds: &[SfcBlockKind]) -> String {
+    mask_with(content, |i| {
+        !blocks
+            .iter()
+            .any(|b| kinds.contains(&b.kind) && (b.start..b.end).contains(&i))
+    })
+}
+
+/// 保留 `keep` 返回 true 的字符，其余替换为空格，换行保持不变
+pub(crate) fn mask_with(content: &str, keep: impl Fn(usize) -> bool) -> String {
+    content
+        .char_indices()
+        .map(|(i, ch)| if ch == '\n' || ch == '\r' || keep(i) { ch } else { ' ' })
+        .collect()
+}
+
+/// Vue 单文件组件前端
+pub struct VueFrontEnd;
+
+impl FrontEnd for VueFrontEnd {
+    fn extract(&self, content: &str, filepath: &str, config: &ScanConfig) -> Vec<KeyOccurrence> {
+        extract_sfc(content, filepath, &config.func)
+    }
+
+    fn extract_with_errors(&self, content: &str, filepath: &str, config: &ScanConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+        extract_sfc_with_errors(content, filepath, &config.func)
+    }
+
+    fn lint(&self, linter: &ZhLinter, content: &str, filepath: &str, _config: &ScanConfig) {
+        lint_sfc(linter, content, filepath);
+    }
+}
+
+/// 提取单文件组件中的 key
+///
+/// `<script>` 与 `<script setup>` 走 JS 提取流程；模板中提取 `$t('key')`、`t('key')` 与 `v-t` 指令，
+/// 模板可以使用 `<script setup>` 顶层声明的绑定（如 `const { t } = useI18n()`）。
+pub fn extract_sfc(content: &str, filepath: &str, func: &FuncConfig) -> Vec<KeyOccurrence> {
+    extract_sfc_with_errors(content, filepath, func).0
+}
+
+/// 同 `extract_sfc`，并返回脚本与模板中无法解析的选项对象
+pub fn extract_sfc_with_errors(content: &str, filepath: &str, func: &FuncConfig) -> (Vec<KeyOccurrence>, Vec<SourceError>) {
+    let blocks = split_sfc(content);
+
+    let script = mask_sfc(content, &blocks, SfcBlockKind::Script);
+    let (mut occurrences, script_bindings, mut errors) = extract_func_calls(&script, filepath, func, &[]);
+
+    let template_bindings = component_bindings(script_bindings, script.len());
+    let template = mask_sfc(content, &blocks, SfcBlockKind::Template);
+    let (template_occurrences, _, template_errors) = extract_func_calls(&template, filepath, func, &template_bindings);
+    occurrences.extend(template_occurrences);
+    occurrences.extend(parse_v_t_directives(&template));
+    errors.extend(template_errors);
+
+    (occurrences, errors)
+}
+
+/// 脚本顶层声明的绑定在整个组件（包括模板）中可用
+pub(crate) fn component_bindings(script_bindings: Vec<TranslationBinding>, script_len: usize) -> Vec<TranslationBinding> {
+    script_bindings
+        .into_iter()
+        .filter(|binding| binding.end == script_len)
+        .map(|binding| TranslationBinding { start: 0, ..binding })
+        .collect()
+}
+
+/// 检查单文件组件：脚本块按 JS 规则检查，模板检查未翻译的中文文本
+pub fn lint_sfc(linter: &ZhLinter, content: &str, filepath: &str) {
+    let blocks = split_sfc(content);
+    linter.verify(&mask_sfc(content, &blocks, SfcBlockKind::Script), filepath);
+    linter.verify_template(&mask_sfc(content, &blocks, SfcBlockKind::Template), filepath);
+}
+
+/// 提取 vue-i18n 的 `v-t="'key'"` 与 `v-t="{ path: 'key' }"`
+pub fn parse_v_t_directives(template: &str) -> Vec<KeyOccurrence> {
+    let directive = Regex::new(r#"\bv-t\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    let literal = Regex::new(r#"^\s*(?:'([^']*)'|"([^"]*)"|`([^`$]*)`)\s*$"#).unwrap();
+    let path = Regex::new(r#"\bpath\s*:\s*(?:'([^']*)'|"([^"]*)"|`([^`$]*)`)"#).unwrap();
+    let mut occurrences = Vec::new();
+
+    for captures in directive.captures_iter(template) {
+        let value = match captures.get(1).or_else(|| captures.get(2)) {
+            Some(value) => value,
+            None => continue,
+        };
+        let expression = value.as_str();
+        let key = literal
+            .captures(expression)
+            .or_else(|| path.captures(expression))
+            .and_then(|c| (1..=3).find_map(|i| c.get(i)));
+        let key = match key {
+            Some(key) if !key.as_str().trim().is_empty() => key,
+            _ => continue,
+        };
+        // 位置指向 key 的引号
+        let (line, column) = offset_to_line_column(template, value.start() + key.start() - 1);
+        occurrences.push(KeyOccurrence {
+            key: key.as_str().trim().to_string(),
+            options: ParseOptions::default(),
+            line,
+            column,
+        });
+    }
+
+    occurrences
+}
+
+fn find_from(content: &str, from: usize, pattern: &str) -> Option<usize> {
+    content[from..].find(pattern).map(|i| from + i)
+}
+
+/// 开始标签中结束的 `>`，跳过引号中的内容
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    for (i, ch) in s.char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '>' => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+/// 与 `start` 之前的开始标签匹配的 `</name>` 的位置，同名元素按深度匹配，未闭合时为文件末尾
+pub(crate) fn element_close(content: &str, start: usize, name: &str) -> usize {
+    let tag = Regex::new(&format!(r"<{0}\b[^>]*>|</{0}\s*>", regex::escape(name))).unwrap();
+    let mut depth = 0usize;
+    for mat in tag.find_iter(&content[start..]) {
+        if mat.as_str().starts_with("</") {
+            if depth == 0 {
+                return start + mat.start();
+            }
+            depth -= 1;
+        } else if !mat.as_str().ends_with("/>") {
+            depth += 1;
+        }
+    }
+    content.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SFC: &str = r#"<template>
+  <div :title="$t('title')">
+    <template v-if="ok"><span v-t="'hello'"></span></template>
+    <p v-t="{ path: 'path.key', args: { n: 1 } }">{{ t('setup.key') }}</p>
+  </div>
+</template>
+
+<script setup lang="ts">
+const { t } = useI18n();
+const label = t('script.key');
+</script>
+
+<style>
+.a { color: red; }
+</style>
+"#;
+
+    #[test]
+    fn test_split_sfc() {
+        let blocks = split_sfc(SFC);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].kind, SfcBlockKind::Template);
+        assert!(SFC[blocks[0].start..blocks[0].end].trim_end().ends_with("</div>"));
+        assert!(blocks[1].setup);
+        assert_eq!(blocks[1].lang.as_deref(), Some("ts"));
+        assert_eq!(blocks[2].kind, SfcBlockKind::Style);
+    }
+
+    #[test]
+    fn test_extract_sfc_keeps_locations() {
+        let occurrences = extract_sfc(SFC, "App.vue", &FuncConfig::default());
+        let position = |key: &str| {
+            let o = occurrences.iter().find(|o| o.key == key).unwrap();
+            (o.line, o.column)
+        };
+        assert_eq!(position("title"), (2, 19));
+        assert_eq!(position("hello"), (3, 36));
+        assert_eq!(position("path.key"), (4, 21));
+        assert_eq!(position("setup.key"), (4, 56));
+        assert_eq!(position("script.key"), (10, 17));
+    }
+
+    #[test]
+    fn test_extract_sfc_reports_parse_errors() {
+        let content = "<template>\n  <p>{{ $t('a', { ns: 'x' ) }}</p>\n</template>\n<script>\nconst b = t('b', { ns: 'y' );\n</script>\n";
+        let (occurrences, errors) = VueFrontEnd.extract_with_errors(content, "App.vue", &ScanConfig::default());
+        let keys: Vec<_> = occurrences.iter().map(|o| o.key.as_str()).collect();
+        assert_eq!(keys, vec!["b", "a"]);
+        let spans: Vec<_> = errors.iter().map(|e| (e.span.start_line, e.span.start_column)).collect();
+        assert_eq!(spans, vec![(5, 18), (2, 17)]);
+    }
+}
@@ -0,0 +1,326 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::Result;
+use super::config::ScanConfig;
+use super::config_check::ConfigDiagnostic;
+use super::diff::push_section;
+use super::globs::resolve_files;
+use super::js_config::JsConfig;
+use super::writer::{resource_coverage, write_resources, ResourceCoverage};
+use super::Scanner;
+
+/// 工作区模式下识别的包配置文件名，同一目录有多个时按此顺序取第一个
+pub const CONFIG_FILE_NAMES: &[&str] = &[
+    "i18n.config.js",
+    "i18n.config.cjs",
+    "i18n.config.mjs",
+    "i18n.config.json",
+    "i18n.config.jsonc",
+    "i18n.config.toml",
+];
+
+/// monorepo 中带有自己配置文件的包
+#[derive(Debug, Clone)]
+pub struct WorkspacePackage {
+    /// 相对于根配置所在目录的包路径，如 `packages/admin`
+    pub name: String,
+    /// 包目录，可直接用于文件操作
+    pub dir: String,
+    pub config_path: String,
+    /// 包配置，其中的相对路径已转换为相对于包目录
+    pub config: ScanConfig,
+    pub diagnostics: Vec<ConfigDiagnostic>,
+}
+
+/// 按根配置中的 `workspaces` 查找各包的配置文件，没有配置文件的包不参与扫描
+pub fn discover_packages<P: AsRef<Path>>(root_config: P, workspaces: &[String], config: &ScanConfig) -> Result<Vec<WorkspacePackage>> {
+    let root_dir = match root_config.as_ref().parent().and_then(|dir| dir.to_str()) {
+        Some("") | None => ".".to_string(),
+        Some(dir) => crate::utils::normalize_path(dir),
+    };
+    let patterns: Vec<String> = workspaces
+        .iter()
+        .map(|workspace| format!("{}/{}/{{{}}}", root_dir, workspace.trim_end_matches('/'), CONFIG_FILE_NAMES.join(",")))
+        .collect();
+
+    // 同一目录只取优先级最高的配置文件
+    let mut found: BTreeMap<String, String> = BTreeMap::new();
+    let rank = |path: &str| CONFIG_FILE_NAMES.iter().position(|name| path.ends_with(name));
+    for file in resolve_files(&patterns, config) {
+        let dir = file.rsplit_once('/').map_or(".", |(dir, _)| dir).to_string();
+        if found.get(&dir).is_none_or(|existing| rank(&file) < rank(existing)) {
+            found.insert(dir, file);
+        }
+    }
+
+    let mut packages = Vec::new();
+    for (dir, config_path) in found {
+        let loaded = JsConfig::load(&config_path)?;
+        let name = relative_to(&root_dir, &dir);
+        packages.push(WorkspacePackage {
+            config: rebase_config(loaded.config.to_scan_config(), &dir),
+            diagnostics: loaded.check(),
+            name,
+            dir,
+            config_path,
+        });
+    }
+    Ok(packages)
+}
+
+fn relative_to(root: &str, path: &str) -> String {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if root == "." {
+        return path.to_string();
+    }
+    path.strip_prefix(&format!("{}/", root)).unwrap_or(path).to_string()
+}
+
+/// 把配置中的相对路径（`input`、含 `/` 的 `exclude` 与词条路径）转换为相对于 `dir`
+pub fn rebase_config(mut config: ScanConfig, dir: &str) -> ScanConfig {
+    config.input = config.input.iter().map(|pattern| join_path(dir, pattern)).collect();
+    config.exclude = config
+        .exclude
+        .iter()
+        .map(|pattern| if pattern.contains('/') { join_path(dir, pattern) } else { pattern.clone() })
+        .collect();
+    config.resource.save_path = join_path(dir, &config.resource.save_path);
+    config.resource.load_path = join_path(dir, &config.resource.load_path);
+    config
+}
+
+/// 拼接相对路径并消去其中的 `.` 与 `..`，`!` 开头的排除模式保留前缀
+fn join_path(dir: &str, path: &str) -> String {
+    if let Some(rest) = path.strip_prefix('!') {
+        return format!("!{}", join_path(dir, rest));
+    }
+    if path.is_empty() || Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in dir.split('/').chain(path.split('/')) {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    if dir.starts_with('/') {
+        format!("/{}", segments.join("/"))
+    } else if segments.is_empty() {
+        ".".to_string()
+    } else {
+        segments.join("/")
+    }
+}
+
+/// 一个包的扫描结果摘要
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageReport {
+    pub name: String,
+    pub config_path: String,
+    pub files_scanned: usize,
+    pub keys_found: usize,
+    pub errors_count: usize,
+    pub warnings_count: usize,
+    /// 写入的词条文件，未写入时为空
+    pub written: Vec<String>,
+    pub coverage: Vec<ResourceCoverage>,
+}
+
+/// 一种语言在所有包中的翻译覆盖率
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LanguageTotal {
+    pub lng: String,
+    pub total: usize,
+    pub translated: usize,
+}
+
+impl LanguageTotal {
+    /// 覆盖率百分比，没有词条时为 100
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// 工作区扫描的汇总报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct WorkspaceReport {
+    pub packages: Vec<PackageReport>,
+    pub totals: Vec<LanguageTotal>,
+}
+
+impl WorkspaceReport {
+    pub fn errors_count(&self) -> usize {
+        self.packages.iter().map(|package| package.errors_count).sum()
+    }
+
+    /// 便于阅读的表格：各包的扫描统计、各包的覆盖率与各语言的合计
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+
+        let rows: Vec<Vec<String>> = self
+            .packages
+            .iter()
+            .map(|package| {
+                vec![
+                    package.name.clone(),
+                    format!("{} files", package.files_scanned),
+                    format!("{} keys", package.keys_found),
+                    format!("{} errors", package.errors_count),
+                    format!("{} warnings", package.warnings_count),
+                ]
+            })
+            .collect();
+        push_section(&mut out, "Packages", &rows);
+
+        let rows: Vec<Vec<String>> = self
+            .packages
+            .iter()
+            .flat_map(|package| {
+                package.coverage.iter().map(move |item| {
+                    vec![
+                        package.name.clone(),
+                        item.lng.clone(),
+                        item.ns.clone(),
+                        format!("{}/{}", item.translated, item.total),
+                        format!("{:.1}%", item.percent()),
+                    ]
+                })
+            })
+            .collect();
+        push_section(&mut out, "Coverage", &rows);
+
+        let rows: Vec<Vec<String>> = self
+            .totals
+            .iter()
+            .map(|total| {
+                vec![
+                    total.lng.clone(),
+                    format!("{}/{}", total.translated, total.total),
+                    format!("{:.1}%", total.percent()),
+                ]
+            })
+            .collect();
+        push_section(&mut out, "Total", &rows);
+
+        if self.packages.is_empty() {
+            out.push_str("No packages\n");
+        }
+        out
+    }
+}
+
+/// 按各包自己的配置分别扫描，`write` 为 true 时写入各包的词条文件
+///
+/// 覆盖率按写入前的词条文件统计，与 `stats` 子命令一致。
+pub fn scan_workspace(packages: &[WorkspacePackage], write: bool) -> Result<WorkspaceReport> {
+    let mut report = WorkspaceReport::default();
+    let mut totals: BTreeMap<String, LanguageTotal> = BTreeMap::new();
+
+    for package in packages {
+        let files = resolve_files(&package.config.input, &package.config);
+        let result = Scanner::with_config(package.config.clone())
+            .scan_files(&files)
+            .map_err(|e| e.in_package(&package.name))?;
+        let coverage = resource_coverage(&package.config, &result).map_err(|e| e.in_package(&package.name))?;
+        let written = if write {
+            write_resources(&package.config, &result).map_err(|e| e.in_package(&package.name))?
+        } else {
+            Vec::new()
+        };
+
+        for item in &coverage {
+            let total = totals.entry(item.lng.clone()).or_insert_with(|| LanguageTotal {
+                lng: item.lng.clone(),
+                total: 0,
+                translated: 0,
+            });
+            total.total += item.total;
+            total.translated += item.translated;
+        }
+        report.packages.push(PackageReport {
+            name: package.name.clone(),
+            config_path: package.config_path.clone(),
+            files_scanned: result.stats.files_scanned,
+            keys_found: result.stats.keys_found,
+            errors_count: result.stats.errors_count,
+            warnings_count: result.stats.warnings_count,
+            written,
+            coverage,
+        });
+    }
+
+    report.totals = totals.into_values().collect();
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_join_path() {
+        assert_eq!(join_path("packages/app", "./src/**/*.ts"), "packages/app/src/**/*.ts");
+        assert_eq!(join_path("packages/app", "!src/legacy/**"), "!packages/app/src/legacy/**");
+        assert_eq!(
+            join_path("packages/app", "../../packages/admin-component/src/**"),
+            "packages/admin-component/src/**"
+        );
+        assert_eq!(join_path(".", "../shared/{{lng}}.json"), "../shared/{{lng}}.json");
+        assert_eq!(join_path("packages/app", "/abs/i18n"), "/abs/i18n");
+    }
+
+    #[test]
+    fn test_scan_workspace() {
+        let root = std::env::temp_dir().join(format!("workspace-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for dir in ["packages/admin/src", "packages/shop/src", "packages/docs", "packages/admin/node_modules/x"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(
+            root.join("i18n.config.js"),
+            "module.exports = {\n  input: ['src/**/*.js'],\n  output: 'i18n',\n  lngs: ['zh', 'en'],\n  defaultLng: 'zh',\n  workspaces: ['packages/*'],\n};\n",
+        )
+        .unwrap();
+        fs::write(root.join("packages/admin/i18n.config.js"), "module.exports = { extends: '../../i18n.config.js' };\n").unwrap();
+        fs::write(
+            root.join("packages/shop/i18n.config.json"),
+            "{ \"extends\": \"../../i18n.config.js\", \"lngs\": [\"zh\", \"en\", \"ja\"] }",
+        )
+        .unwrap();
+        fs::write(root.join("packages/admin/node_modules/x/i18n.config.js"), "broken").unwrap();
+        fs::write(root.join("packages/admin/src/a.js"), "t('save'); t('cancel');").unwrap();
+        fs::write(root.join("packages/shop/src/b.js"), "t('buy');").unwrap();
+        fs::create_dir_all(root.join("packages/shop/i18n")).unwrap();
+        let buy = crate::scan::hash_key_simple("buy", None);
+        fs::write(root.join("packages/shop/i18n/en.json"), format!("{{ \"{}\": \"Buy\" }}", buy)).unwrap();
+
+        let root_config = root.join("i18n.config.js");
+        let js_config = JsConfig::from_js_file(&root_config).unwrap();
+        let packages = discover_packages(&root_config, &js_config.workspaces, &js_config.to_scan_config()).unwrap();
+        let names: Vec<_> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["packages/admin", "packages/shop"]);
+        assert!(packages[0].config.input[0].ends_with("packages/admin/src/**/*.js"));
+        assert_eq!(packages[1].config.lngs, vec!["zh", "en", "ja"]);
+
+        let report = scan_workspace(&packages, true).unwrap();
+        assert_eq!(report.packages[0].keys_found, 2);
+        assert_eq!(report.packages[1].written.len(), 3);
+        assert!(root.join("packages/admin/i18n/en.json").exists());
+        let totals: Vec<_> = report.totals.iter().map(|t| (t.lng.as_str(), t.translated, t.total)).collect();
+        assert_eq!(totals, vec![("en", 1, 3), ("ja", 0, 1), ("zh", 3, 3)]);
+        assert!(report.to_table().starts_with("Packages (2)\n"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
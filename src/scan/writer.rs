@@ -0,0 +1,515 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use super::config::ScanConfig;
+use super::{context, namespace};
+use super::plural::{is_singular_suffix, plural_suffixes};
+use super::ScanResult;
+
+/// JS 词条文件模板，与 `demo/i18n/resource/lng.js.tpl` 保持一致
+const LNG_FILE_TPL: &str = r#"/* eslint-disable */
+/**
+ * @fileoverview
+ *
+ * 本文件词条由扫描工具生成，请勿手动编辑
+ */
+
+var translation = {
+__$$(translation)__
+};
+
+window.__i18n_translation = translation;
+
+module.exports = { translation: translation };
+"#;
+
+/// 一个语言、命名空间对应的词条文件
+#[derive(Debug, Clone)]
+pub struct ResourcePlan {
+    pub lng: String,
+    pub ns: String,
+    pub save_path: String,
+    /// 扫描结果对应的全部词条 key，包括尚未翻译的
+    pub keys: Vec<String>,
+    /// 按句子分组的词条 key，复数 key 的各个形式属于同一组
+    pub sentences: Vec<Vec<String>>,
+    /// 有值的词条：默认语言为默认值，其他语言为已有翻译
+    pub translation: BTreeMap<String, String>,
+    /// `load_path` 中已有的词条
+    pub existing: BTreeMap<String, String>,
+}
+
+/// 按配置计算各语言、各命名空间的词条，不写入文件
+///
+/// 词条 key 使用 `generated_keys` 中生成的 key；默认语言写入默认值，
+/// 其他语言保留 `load_path` 中已有的翻译，未翻译的词条不写入。
+/// 带 `count` 的 key 按各语言的复数类别展开为多个 key。
+/// 默认命名空间总会写入，其他命名空间有词条时才写入，此时 `save_path` 必须包含 `{{ns}}`。
+pub fn plan_resources(config: &ScanConfig, result: &ScanResult) -> Result<Vec<ResourcePlan>> {
+    let mut namespaces: BTreeMap<&str, &[String]> = result
+        .namespaces
+        .iter()
+        .map(|(ns, keys)| (ns.as_str(), keys.as_slice()))
+        .collect();
+    if result.namespaces.is_empty() {
+        namespaces.insert(&config.default_ns, &result.keys);
+    }
+    namespaces.entry(&config.default_ns).or_insert(&[]);
+
+    if namespaces.len() > 1 && !config.resource.save_path.contains("{{ns}}") {
+        return Err(Error::Config(format!(
+            "resource.savePath \"{}\" must contain {{{{ns}}}} to write namespaces: {}",
+            config.resource.save_path,
+            namespaces.keys().copied().collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    let plural_keys: HashSet<&str> = result.plural_keys.iter().map(|k| k.as_str()).collect();
+    let mut plans = Vec::new();
+    for (ns, keys) in namespaces {
+        for lng in &config.lngs {
+            let existing = read_resource(&config.load_path(lng, ns));
+            let mut resource_keys = Vec::new();
+            let mut sentences = Vec::new();
+            let mut translation = BTreeMap::new();
+
+            for key in keys {
+                let base_key = result.generated_keys.get(key).unwrap_or(key);
+                let default_value = match result.translations.get(key) {
+                    Some(translation) => translation.as_str(),
+                    None => context::strip_context(
+                        namespace::unqualify_key(config, ns, key),
+                        result.contexts.get(key).map(|context| context.as_str()),
+                    ),
+                };
+                let forms = if plural_keys.contains(key.as_str()) {
+                    // 复数 key 按语言的 CLDR 类别展开，单数以外的形式优先使用 defaultValue_plural
+                    plural_suffixes(lng, config.plural_style)
+                        .into_iter()
+                        .map(|suffix| {
+                            let value = match result.plural_translations.get(key) {
+                                Some(plural) if !is_singular_suffix(lng, config.plural_style, &suffix) => plural.as_str(),
+                                _ => default_value,
+                            };
+                            (format!("{}{}", base_key, suffix), value)
+                        })
+                        .collect()
+                } else {
+                    vec![(base_key.clone(), default_value)]
+                };
+
+                let mut sentence = Vec::new();
+                for (resource_key, default_value) in forms {
+                    let value = if *lng == config.default_lng {
+                        Some(default_value.to_string())
+                    } else {
+                        existing.get(&resource_key).filter(|v| !v.is_empty()).cloned()
+                    };
+                    if let Some(value) = value {
+                        translation.insert(resource_key.clone(), value);
+                    }
+                    sentence.push(resource_key);
+                }
+                resource_keys.extend(sentence.iter().cloned());
+                sentences.push(sentence);
+            }
+
+            plans.push(ResourcePlan {
+                lng: lng.clone(),
+                ns: ns.to_string(),
+                save_path: config.save_path(lng, ns),
+                keys: resource_keys,
+                sentences,
+                translation,
+                existing,
+            });
+        }
+    }
+
+    Ok(plans)
+}
+
+/// 按配置写入各语言、各命名空间的词条文件，返回写入的文件路径
+///
+/// 只写入扫描结果中的词条，已有文件中不再使用的词条会被移除，见 [`plan_resources`]。
+pub fn write_resources(config: &ScanConfig, result: &ScanResult) -> Result<Vec<String>> {
+    let mut written = Vec::new();
+    for plan in plan_resources(config, result)? {
+        save_resource(&plan.save_path, &plan.translation)?;
+        written.push(plan.save_path);
+    }
+    Ok(written)
+}
+
+/// 把扫描结果合并进已有的语言包，返回写入的文件路径
+///
+/// 与 [`write_resources`] 不同，已有文件中不再使用的词条会保留，可以之后用 [`prune_resources`] 清理。
+pub fn sync_resources(config: &ScanConfig, result: &ScanResult) -> Result<Vec<String>> {
+    let mut written = Vec::new();
+    for plan in plan_resources(config, result)? {
+        let mut merged = plan.existing;
+        merged.extend(plan.translation);
+        save_resource(&plan.save_path, &merged)?;
+        written.push(plan.save_path);
+    }
+    Ok(written)
+}
+
+/// 语言包中不再使用的词条
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct PrunedResource {
+    pub path: String,
+    pub removed: Vec<String>,
+}
+
+/// 从 `save_path` 的语言包中移除扫描结果里已不存在的词条，`dry_run` 时只返回将被移除的词条
+pub fn prune_resources(
+    config: &ScanConfig,
+    result: &ScanResult,
+    dry_run: bool,
+) -> Result<Vec<PrunedResource>> {
+    let mut pruned = Vec::new();
+    for plan in plan_resources(config, result)? {
+        let mut translation = read_resource(&plan.save_path);
+        let used: HashSet<&str> = plan.keys.iter().map(|k| k.as_str()).collect();
+        let removed: Vec<String> = translation.keys().filter(|k| !used.contains(k.as_str())).cloned().collect();
+        if removed.is_empty() {
+            continue;
+        }
+        if !dry_run {
+            translation.retain(|key, _| used.contains(key.as_str()));
+            save_resource(&plan.save_path, &translation)?;
+        }
+        pruned.push(PrunedResource {
+            path: plan.save_path,
+            removed,
+        });
+    }
+    Ok(pruned)
+}
+
+/// 一个语言、命名空间的翻译覆盖率，同一复数组的多个 key 按一个句子计算
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ResourceCoverage {
+    pub lng: String,
+    pub ns: String,
+    /// 句子数
+    pub total: usize,
+    /// 全部形式都已翻译的句子数
+    pub translated: usize,
+    /// 尚未翻译的词条 key，复数组中只列出缺少的形式
+    pub missing: Vec<String>,
+}
+
+impl ResourceCoverage {
+    /// 覆盖率百分比，没有词条时为 100
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated as f64 * 100.0 / self.total as f64
+        }
+    }
+}
+
+/// 按 `load_path` 中已有的翻译统计各语言、各命名空间的覆盖率
+pub fn resource_coverage(config: &ScanConfig, result: &ScanResult) -> Result<Vec<ResourceCoverage>> {
+    Ok(plan_resources(config, result)?
+        .into_iter()
+        .map(|plan| {
+            let mut translated = 0;
+            let mut missing = Vec::new();
+            for sentence in &plan.sentences {
+                let before = missing.len();
+                missing.extend(sentence.iter().filter(|key| !plan.translation.contains_key(key.as_str())).cloned());
+                if missing.len() == before {
+                    translated += 1;
+                }
+            }
+            ResourceCoverage {
+                lng: plan.lng,
+                ns: plan.ns,
+                total: plan.sentences.len(),
+                translated,
+                missing,
+            }
+        })
+        .collect())
+}
+
+fn save_resource(save_path: &str, translation: &BTreeMap<String, String>) -> Result<()> {
+    if let Some(parent) = Path::new(save_path).parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::file(parent, e))?;
+    }
+    fs::write(save_path, render_resource(save_path, translation)?).map_err(|e| Error::file(save_path, e))?;
+    Ok(())
+}
+
+/// 按扩展名生成文件内容：`.json` 输出 JSON 对象，其余使用 JS 模板
+fn render_resource(path: &str, translation: &BTreeMap<String, String>) -> Result<String> {
+    if path.ends_with(".json") {
+        return Ok(serde_json::to_string_pretty(translation)?);
+    }
+
+    let mut lines = Vec::new();
+    for (key, value) in translation {
+        lines.push(format!("  {}: {},", serde_json::to_string(key)?, serde_json::to_string(value)?));
+    }
+    Ok(LNG_FILE_TPL.replace("__$$(translation)__", &lines.join("\n")))
+}
+
+/// 读取已有词条文件，文件不存在或无法解析时返回空集合
+pub fn read_resource(path: &str) -> BTreeMap<String, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return BTreeMap::new(),
+    };
+
+    if path.ends_with(".json") {
+        return serde_json::from_str(&content).unwrap_or_default();
+    }
+
+    // JS 模板中每行一个词条：`  "key": "value", // 注释`
+    let mut translation = BTreeMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if !line.starts_with('"') {
+            continue;
+        }
+        let entry = format!("{{{}}}", line.trim_end_matches(|c| c != '"'));
+        if let Ok(map) = serde_json::from_str::<BTreeMap<String, String>>(&entry) {
+            translation.extend(map);
+        }
+    }
+    translation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn scan_result() -> ScanResult {
+        ScanResult {
+            keys: vec!["保存".to_string(), "save".to_string()],
+            translations: HashMap::from([("save".to_string(), "存储".to_string())]),
+            generated_keys: HashMap::from([
+                ("保存".to_string(), "k_a".to_string()),
+                ("save".to_string(), "k_b".to_string()),
+            ]),
+            namespaces: HashMap::new(),
+            contexts: HashMap::new(),
+            plural_keys: vec![],
+            plural_translations: HashMap::new(),
+            errors: vec![],
+            warnings: vec![],
+            stats: super::super::ScanStats {
+                files_scanned: 1,
+                keys_found: 2,
+                errors_count: 0,
+                warnings_count: 0,
+                processing_time_ms: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_write_js_resources_keeps_existing_translations() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("{{lng}}.js").to_string_lossy().to_string();
+        let config = ScanConfig::default()
+            .with_languages(vec!["zh".to_string(), "en".to_string()])
+            .with_resource(super::super::config::ResourceConfig {
+                load_path: String::new(),
+                save_path: save_path.clone(),
+            });
+
+        let en_path = save_path.replace("{{lng}}", "en");
+        fs::write(&en_path, "var translation = {\n  \"k_a\": \"Save\", // fallback from en\n};").unwrap();
+
+        let written = write_resources(&config, &scan_result()).unwrap();
+        assert_eq!(written.len(), 2);
+
+        let zh = read_resource(&save_path.replace("{{lng}}", "zh"));
+        assert_eq!(zh.get("k_a"), Some(&"保存".to_string()));
+        assert_eq!(zh.get("k_b"), Some(&"存储".to_string()));
+
+        let en = read_resource(&en_path);
+        assert_eq!(en.get("k_a"), Some(&"Save".to_string()));
+        assert!(!en.contains_key("k_b"));
+    }
+
+    #[test]
+    fn test_sync_prune_and_coverage() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("{{lng}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default()
+            .with_languages(vec!["zh".to_string(), "en".to_string()])
+            .with_resource(super::super::config::ResourceConfig {
+                load_path: String::new(),
+                save_path: save_path.clone(),
+            });
+        let en_path = save_path.replace("{{lng}}", "en");
+        fs::write(&en_path, r#"{ "k_a": "Save", "k_old": "Old" }"#).unwrap();
+
+        sync_resources(&config, &scan_result()).unwrap();
+        let en = read_resource(&en_path);
+        assert_eq!(en.keys().collect::<Vec<_>>(), vec!["k_a", "k_old"]);
+
+        let coverage = resource_coverage(&config, &scan_result()).unwrap();
+        let en_coverage = coverage.iter().find(|c| c.lng == "en").unwrap();
+        assert_eq!((en_coverage.translated, en_coverage.total), (1, 2));
+        assert_eq!(en_coverage.missing, vec!["k_b"]);
+        assert_eq!(en_coverage.percent(), 50.0);
+
+        let pruned = prune_resources(&config, &scan_result(), true).unwrap();
+        assert_eq!(pruned, vec![PrunedResource { path: en_path.clone(), removed: vec!["k_old".to_string()] }]);
+        assert!(read_resource(&en_path).contains_key("k_old"));
+
+        prune_resources(&config, &scan_result(), false).unwrap();
+        assert_eq!(read_resource(&en_path).keys().collect::<Vec<_>>(), vec!["k_a"]);
+    }
+
+    #[test]
+    fn test_write_json_resources() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("locales/{{lng}}/{{ns}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default().with_resource(super::super::config::ResourceConfig {
+            load_path: String::new(),
+            save_path: save_path.clone(),
+        });
+
+        let written = write_resources(&config, &scan_result()).unwrap();
+        let content = fs::read_to_string(&written[0]).unwrap();
+        let zh: BTreeMap<String, String> = serde_json::from_str(&content).unwrap();
+        assert_eq!(zh.get("k_b"), Some(&"存储".to_string()));
+        assert!(written[0].ends_with("zh/translation.json"));
+    }
+
+    #[test]
+    fn test_write_namespaces() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("{{lng}}/{{ns}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default().with_resource(super::super::config::ResourceConfig {
+            load_path: String::new(),
+            save_path: save_path.clone(),
+        });
+        let mut result = scan_result();
+        result.namespaces = HashMap::from([
+            ("translation".to_string(), vec!["保存".to_string()]),
+            ("common".to_string(), vec!["save".to_string()]),
+        ]);
+
+        let written = write_resources(&config, &result).unwrap();
+        assert_eq!(written.len(), 2);
+        let common = read_resource(&save_path.replace("{{lng}}", "zh").replace("{{ns}}", "common"));
+        assert_eq!(common.keys().collect::<Vec<_>>(), vec!["k_b"]);
+
+        let flat = config.with_resource(super::super::config::ResourceConfig {
+            load_path: String::new(),
+            save_path: temp_dir.path().join("{{lng}}.json").to_string_lossy().to_string(),
+        });
+        assert!(write_resources(&flat, &result).is_err());
+    }
+
+    #[test]
+    fn test_write_namespaced_sentences() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("app.js");
+        fs::write(&app, "t('common:取消');\nt('保存', { ns: 'common' });\nt('确定');\n").unwrap();
+        let save_path = temp_dir.path().join("{{lng}}/{{ns}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default()
+            .with_namespaces(vec!["translation".to_string(), "common".to_string()])
+            .with_resource(super::super::config::ResourceConfig {
+                load_path: String::new(),
+                save_path: save_path.clone(),
+            });
+
+        let mut scanner = super::super::Scanner::with_config(config.clone());
+        let result = scanner.scan_files(&[app.to_string_lossy().to_string()]).unwrap();
+        write_resources(&config, &result).unwrap();
+
+        let common = read_resource(&save_path.replace("{{lng}}", "zh").replace("{{ns}}", "common"));
+        let mut values: Vec<_> = common.values().cloned().collect();
+        values.sort();
+        assert_eq!(values, vec!["保存", "取消"]);
+        let translation = read_resource(&save_path.replace("{{lng}}", "zh").replace("{{ns}}", "translation"));
+        assert_eq!(translation.values().collect::<Vec<_>>(), vec!["确定"]);
+    }
+
+    #[test]
+    fn test_write_context_sentences() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = temp_dir.path().join("app.js");
+        fs::write(&app, "t('打开', { context: 'verb' });\nt('打开', { context: 'adj' });\nt('common:关闭', { context: 'verb' });\n").unwrap();
+        let save_path = temp_dir.path().join("{{lng}}/{{ns}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default().with_resource(super::super::config::ResourceConfig {
+            load_path: String::new(),
+            save_path: save_path.clone(),
+        });
+
+        let mut scanner = super::super::Scanner::with_config(config.clone());
+        let result = scanner.scan_files(&[app.to_string_lossy().to_string()]).unwrap();
+        write_resources(&config, &result).unwrap();
+
+        let zh = read_resource(&save_path.replace("{{lng}}", "zh").replace("{{ns}}", "translation"));
+        assert_eq!(zh.len(), 2);
+        assert!(zh.keys().all(|key| key.ends_with("_verb") || key.ends_with("_adj")));
+        assert!(zh.values().all(|value| value == "打开"));
+        let common = read_resource(&save_path.replace("{{lng}}", "zh").replace("{{ns}}", "common"));
+        assert_eq!(common.values().collect::<Vec<_>>(), vec!["关闭"]);
+    }
+
+    #[test]
+    fn test_write_plural_forms() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("{{lng}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default()
+            .with_languages(vec!["zh".to_string(), "en".to_string()])
+            .with_plural_style(crate::scan::plural::PluralSuffixStyle::V4)
+            .with_resource(super::super::config::ResourceConfig {
+                load_path: String::new(),
+                save_path: save_path.clone(),
+            });
+        let en_path = save_path.replace("{{lng}}", "en");
+        fs::write(&en_path, r#"{ "k_b_one": "{{count}} item", "k_b_other": "{{count}} items", "k_b": "stale" }"#).unwrap();
+
+        let mut result = scan_result();
+        result.plural_keys = vec!["save".to_string()];
+        write_resources(&config, &result).unwrap();
+
+        let zh = read_resource(&save_path.replace("{{lng}}", "zh"));
+        assert_eq!(zh.get("k_b_other"), Some(&"存储".to_string()));
+        assert!(!zh.contains_key("k_b"));
+
+        let en = read_resource(&en_path);
+        assert_eq!(en.get("k_b_one"), Some(&"{{count}} item".to_string()));
+        assert_eq!(en.get("k_b_other"), Some(&"{{count}} items".to_string()));
+        assert!(!en.contains_key("k_b"));
+    }
+
+    #[test]
+    fn test_coverage_counts_plural_group_once() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let save_path = temp_dir.path().join("{{lng}}.json").to_string_lossy().to_string();
+        let config = ScanConfig::default()
+            .with_languages(vec!["zh".to_string(), "en".to_string()])
+            .with_plural_style(crate::scan::plural::PluralSuffixStyle::V4)
+            .with_resource(super::super::config::ResourceConfig {
+                load_path: String::new(),
+                save_path: save_path.clone(),
+            });
+        fs::write(save_path.replace("{{lng}}", "en"), r#"{ "k_a": "Save", "k_b_one": "{{count}} item" }"#).unwrap();
+        let mut result = scan_result();
+        result.plural_keys = vec!["save".to_string()];
+
+        let coverage = resource_coverage(&config, &result).unwrap();
+        let zh = coverage.iter().find(|c| c.lng == "zh").unwrap();
+        let en = coverage.iter().find(|c| c.lng == "en").unwrap();
+        assert_eq!((zh.translated, zh.total), (2, 2));
+        assert_eq!((en.translated, en.total), (1, 2));
+        assert_eq!(en.missing, vec!["k_b_other"]);
+    }
+}
@@ -61,6 +61,7 @@ pub enum MessageId {
 }
 
 impl MessageId {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "bareZhInJs" => Some(MessageId::BareZhInJs),
@@ -384,6 +385,118 @@ impl ZhLinter {
     }
 }
 
+impl ZhLinter {
+    /// 检查 Vue、Angular 模板中未翻译的中文文本节点，跳过标签、注释与 `{{ }}` 插值
+    ///
+    /// `template` 需与原文件行列对应（见 `vue::mask_sfc`）。
+    pub fn verify_template(&self, template: &str, filepath: &str) {
+        self.verify_markup(template, filepath, Some(("{{", "}}")));
+    }
+
+    /// 检查标记中的中文文本节点，`interpolation` 为插值分隔符（如 Svelte 的 `{ }`，可以嵌套），
+    /// 静态 HTML 传 `None`
+    pub fn verify_markup(&self, template: &str, filepath: &str, interpolation: Option<(&str, &str)>) {
+        let mut ranges = Vec::new();
+        let mut text_start: Option<usize> = None;
+        let mut i = 0;
+
+        while i < template.len() {
+            let rest = &template[i..];
+            let skip_to = if rest.starts_with("<!--") {
+                Some(rest.find("-->").map_or(template.len(), |e| i + e + 3))
+            } else if let Some((open, close)) = interpolation.filter(|(open, _)| rest.starts_with(open)) {
+                Some(i + interpolation_len(rest, open, close))
+            } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+                Some(rest.find('>').map_or(template.len(), |e| i + e + 1))
+            } else {
+                None
+            };
+
+            match skip_to {
+                Some(next) => {
+                    if let Some(start) = text_start.take() {
+                        ranges.push(start..i);
+                    }
+                    i = next;
+                }
+                None => {
+                    text_start.get_or_insert(i);
+                    i += rest.chars().next().map_or(1, char::len_utf8);
+                }
+            }
+        }
+        if let Some(start) = text_start {
+            ranges.push(start..template.len());
+        }
+        self.verify_text_ranges(template, filepath, &ranges);
+    }
+
+    /// 报告包含中文的文本片段，位置为去掉首尾空白后的范围，值中的连续空白合并为一个空格
+    pub fn verify_text_ranges(&self, content: &str, filepath: &str, ranges: &[std::ops::Range<usize>]) {
+        let mut result = RESULT.lock().unwrap();
+        for range in ranges {
+            let text = &content[range.clone()];
+            if !self.zh_pattern.is_match(text) {
+                continue;
+            }
+            let trimmed = text.trim();
+            let start = range.start + (text.len() - text.trim_start().len());
+            result.push(LintResult {
+                filepath: filepath.to_string(),
+                loc: span_location(content, start, start + trimmed.len()),
+                value: trimmed.split_whitespace().collect::<Vec<_>>().join(" "),
+            });
+        }
+    }
+
+    /// 检查标签中包含中文的静态属性值，如 `<img alt="示意图">`，JSX 的 `{ }` 属性值跳过
+    pub fn verify_attributes(&self, content: &str, filepath: &str) {
+        let tag = Regex::new(r#"<[A-Za-z][\w:.-]*(?:\s+[^\s"'>/=]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|\{[^}]*\}|[^\s"'>{]+))?)*\s*/?>"#).unwrap();
+        let attribute = Regex::new(r#"\s[^\s"'>/=]+\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+        let mut ranges = Vec::new();
+        for mat in tag.find_iter(content) {
+            for captures in attribute.captures_iter(mat.as_str()) {
+                if let Some(value) = captures.get(1).or_else(|| captures.get(2)) {
+                    ranges.push(mat.start() + value.start()..mat.start() + value.end());
+                }
+            }
+        }
+        self.verify_text_ranges(content, filepath, &ranges);
+    }
+}
+
+/// 将字节范围转换为行列号范围
+fn span_location(content: &str, start: usize, end: usize) -> Location {
+    let (start_line, start_column) = super::parse_func_from_string_by_babel::offset_to_line_column(content, start);
+    let (end_line, end_column) = super::parse_func_from_string_by_babel::offset_to_line_column(content, end);
+    Location {
+        start: Position { line: start_line, column: start_column },
+        end: Position { line: end_line, column: end_column },
+    }
+}
+
+/// 从 `open` 开始到匹配的 `close` 的长度（含两端），未闭合时到末尾
+fn interpolation_len(s: &str, open: &str, close: &str) -> usize {
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < s.len() {
+        let rest = &s[i..];
+        if rest.starts_with(open) {
+            depth += 1;
+            i += open.len();
+        } else if rest.starts_with(close) {
+            depth -= 1;
+            i += close.len();
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    s.len()
+}
+
 /// 获取验证结果
 pub fn get_result() -> Vec<LintResult> {
     RESULT.lock().unwrap().clone()
@@ -453,6 +566,7 @@ mod tests {
 
     #[test]
     fn test_verify() {
+        let _guard = crate::scan::lock_scan();
         clear_results();
         let linter = ZhLinter::new();
         let content = r#"
@@ -486,4 +600,20 @@ const concat = "hello" + "world";
         assert_eq!(MessageId::BareZhInJs.as_str(), "bareZhInJs");
         assert_eq!(MessageId::from_str("unknown"), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_verify_template() {
+        let _guard = crate::scan::lock_scan();
+        let linter = ZhLinter::new();
+        let template = "\n  <p title=\"标题\">共 {{ count }} 项</p>\n  <!-- 注释 -->\n  <span>{{ $t('已翻译') }}</span>\n";
+        linter.verify_template(template, "template-test.vue");
+
+        let results: Vec<_> = get_result()
+            .into_iter()
+            .filter(|r| r.filepath == "template-test.vue")
+            .collect();
+        let values: Vec<_> = results.iter().map(|r| r.value.as_str()).collect();
+        assert_eq!(values, vec!["共", "项"]);
+        assert_eq!((results[0].loc.start.line, results[0].loc.start.column), (2, 17));
+    }
+}
@@ -7,12 +7,11 @@ pub use remove_i18n_key::*;
 use std::fs;
 use std::path::Path;
 
+use crate::error::{Error, Result};
+
 /// Read file content safely
-pub fn read_file_safe(filepath: &str) -> Result<String, Box<dyn std::error::Error>> {
-    match fs::read_to_string(filepath) {
-        Ok(content) => Ok(content),
-        Err(e) => Err(format!("Failed to read file {}: {}", filepath, e).into()),
-    }
+pub fn read_file_safe(filepath: &str) -> Result<String> {
+    fs::read_to_string(filepath).map_err(|e| Error::file(filepath, e))
 }
 
 /// Check if file exists
@@ -58,21 +57,21 @@ pub fn get_filename(filepath: &str) -> String {
 }
 
 /// Create directory if it doesn't exist
-pub fn ensure_directory(dirpath: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub fn ensure_directory(dirpath: &str) -> Result<()> {
     if !Path::new(dirpath).exists() {
-        fs::create_dir_all(dirpath)?;
+        fs::create_dir_all(dirpath).map_err(|e| Error::file(dirpath, e))?;
     }
     Ok(())
 }
 
 /// Write content to file safely
-pub fn write_file_safe(filepath: &str, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+pub fn write_file_safe(filepath: &str, content: &str) -> Result<()> {
     // Ensure parent directory exists
     if let Some(parent) = Path::new(filepath).parent() {
         ensure_directory(&parent.to_string_lossy())?;
     }
     
-    fs::write(filepath, content)?;
+    fs::write(filepath, content).map_err(|e| Error::file(filepath, e))?;
     Ok(())
 }
 
@@ -95,14 +94,14 @@ pub fn format_file_size(size: u64) -> String {
 }
 
 /// Get file size
-pub fn get_file_size(filepath: &str) -> Result<u64, Box<dyn std::error::Error>> {
-    let metadata = fs::metadata(filepath)?;
+pub fn get_file_size(filepath: &str) -> Result<u64> {
+    let metadata = fs::metadata(filepath).map_err(|e| Error::file(filepath, e))?;
     Ok(metadata.len())
 }
 
 /// Check if string contains Chinese characters
 pub fn contains_chinese(text: &str) -> bool {
-    text.chars().any(|c| '\u{4e00}' <= c && c <= '\u{9fff}')
+    text.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c))
 }
 
 /// Remove whitespace from string
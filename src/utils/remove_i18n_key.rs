@@ -1,6 +1,6 @@
 use reqwest::{Client, header::{HeaderMap, HeaderValue}};
 use serde_json::{json, Value};
-use std::error::Error;
+use crate::error::{Error, Result};
 
 pub struct RemoveI18nKeyConfig {
     pub module_id: u32,
@@ -9,14 +9,16 @@ pub struct RemoveI18nKeyConfig {
 }
 
 pub struct RemoveI18nKeyResult {
+    /// 已删除的词条 id
     pub ids: Vec<u32>,
+    /// 未找到 id 的 key
     pub unknowns: Vec<String>,
 }
 
 pub async fn remove_i18n_keys(
     config: RemoveI18nKeyConfig,
     keys: Vec<String>,
-) -> Result<RemoveI18nKeyResult, Box<dyn Error>> {
+) -> Result<RemoveI18nKeyResult> {
     let client = Client::new();
     let mut ids = Vec::new();
     let mut unknowns = Vec::new();
@@ -55,15 +57,10 @@ pub async fn remove_i18n_keys(
 
         match query_translation_id(&client, &headers, &query_body).await {
             Ok(id) => ids.push(id),
-            Err(_) => {
-                unknowns.push(key.clone());
-                eprintln!("Failed to find id for key: {}", key);
-            }
+            Err(_) => unknowns.push(key.clone()),
         }
     }
 
-    println!("未找到id的标识：{:?}", unknowns);
-
     // 如果有找到的 ids，执行删除操作
     if !ids.is_empty() {
         let delete_body = json!({
@@ -78,9 +75,7 @@ pub async fn remove_i18n_keys(
             "operate": config.operate
         });
 
-        if let Err(e) = delete_translations(&client, &headers, &delete_body).await {
-            eprintln!("Failed to delete translations: {}", e);
-        }
+        delete_translations(&client, &headers, &delete_body).await?;
     }
 
     Ok(RemoveI18nKeyResult { ids, unknowns })
@@ -90,9 +85,10 @@ async fn query_translation_id(
     client: &Client,
     headers: &HeaderMap,
     body: &Value,
-) -> Result<u32, Box<dyn Error>> {
+) -> Result<u32> {
+    let url = "https://lingo.woa.com/polaris/api/langpkg/queryTranslationList";
     let response = client
-        .post("https://lingo.woa.com/polaris/api/langpkg/queryTranslationList")
+        .post(url)
         .headers(headers.clone())
         .header("referer", "https://lingo.woa.com/")
         .json(body)
@@ -108,7 +104,10 @@ async fn query_translation_id(
         .and_then(|arr| arr.first())
         .and_then(|first| first.get("id"))
         .and_then(|id| id.as_u64())
-        .ok_or("Failed to extract id from response")?;
+        .ok_or_else(|| Error::Response {
+            url: url.to_string(),
+            message: "missing data.translations[0].id".to_string(),
+        })?;
 
     Ok(id as u32)
 }
@@ -117,7 +116,7 @@ async fn delete_translations(
     client: &Client,
     headers: &HeaderMap,
     body: &Value,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<()> {
     let response = client
         .post("https://lingo.woa.com/polaris/api/langpkg/deleteTranslation")
         .headers(headers.clone())
@@ -126,12 +125,7 @@ async fn delete_translations(
         .send()
         .await?;
 
-    if response.status().is_success() {
-        println!("Successfully deleted translations");
-    } else {
-        eprintln!("Failed to delete translations: {}", response.status());
-    }
-
+    response.error_for_status()?;
     Ok(())
 }
 
@@ -151,14 +145,9 @@ mod tests {
             // 在这里添加测试用的 key
         ];
 
-        match remove_i18n_keys(config, keys).await {
-            Ok(result) => {
-                println!("Found IDs: {:?}", result.ids);
-                println!("Unknown keys: {:?}", result.unknowns);
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-            }
-        }
+        // 没有 key 时不会发出请求
+        let result = remove_i18n_keys(config, keys).await.unwrap();
+        assert!(result.ids.is_empty());
+        assert!(result.unknowns.is_empty());
     }
 }
\ No newline at end of file